@@ -0,0 +1,202 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::commands::helps::add;
+use crate::commands::install::Install;
+use crate::commands::lock::Lock;
+use crate::core::catalog::Catalog;
+use crate::core::context::Context;
+use crate::core::ip::{Ip, PartialIpSpec};
+use crate::core::manifest::{self, IP_MANIFEST_FILE};
+use crate::core::pkgid::PkgPart;
+use crate::core::uuid::Uuid;
+use crate::core::version::AnyVersion;
+use crate::error::{Error, Hint};
+use crate::info;
+use crate::util::anyerror::Fault;
+use crate::util::filesystem;
+use std::fs;
+use std::path::PathBuf;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Add {
+    dev: bool,
+    path: Option<PathBuf>,
+    ip: Option<PartialIpSpec>,
+}
+
+/// The resolved entry to write into the manifest.
+struct Entry {
+    name: PkgPart,
+    version: String,
+    uuid: Option<Uuid>,
+    path: Option<String>,
+}
+
+impl Subcommand<Context> for Add {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(add::HELP))?;
+        Ok(Add {
+            // Flags
+            dev: cli.check(Arg::flag("dev"))?,
+            // Options
+            path: cli.get(Arg::option("path"))?,
+            // Arguments
+            ip: cli.get(Arg::positional("ip"))?,
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // relative paths on the command-line are resolved from where orbit was called
+        let caller_dir = std::env::current_dir()?;
+
+        // go to the ip directory
+        c.jump_to_working_ip()?;
+        let local_ip = Ip::load(c.get_ip_path().unwrap().clone(), true, false)?;
+
+        let entry = match &self.path {
+            Some(p) => self.resolve_path(&caller_dir, p, &local_ip)?,
+            None => match &self.ip {
+                Some(spec) => Self::resolve_spec(spec, c)?,
+                None => return Err(Error::AddWithoutIp)?,
+            },
+        };
+
+        if &entry.name == local_ip.get_man().get_ip().get_name() {
+            return Err(Error::CyclicDependencyIp(entry.name))?;
+        }
+
+        // edit the manifest
+        let man_path = local_ip.get_root().join(IP_MANIFEST_FILE);
+        let contents = fs::read_to_string(&man_path)?;
+        fs::write(
+            &man_path,
+            manifest::insert_dependency(
+                &contents,
+                &entry.name,
+                &entry.version,
+                entry.uuid.as_ref(),
+                entry.path.as_deref(),
+                self.dev,
+            )?,
+        )?;
+
+        // update the lockfile with the new dependency
+        Lock::run_after_edit(c, local_ip.get_root(), &man_path, &contents)?;
+        info!(
+            "added {} {} to {}",
+            entry.name,
+            entry.version,
+            match self.dev {
+                true => "dev-dependencies",
+                false => "dependencies",
+            }
+        );
+        Ok(())
+    }
+}
+
+impl Add {
+    /// Reads the ip found at `path` to use as a relative dependency.
+    fn resolve_path(
+        &self,
+        caller_dir: &PathBuf,
+        path: &PathBuf,
+        local_ip: &Ip,
+    ) -> Result<Entry, Fault> {
+        let dir = match caller_dir.join(path).canonicalize() {
+            Ok(d) => d,
+            Err(_) => return Err(Error::PathNotFound(path.clone()))?,
+        };
+        let ip = Ip::load(dir.clone(), false, false)?;
+        let name = ip.get_man().get_ip().get_name();
+        if let Some(spec) = &self.ip {
+            if spec.get_name() != name {
+                return Err(Error::DependencyNotAtPath(
+                    spec.get_name().clone(),
+                    path.clone(),
+                ))?;
+            }
+        }
+        let root = local_ip.get_root().canonicalize()?;
+        Ok(Entry {
+            name: name.clone(),
+            version: ip.get_man().get_ip().get_version().to_string(),
+            uuid: None,
+            path: Some(filesystem::into_std_str(filesystem::relative_path(
+                &root, &dir,
+            ))),
+        })
+    }
+
+    /// Finds the ip matching `spec` in the catalog, installing it if it is not
+    /// already installed.
+    ///
+    /// The version requirement is the one provided in `spec`, or else the latest
+    /// version of the ip.
+    fn resolve_spec(spec: &PartialIpSpec, c: &Context) -> Result<Entry, Fault> {
+        let catalog = Catalog::new()
+            .installations(c.get_cache_path())?
+            .downloads(c.get_downloads_path())?
+            .available(&c.get_config().get_channels())?;
+
+        let level = match catalog.translate_name(&spec.to_pkg_name())? {
+            Some(lvl) => lvl,
+            None => {
+                return Err(Error::IpNotFoundAnywhere(
+                    spec.to_string(),
+                    Hint::CatalogList,
+                ))?
+            }
+        };
+        let target = match level.get(true, true, spec.get_version()) {
+            Some(ip) => ip,
+            None => {
+                return Err(Error::VersionNotFound(
+                    spec.get_version().clone(),
+                    Hint::ShowVersions,
+                ))?
+            }
+        };
+        let version = match spec.get_version() {
+            AnyVersion::Specific(v) => v.to_string(),
+            AnyVersion::Latest => target.get_man().get_ip().get_version().to_string(),
+        };
+
+        // the dependency must be installed to be locked
+        if level.get_install(spec.get_version()).is_none() == true {
+            Install::install_from_catalog(
+                PartialIpSpec::new(
+                    spec.get_name().clone(),
+                    Some(target.get_uuid().clone()),
+                    target.get_man().get_ip().get_version().to_partial_version(),
+                ),
+                c,
+            )?;
+        }
+
+        Ok(Entry {
+            name: spec.get_name().clone(),
+            version: version,
+            uuid: spec.as_uuid().clone(),
+            path: None,
+        })
+    }
+}
@@ -18,24 +18,33 @@
 use super::plan;
 use super::plan::Plan;
 use crate::commands::helps::build;
+use crate::core::blueprint::Blueprint;
 use crate::core::blueprint::Scheme;
 use crate::core::catalog::Catalog;
 use crate::core::context::Context;
 use crate::core::fileset::Fileset;
+use crate::core::fingerprint::Fingerprint;
+use crate::core::fingerprint::FINGERPRINT_FILE;
+use crate::core::generic::{self, Combination, Generic};
 use crate::core::ip::Ip;
 use crate::core::lang::vhdl::token::Identifier;
 use crate::core::swap::StrSwapTable;
 use crate::core::target::Process;
 use crate::core::target::Target;
+use crate::core::watch;
 use crate::error::Error;
 use crate::error::LastError;
+use crate::util::anyerror::Fault;
 use crate::util::environment::EnvVar;
 use crate::util::environment::Environment;
+use crate::util::environment::DOT_ENV_FILE;
 use crate::util::environment::ORBIT_BLUEPRINT;
 use crate::util::environment::ORBIT_OUT_DIR;
 use crate::util::environment::ORBIT_TARGET;
 use crate::util::environment::ORBIT_TARGET_DIR;
+use crate::util::filesystem;
 use colored::Colorize;
+use std::path::PathBuf;
 
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
@@ -54,6 +63,9 @@ pub struct Build {
     args: Vec<String>,
     verbose: bool,
     filesets: Option<Vec<Fileset>>,
+    no_cache: bool,
+    watch: bool,
+    generics: Vec<Generic>,
 }
 
 impl Subcommand<Context> for Build {
@@ -66,6 +78,8 @@ impl Subcommand<Context> for Build {
             force: cli.check(Arg::flag("force"))?,
             all: cli.check(Arg::flag("all"))?,
             dirty: cli.check(Arg::flag("no-clean"))?,
+            no_cache: cli.check(Arg::flag("no-cache"))?,
+            watch: cli.check(Arg::flag("watch"))?,
             // Options
             top: cli.get(Arg::option("top").value("unit"))?,
             plan: cli.get(Arg::option("plan").value("format"))?,
@@ -73,6 +87,9 @@ impl Subcommand<Context> for Build {
             target_dir: cli.get(Arg::option("target-dir").value("dir"))?,
             command: cli.get(Arg::option("command").value("path"))?,
             filesets: cli.get_all(Arg::option("fileset").value("key=glob"))?,
+            generics: cli
+                .get_all(Arg::option("generic").value("name=value"))?
+                .unwrap_or_default(),
             // Remaining args
             args: cli.remainder()?,
         })
@@ -105,8 +122,9 @@ impl Subcommand<Context> for Build {
 
         let target = target.unwrap();
 
-        // coordinate the plan
-        let plan = target.coordinate_plan(&self.plan)?;
+        // determine the targets to execute before the selected target
+        let targets = c.get_config().get_targets();
+        let pipeline = target.resolve_pipeline(&targets)?;
 
         // verify running from an ip directory and enter ip's root directory
         c.jump_to_working_ip()?;
@@ -116,61 +134,531 @@ impl Subcommand<Context> for Build {
         // determine the build directory based on cli priority
         let default_target_dir = c.get_target_dir();
         let target_dir = self.target_dir.as_ref().unwrap_or(&default_target_dir);
-        let out_dir = target.get_name();
-
-        let output_path = working_ip.get_root().join(target_dir).join(out_dir);
-
-        // gather the catalog and resolve any missing dependencies
-        let catalog = Catalog::new()
-            .installations(c.get_cache_path())?
-            .downloads(c.get_downloads_path())?;
-        let catalog = plan::resolve_missing_deps(c, &working_ip, catalog, self.force)?;
-
-        // plan for the provided target
-        let blueprint_name = Plan::run(
-            &working_ip,
-            target_dir,
-            target,
-            catalog,
-            self.dirty == false,
-            self.force,
-            false,
-            self.all,
-            &None,
-            &self.top,
-            &self.filesets,
-            &plan,
-            false,
-            false,
-        )?
-        .unwrap_or_default();
-
-        let envs = Environment::new()
+
+        // execute the pipeline for every combination of generics
+        let run = |ip: &Ip, replan: bool| {
+            generic::sweep(&self.generics, target.get_name(), |combo| {
+                self.run(c, ip, target, &pipeline, target_dir, combo, replan)
+            })
+        };
+
+        match self.watch {
+            true => watch::repeat(working_ip, run),
+            false => run(&working_ip, true),
+        }
+    }
+}
+
+impl Build {
+    /// Executes each target of the `pipeline`, planning the design again only
+    /// when `replan` is `true`.
+    fn run(
+        &self,
+        c: &Context,
+        working_ip: &Ip,
+        target: &Target,
+        pipeline: &Vec<&Target>,
+        target_dir: &str,
+        generics: &Combination,
+        replan: bool,
+    ) -> Result<(), Fault> {
+        // the fingerprint of the last execution is only trusted when caching is allowed
+        let use_cache = self.force == false && self.no_cache == false && self.watch == false;
+
+        let mut blueprint_name = None;
+        let mut up_to_date = false;
+        let mut fingerprint = None;
+        for (i, &step) in pipeline.iter().enumerate() {
+            let is_last = i == pipeline.len() - 1;
+            // a shared pipeline uses the selected target's blueprint and output directory
+            let planner = match target.is_sharing() {
+                true => target,
+                false => step,
+            };
+            let out_dir = &generic::to_out_dir(planner.get_name(), &self.generics, generics);
+            let output_path = working_ip.get_root().join(target_dir).join(out_dir);
+
+            // plan for the step unless its blueprint is already shared
+            if blueprint_name.is_none() || target.is_sharing() == false {
+                // command-line options are only applied to the selected target
+                let plan = match planner == target {
+                    true => planner.coordinate_plan(&self.plan)?,
+                    false => planner.coordinate_plan(&None)?,
+                };
+
+                // reuse the blueprint from the last plan
+                if replan == false {
+                    Fingerprint::remove(&output_path)?;
+                    blueprint_name = Some(Blueprint::new(plan).get_filename());
+                } else {
+                    // gather the catalog and resolve any missing dependencies
+                    let catalog = Catalog::new()
+                        .installations(c.get_cache_path())?
+                        .downloads(c.get_downloads_path())?;
+                    let catalog = plan::resolve_missing_deps(c, &working_ip, catalog, self.force)?;
+
+                    // defer cleaning until the inputs are known to have changed
+                    let previous = match use_cache {
+                        true => Fingerprint::read(&output_path),
+                        false => None,
+                    };
+                    // an incremental blueprint relies on the outputs of the previous runs
+                    let clean =
+                        self.dirty == false && (plan.is_incremental() == false || self.force);
+
+                    // plan for the provided target
+                    let (name, files) = Plan::run(
+                        &working_ip,
+                        target_dir,
+                        planner,
+                        out_dir,
+                        catalog,
+                        clean && previous.is_none(),
+                        self.force,
+                        false,
+                        self.all,
+                        &None,
+                        &self.top,
+                        &self.filesets,
+                        generics,
+                        &plan,
+                        false,
+                        false,
+                    )?
+                    .unwrap_or_default();
+
+                    // summarize every input that affects the executions using this blueprint
+                    let steps = match target.is_sharing() {
+                        true => &pipeline[..],
+                        false => &pipeline[i..i + 1],
+                    };
+                    let current = self.fingerprint(
+                        c,
+                        &working_ip,
+                        steps,
+                        &pipeline[pipeline.len() - 1],
+                        target_dir,
+                        out_dir,
+                        &output_path,
+                        &name,
+                        &files,
+                    )?;
+
+                    up_to_date = previous.as_ref() == Some(&current.to_string());
+                    if up_to_date == false {
+                        if previous.is_some() && clean == true {
+                            Self::clean_output(&output_path, &name)?;
+                        }
+                        Fingerprint::remove(&output_path)?;
+                    }
+                    fingerprint = Some(current);
+                    blueprint_name = Some(name);
+                }
+            }
+
+            if up_to_date == true {
+                println!("info: target {} is up to date", step.get_name().green());
+                continue;
+            }
+
+            let envs = Self::environment(
+                c,
+                &working_ip,
+                step,
+                blueprint_name.as_ref().unwrap(),
+                target_dir,
+                out_dir,
+                &output_path,
+            )?;
+
+            // modify the target to update with the available
+            let swap_table = StrSwapTable::new().load_environment(&envs)?;
+            let step_target = step.clone().replace_vars_in_args(&swap_table);
+
+            // run the command from the output path
+            match pipeline.len() > 1 {
+                true => println!(
+                    "info: executing target {} ({}/{})",
+                    step_target.get_name().green(),
+                    i + 1,
+                    pipeline.len()
+                ),
+                false => println!("info: executing target {}", step_target.get_name().green()),
+            }
+            let (command, args) = match is_last {
+                true => (&self.command, &self.args[..]),
+                false => (&None, &[][..]),
+            };
+            match step_target.execute(command, args, self.verbose, &output_path, envs.into_map()) {
+                Ok(()) => (),
+                Err(e) => match pipeline.len() > 1 {
+                    true => Err(Error::TargetPipelineFailed(
+                        step.get_name().to_string(),
+                        target.get_name().to_string(),
+                        LastError(e.to_string()),
+                    ))?,
+                    false => Err(Error::TargetProcFailed(LastError(e.to_string())))?,
+                },
+            }
+
+            // remember the inputs once every step using this output directory succeeded
+            if target.is_sharing() == false || is_last == true {
+                if let Some(f) = fingerprint.as_ref() {
+                    f.write(&output_path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Collects the environment variables available to the `step`'s process.
+    fn environment(
+        c: &Context,
+        working_ip: &Ip,
+        step: &Target,
+        blueprint_name: &str,
+        target_dir: &str,
+        out_dir: &str,
+        output_path: &PathBuf,
+    ) -> Result<Environment, Fault> {
+        Ok(Environment::new()
             // read config.toml for setting any env variables
             .from_config(c.get_config())?
             // read ip manifest for env variables
-            .from_ip(&working_ip)?
-            .add(EnvVar::with(ORBIT_TARGET, target.get_name()))
-            .add(EnvVar::with(ORBIT_BLUEPRINT, &blueprint_name))
+            .from_ip(working_ip)?
+            .add(EnvVar::with(ORBIT_TARGET, step.get_name()))
+            .add(EnvVar::with(ORBIT_BLUEPRINT, blueprint_name))
             .add(EnvVar::with(ORBIT_TARGET_DIR, target_dir))
             .add(EnvVar::with(ORBIT_OUT_DIR, out_dir))
-            .from_env_file(&output_path)?;
-
-        // modify the target to update with the available
-        let swap_table = StrSwapTable::new().load_environment(&envs)?;
-        let target = target.clone().replace_vars_in_args(&swap_table);
-
-        // run the command from the output path
-        println!("info: executing target {}", target.get_name().green());
-        match target.execute(
-            &self.command,
-            &self.args,
-            self.verbose,
-            &output_path,
-            envs.into_map(),
-        ) {
-            Ok(()) => Ok(()),
-            Err(e) => Err(Error::TargetProcFailed(LastError(e.to_string())))?,
+            .from_env_file(output_path)?)
+    }
+
+    /// Computes the fingerprint for executing the `steps` from the blueprint
+    /// `blueprint_name` that references the `files`.
+    ///
+    /// The command-line overrides only affect the `last` step of the pipeline.
+    fn fingerprint(
+        &self,
+        c: &Context,
+        working_ip: &Ip,
+        steps: &[&Target],
+        last: &Target,
+        target_dir: &str,
+        out_dir: &str,
+        output_path: &PathBuf,
+        blueprint_name: &str,
+        files: &Vec<String>,
+    ) -> Result<Fingerprint, Fault> {
+        let mut fingerprint = Fingerprint::new()
+            .text("version", env!("CARGO_PKG_VERSION"))
+            .file(&filesystem::into_std_str(output_path.join(blueprint_name)));
+        for file in files {
+            fingerprint = fingerprint.file(file);
         }
+        for &step in steps {
+            let envs = Self::environment(
+                c,
+                working_ip,
+                step,
+                blueprint_name,
+                target_dir,
+                out_dir,
+                output_path,
+            )?;
+            let swap_table = StrSwapTable::new().load_environment(&envs)?;
+            let step_target = step.clone().replace_vars_in_args(&swap_table);
+            let (command, args) = match step == last {
+                true => (self.command.as_ref(), &self.args[..]),
+                false => (None, &[][..]),
+            };
+            fingerprint = fingerprint
+                .text("target", step.get_name())
+                .text("command", command.unwrap_or(step_target.get_command()))
+                .text(
+                    "args",
+                    &step_target
+                        .get_args()
+                        .into_iter()
+                        .chain(args.iter())
+                        .map(|a| a.as_str())
+                        .collect::<Vec<&str>>()
+                        .join("\t"),
+                )
+                .vars(&envs.into_map());
+        }
+        Ok(fingerprint)
+    }
+
+    /// Removes the previous execution's files from the output directory while
+    /// keeping the files that were just planned.
+    fn clean_output(output_path: &PathBuf, blueprint_name: &str) -> Result<(), Fault> {
+        for entry in std::fs::read_dir(output_path)? {
+            let path = entry?.path();
+            let name = path.file_name().unwrap_or_default();
+            if name == blueprint_name || name == DOT_ENV_FILE || name == FINGERPRINT_FILE {
+                continue;
+            }
+            match path.is_dir() {
+                true => std::fs::remove_dir_all(&path)?,
+                false => std::fs::remove_file(&path)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+    use tempfile::tempdir;
+
+    const TARGET: &str = r#"
+name = "sim"
+command = "true"
+plans = ["ninja"]
+ninja.vhdl = "ghdl -a --work=$library $in"
+"#;
+
+    fn build() -> Build {
+        Build {
+            target: Some(String::from("sim")),
+            list: false,
+            force: false,
+            dirty: false,
+            all: false,
+            command: None,
+            top: None,
+            plan: None,
+            target_dir: None,
+            args: Vec::new(),
+            verbose: false,
+            filesets: None,
+            no_cache: false,
+            watch: false,
+            generics: Vec::new(),
+        }
+    }
+
+    /// Writes an ip with a top-level entity that instantiates another entity
+    /// into the directory `root`.
+    fn write_ip(root: &PathBuf) -> Ip {
+        std::fs::write(
+            root.join("Orbit.toml"),
+            "[ip]\nname = \"gates\"\nversion = \"0.1.0\"\nuuid = \"0000000000000000000000000\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("and_gate.vhd"),
+            "entity and_gate is\nend entity;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("top.vhd"),
+            "entity top is\n  port (a : in bit);\nend entity;\n\narchitecture rtl of top is\nbegin\n  u0: entity work.and_gate;\nend architecture;\n",
+        )
+        .unwrap();
+        Ip::load(root.clone(), true, false).unwrap()
+    }
+
+    /// Creates a target that runs the shell `script` from the ip at `root`.
+    fn script(root: &PathBuf, name: &str, script: &str, extra: &str) -> Target {
+        Target::from_str(&format!(
+            "name = \"{}\"\ncommand = \"sh\"\nargs = [\"-c\", \"{}\"]\n{}",
+            name, script, extra
+        ))
+        .unwrap()
+        .root(root.clone())
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn pipeline_stops_on_failure() {
+        let c = Context::new().cache().unwrap().archive().unwrap();
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let ip = write_ip(&root);
+
+        let lint = script(&root, "lint", "exit 3", "");
+        let sim = script(&root, "sim", "touch ran", "requires = [\"lint\"]");
+        let result = build().run(
+            &c,
+            &ip,
+            &sim,
+            &vec![&lint, &sim],
+            "target",
+            &Combination::new(),
+            true,
+        );
+        // the failing step is reported along with the selected target
+        let err = result.unwrap_err();
+        match err.downcast_ref::<Error>() {
+            Some(Error::TargetPipelineFailed(step, target, _)) => {
+                assert_eq!(step, "lint");
+                assert_eq!(target, "sim");
+            }
+            _ => panic!("unexpected error: {}", err),
+        }
+        // the steps after the failure are not executed
+        assert_eq!(root.join("target").join("sim").join("ran").exists(), false);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn pipeline_shares_output() {
+        let c = Context::new().cache().unwrap().archive().unwrap();
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let ip = write_ip(&root);
+
+        let lint = script(&root, "lint", "echo $ORBIT_OUT_DIR > lint.txt", "");
+        let sim = script(
+            &root,
+            "sim",
+            "test -f lint.txt && echo $ORBIT_OUT_DIR > sim.txt",
+            "requires = [\"lint\"]\nshare = true",
+        );
+        build()
+            .run(
+                &c,
+                &ip,
+                &sim,
+                &vec![&lint, &sim],
+                "target",
+                &Combination::new(),
+                true,
+            )
+            .unwrap();
+        // every step runs from the selected target's output directory
+        let output_path = root.join("target").join("sim");
+        assert_eq!(root.join("target").join("lint").exists(), false);
+        assert_eq!(
+            std::fs::read_to_string(output_path.join("lint.txt")).unwrap(),
+            "sim\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(output_path.join("sim.txt")).unwrap(),
+            "sim\n"
+        );
+
+        // without sharing, each step uses its own output directory
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let ip = write_ip(&root);
+        let lint = script(&root, "lint", "echo $ORBIT_OUT_DIR > lint.txt", "");
+        let sim = script(&root, "sim", "true", "requires = [\"lint\"]");
+        build()
+            .run(
+                &c,
+                &ip,
+                &sim,
+                &vec![&lint, &sim],
+                "target",
+                &Combination::new(),
+                true,
+            )
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(root.join("target").join("lint").join("lint.txt")).unwrap(),
+            "lint\n"
+        );
+        assert_eq!(
+            root.join("target").join("sim").join("lint.txt").exists(),
+            false
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn config_change_invalidates_cache() {
+        let c = Context::new().cache().unwrap().archive().unwrap();
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let ip = write_ip(&root);
+
+        // count the executions outside of the ip so its checksum is unchanged
+        let log = tempdir().unwrap();
+        let runs = log.path().join("runs");
+        let run = |target: &Target| {
+            build()
+                .run(
+                    &c,
+                    &ip,
+                    target,
+                    &vec![target],
+                    "target",
+                    &Combination::new(),
+                    true,
+                )
+                .unwrap();
+            std::fs::read_to_string(&runs).unwrap().lines().count()
+        };
+
+        let append = |text: &str| format!("echo {} >> {}", text, runs.display());
+        let sim = script(&root, "sim", &append("a"), "");
+        assert_eq!(run(&sim), 1);
+        // nothing changed since the last execution
+        assert_eq!(run(&sim), 1);
+
+        // changing the target's arguments executes it again
+        let sim = script(&root, "sim", &append("b"), "");
+        assert_eq!(run(&sim), 2);
+        assert_eq!(run(&sim), 2);
+
+        // changing the target's blueprint executes it again
+        let sim = script(&root, "sim", &append("b"), "plans = [\"f\"]");
+        assert_eq!(run(&sim), 3);
+        assert_eq!(
+            root.join("target").join("sim").join("blueprint.f").exists(),
+            true
+        );
+        assert_eq!(run(&sim), 3);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn ninja_keeps_outputs() {
+        let c = Context::new().cache().unwrap().archive().unwrap();
+
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let ip = write_ip(&root);
+        let target = &Target::from_str(TARGET).unwrap().root(root.clone());
+        let cmd = build();
+        let run = || {
+            cmd.run(
+                &c,
+                &ip,
+                target,
+                &vec![target],
+                "target",
+                &Combination::new(),
+                true,
+            )
+            .unwrap()
+        };
+
+        // ninja records the analyzed files in the output directory
+        run();
+        let output_path = root.join("target").join("sim");
+        assert_eq!(output_path.join("blueprint.ninja").exists(), true);
+        let stamps = output_path.join("stamps").join("work");
+        std::fs::create_dir_all(&stamps).unwrap();
+        std::fs::write(stamps.join("and_gate.vhd.stamp"), "").unwrap();
+        std::fs::write(stamps.join("top.vhd.stamp"), "").unwrap();
+        std::fs::write(output_path.join(".ninja_log"), "").unwrap();
+        let before = Fingerprint::read(&output_path).unwrap();
+
+        // changing one file executes the target again without removing ninja's records
+        std::fs::write(
+            root.join("top.vhd"),
+            "entity top is\n  port (a : in bit);\nend entity;\n\narchitecture rtl of top is\nbegin\n  u1: entity work.and_gate;\nend architecture;\n",
+        )
+        .unwrap();
+        run();
+        assert_ne!(Fingerprint::read(&output_path).unwrap(), before);
+        assert_eq!(stamps.join("and_gate.vhd.stamp").exists(), true);
+        assert_eq!(stamps.join("top.vhd.stamp").exists(), true);
+        assert_eq!(output_path.join(".ninja_log").exists(), true);
     }
 }
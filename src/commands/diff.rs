@@ -0,0 +1,495 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::commands::helps::diff;
+use crate::commands::publish::Publish;
+use crate::core::catalog::{Catalog, IpLevel};
+use crate::core::compat::{self, Change};
+use crate::core::context::Context;
+use crate::core::ip::{Ip, Mapping, PartialIpSpec, UnitEntry};
+use crate::core::iparchive::IpArchive;
+use crate::core::manifest::Manifest;
+use crate::core::version::AnyVersion;
+use crate::error::{Error, Hint};
+use crate::util::anyerror::Fault;
+use crate::util::filesystem;
+use crate::warn;
+use serde_derive::Serialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Diff {
+    json: bool,
+    ip: PartialIpSpec,
+    from: AnyVersion,
+    to: AnyVersion,
+}
+
+impl Subcommand<Context> for Diff {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(diff::HELP))?;
+        Ok(Diff {
+            // Flags
+            json: cli.check(Arg::flag("json"))?,
+            // Arguments
+            ip: cli.require(Arg::positional("ip"))?,
+            from: cli.require(Arg::positional("from"))?,
+            to: cli
+                .get(Arg::positional("to"))?
+                .unwrap_or(AnyVersion::Latest),
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        let catalog = Catalog::new()
+            .installations(c.get_cache_path())?
+            .downloads(c.get_downloads_path())?
+            .available(&c.get_config().get_channels())?;
+
+        let level = match catalog.translate_name(&self.ip.to_pkg_name())? {
+            Some(lvl) => lvl,
+            None => {
+                return Err(Error::IpNotFoundAnywhere(
+                    self.ip.to_string(),
+                    Hint::CatalogList,
+                ))?
+            }
+        };
+
+        let channels: Vec<PathBuf> = c
+            .get_config()
+            .get_channels()
+            .values()
+            .map(|chan| chan.get_root().clone())
+            .collect();
+
+        let old = Snapshot::load(Self::select(level, &self.from)?, &channels)?;
+        let new = Snapshot::load(Self::select(level, &self.to)?, &channels)?;
+
+        let report = Report::new(self.ip.get_name().to_string(), &old, &new);
+        match self.json {
+            true => println!("{}", serde_json::to_string_pretty(&report)?),
+            false => {
+                for snap in [&old, &new] {
+                    if snap.files.is_none() == true {
+                        warn!(
+                            "files of version {} are unknown because it is only available from a channel",
+                            snap.version
+                        );
+                    }
+                    if snap.units.is_none() == true {
+                        warn!(
+                            "units of version {} are unknown because its channel does not list them",
+                            snap.version
+                        );
+                    }
+                }
+                print!("{}", report)
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Diff {
+    /// Finds the ip for the `version` from the highest catalog level that has it.
+    fn select<'a>(level: &'a IpLevel, version: &AnyVersion) -> Result<&'a Ip, Fault> {
+        match level
+            .get_install(version)
+            .or(level.get_download(version))
+            .or(level.get_available(version))
+        {
+            Some(ip) => Ok(ip),
+            None => Err(Error::VersionNotFound(version.clone(), Hint::ShowVersions))?,
+        }
+    }
+}
+
+/// The contents of a single version of an ip.
+struct Snapshot {
+    version: String,
+    /// The list of files, which is unknown for ips only available from a channel.
+    files: Option<Vec<String>>,
+    /// The list of units, which is unknown for ips published to a channel by
+    /// older versions of orbit.
+    units: Option<Vec<UnitEntry>>,
+    deps: BTreeMap<String, String>,
+    dev_deps: BTreeMap<String, String>,
+}
+
+impl Snapshot {
+    fn load(ip: &Ip, channels: &Vec<PathBuf>) -> Result<Self, Fault> {
+        let version = ip.get_man().get_ip().get_version().to_string();
+        let (files, units) = match ip.get_mapping() {
+            Mapping::Physical | Mapping::Relative(_) => {
+                (Some(Self::list_files(ip)), ip.list_units()?)
+            }
+            Mapping::Virtual(bytes) => {
+                let dir = tempfile::tempdir()?;
+                IpArchive::extract(&bytes, dir.path())?;
+                let extracted = Ip::load(dir.path().to_path_buf(), false, false)?;
+                (Some(Self::list_files(&extracted)), extracted.list_units()?)
+            }
+            // channels only hold the unit list that was recorded when publishing
+            Mapping::Imaginary => {
+                let units = channels
+                    .iter()
+                    .find_map(|root| {
+                        Ip::read_cache_metadata(&root.join(Publish::create_pointer_directory(ip)))
+                    })
+                    .map(|meta| meta.get_units().clone())
+                    .filter(|units| units.is_empty() == false);
+                (None, units)
+            }
+        };
+        Ok(Self {
+            version: version,
+            files: files,
+            units: units,
+            deps: Self::list_deps(ip.get_man(), false),
+            dev_deps: Self::list_deps(ip.get_man(), true),
+        })
+    }
+
+    /// Collects the files of the ip relative to its root directory.
+    fn list_files(ip: &Ip) -> Vec<String> {
+        let mut files: Vec<String> = ip
+            .gather_current_files()
+            .into_iter()
+            .map(|f| {
+                filesystem::into_std_str(filesystem::remove_base(ip.get_root(), &PathBuf::from(f)))
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Maps each dependency's name to its version requirement or path.
+    fn list_deps(man: &Manifest, dev: bool) -> BTreeMap<String, String> {
+        let deps = match dev {
+            true => man.get_dev_deps(),
+            false => man.get_deps(),
+        };
+        deps.iter()
+            .map(|(name, dep)| {
+                (
+                    name.to_string(),
+                    match dep.as_path() {
+                        Some(path) => filesystem::into_std_str(path.clone()),
+                        None => dep.get_version().to_string(),
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq, Default)]
+struct ListDiff {
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+impl ListDiff {
+    fn new(old: &Vec<String>, new: &Vec<String>) -> Self {
+        Self {
+            added: new
+                .iter()
+                .filter(|n| old.contains(n) == false)
+                .cloned()
+                .collect(),
+            removed: old
+                .iter()
+                .filter(|o| new.contains(o) == false)
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() == true && self.removed.is_empty() == true
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct InterfaceChange {
+    #[serde(flatten)]
+    change: Change,
+    bump: String,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct DependencyChange {
+    name: String,
+    dev: bool,
+    from: Option<String>,
+    to: Option<String>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+struct Report {
+    ip: String,
+    from: String,
+    to: String,
+    /// The smallest version bump required by the changes to public units.
+    bump: Option<String>,
+    files: Option<ListDiff>,
+    units: Option<ListDiff>,
+    interfaces: Vec<InterfaceChange>,
+    dependencies: Vec<DependencyChange>,
+}
+
+impl Report {
+    fn new(ip: String, old: &Snapshot, new: &Snapshot) -> Self {
+        let files = match (&old.files, &new.files) {
+            (Some(a), Some(b)) => Some(ListDiff::new(a, b)),
+            _ => None,
+        };
+
+        let (units, interfaces, bump) = match (&old.units, &new.units) {
+            (Some(a), Some(b)) => {
+                let mut units = ListDiff::default();
+                let mut interfaces = Vec::new();
+                for change in compat::compare_units(a, b) {
+                    match change {
+                        Change::UnitAdded(u) => units.added.push(u),
+                        Change::UnitRemoved(u) => units.removed.push(u),
+                        _ => interfaces.push(InterfaceChange {
+                            bump: change.get_bump().to_string(),
+                            change: change,
+                        }),
+                    }
+                }
+                let bump = compat::required_bump(&compat::compare(a, b));
+                (Some(units), interfaces, Some(bump.to_string()))
+            }
+            _ => (None, Vec::new(), None),
+        };
+
+        let mut dependencies = Self::compare_deps(&old.deps, &new.deps, false);
+        dependencies.append(&mut Self::compare_deps(&old.dev_deps, &new.dev_deps, true));
+
+        Self {
+            ip: ip,
+            from: old.version.clone(),
+            to: new.version.clone(),
+            bump: bump,
+            files: files,
+            units: units,
+            interfaces: interfaces,
+            dependencies: dependencies,
+        }
+    }
+
+    fn compare_deps(
+        old: &BTreeMap<String, String>,
+        new: &BTreeMap<String, String>,
+        dev: bool,
+    ) -> Vec<DependencyChange> {
+        let mut names: Vec<&String> = old.keys().chain(new.keys()).collect();
+        names.sort();
+        names.dedup();
+        names
+            .into_iter()
+            .filter(|n| old.get(*n) != new.get(*n))
+            .map(|n| DependencyChange {
+                name: n.clone(),
+                dev: dev,
+                from: old.get(n).cloned(),
+                to: new.get(n).cloned(),
+            })
+            .collect()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.files.as_ref().map_or(true, |f| f.is_empty() == true)
+            && self.units.as_ref().map_or(true, |u| u.is_empty() == true)
+            && self.interfaces.is_empty() == true
+            && self.dependencies.is_empty() == true
+    }
+}
+
+impl std::fmt::Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} {} -> {}", self.ip, self.from, self.to)?;
+        if self.is_empty() == true {
+            return writeln!(f, "\nno differences found");
+        }
+        if let Some(files) = self.files.as_ref().filter(|l| l.is_empty() == false) {
+            writeln!(f, "\nFiles:")?;
+            files
+                .added
+                .iter()
+                .try_for_each(|a| writeln!(f, "  + {}", a))?;
+            files
+                .removed
+                .iter()
+                .try_for_each(|r| writeln!(f, "  - {}", r))?;
+        }
+        if let Some(units) = self.units.as_ref().filter(|l| l.is_empty() == false) {
+            writeln!(f, "\nUnits:")?;
+            units
+                .added
+                .iter()
+                .try_for_each(|a| writeln!(f, "  + {}", a))?;
+            units
+                .removed
+                .iter()
+                .try_for_each(|r| writeln!(f, "  - {}", r))?;
+        }
+        if self.interfaces.is_empty() == false {
+            writeln!(f, "\nInterfaces:")?;
+            self.interfaces
+                .iter()
+                .try_for_each(|i| writeln!(f, "  ~ {} ({})", i.change, i.bump))?;
+        }
+        if self.dependencies.is_empty() == false {
+            writeln!(f, "\nDependencies:")?;
+            for dep in &self.dependencies {
+                let name = match dep.dev {
+                    true => format!("{} (dev)", dep.name),
+                    false => dep.name.clone(),
+                };
+                match (&dep.from, &dep.to) {
+                    (Some(a), Some(b)) => writeln!(f, "  ~ {} {} -> {}", name, a, b)?,
+                    (None, Some(b)) => writeln!(f, "  + {} {}", name, b)?,
+                    (Some(a), None) => writeln!(f, "  - {} {}", name, a)?,
+                    (None, None) => (),
+                }
+            }
+        }
+        if let Some(bump) = &self.bump {
+            writeln!(f, "\nrequired version bump: {}", bump)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::ip::{InterfaceItem, UnitInterface};
+
+    fn snapshot(
+        version: &str,
+        files: &[&str],
+        units: Vec<UnitEntry>,
+        deps: &[(&str, &str)],
+    ) -> Snapshot {
+        Snapshot {
+            version: version.to_string(),
+            files: Some(files.iter().map(|s| s.to_string()).collect()),
+            units: Some(units),
+            deps: deps
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            dev_deps: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn report_changes() {
+        let unit = |name: &str, ports: Vec<InterfaceItem>| {
+            UnitEntry::new(
+                name,
+                "verilog",
+                "module",
+                "public",
+                Some(UnitInterface::new(Vec::new(), ports)),
+            )
+        };
+        let old = snapshot(
+            "1.0.0",
+            &["alu.v", "fa.v"],
+            vec![
+                unit(
+                    "alu",
+                    vec![InterfaceItem::new("a", Some("input"), None, None)],
+                ),
+                unit("fa", vec![]),
+            ],
+            &[("gates", "1.0"), ("lib", "2")],
+        );
+        let new = snapshot(
+            "1.1.0",
+            &["alu.v", "mux.v"],
+            vec![
+                unit(
+                    "alu",
+                    vec![
+                        InterfaceItem::new("a", Some("input"), None, None),
+                        InterfaceItem::new("z", Some("output"), None, None),
+                    ],
+                ),
+                unit("mux", vec![]),
+            ],
+            &[("gates", "1.2"), ("fifo", "0.3")],
+        );
+        let report = Report::new(String::from("arith"), &old, &new);
+        assert_eq!(
+            report.files,
+            Some(ListDiff {
+                added: vec![String::from("mux.v")],
+                removed: vec![String::from("fa.v")],
+            })
+        );
+        assert_eq!(
+            report.units,
+            Some(ListDiff {
+                added: vec![String::from("mux")],
+                removed: vec![String::from("fa")],
+            })
+        );
+        assert_eq!(report.interfaces.len(), 1);
+        assert_eq!(
+            serde_json::to_string(&report.interfaces).unwrap(),
+            "[{\"unit\":\"alu\",\"kind\":\"port-added\",\"item\":\"z\",\"from\":null,\"to\":null,\"bump\":\"minor\"}]"
+        );
+        let change = Change::PortTypeChanged(
+            String::from("alu"),
+            String::from("a"),
+            String::from("logic"),
+            String::from("logic [7:0]"),
+        );
+        assert_eq!(
+            serde_json::to_string(&change).unwrap(),
+            "{\"unit\":\"alu\",\"kind\":\"port-type-changed\",\"item\":\"a\",\"from\":\"logic\",\"to\":\"logic [7:0]\"}"
+        );
+        assert_eq!(report.bump, Some(String::from("major")));
+        assert_eq!(
+            report
+                .dependencies
+                .iter()
+                .map(|d| (d.name.as_str(), d.from.as_deref(), d.to.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("fifo", None, Some("0.3")),
+                ("gates", Some("1.0"), Some("1.2")),
+                ("lib", Some("2"), None),
+            ]
+        );
+        assert_eq!(
+            Report::new(String::from("arith"), &old, &old).is_empty(),
+            true
+        );
+    }
+}
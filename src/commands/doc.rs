@@ -0,0 +1,350 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::tree::Tree;
+use crate::commands::helps::doc;
+use crate::core::algo;
+use crate::core::catalog::Catalog;
+use crate::core::context::Context;
+use crate::core::ip::Ip;
+use crate::core::lang::node::HdlNode;
+use crate::core::lang::reference::CompoundIdentifier;
+use crate::core::lang::{Lang, LangIdentifier, LangUnit};
+use crate::info;
+use crate::util::anyerror::Fault;
+use crate::util::graphmap::GraphMap;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Doc {
+    no_deps: bool,
+    output: Option<PathBuf>,
+}
+
+impl Subcommand<Context> for Doc {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(doc::HELP))?;
+        Ok(Doc {
+            // Flags
+            no_deps: cli.check(Arg::flag("no-deps"))?,
+            // Options
+            output: cli.get(Arg::option("output").value("dir"))?,
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // relative paths on the command-line are resolved from where orbit was called
+        let caller_dir = std::env::current_dir()?;
+
+        // go to the ip directory
+        c.jump_to_working_ip()?;
+
+        // get the ip manifest
+        let ip = Ip::load(c.get_ip_path().unwrap().clone(), true, false)?;
+
+        let output = match &self.output {
+            Some(dir) => caller_dir.join(dir),
+            None => ip.get_root().join(c.get_target_dir()).join("doc"),
+        };
+
+        // gather the catalog
+        let catalog = Catalog::new().installations(c.get_cache_path())?;
+
+        self.run(&ip, &catalog, &output, c)?;
+        info!("documentation created at: {:?}", output.join("index.md"));
+        Ok(())
+    }
+}
+
+/// The units of an ip that are documented.
+struct IpPages<'a> {
+    ip: &'a Ip,
+    units: HashMap<LangIdentifier, LangUnit>,
+}
+
+impl<'a> IpPages<'a> {
+    fn get_dir(&self) -> String {
+        self.ip.get_man().get_ip().get_name().to_string()
+    }
+
+    /// Checks if the unit has its own page.
+    fn has_page(&self, name: &LangIdentifier) -> bool {
+        match self.units.get(name) {
+            Some(u) => u.is_component() == true,
+            None => false,
+        }
+    }
+}
+
+impl Doc {
+    fn run(
+        &self,
+        target: &Ip,
+        catalog: &Catalog,
+        output: &PathBuf,
+        c: &Context,
+    ) -> Result<(), Fault> {
+        // the pages are written to files
+        colored::control::set_override(false);
+
+        let ip_graph = algo::compute_final_ip_graph(&target, &catalog)?;
+        let files = algo::build_ip_file_list(&ip_graph, &target);
+        let global_graph = Tree::build_graph(&files, true)?;
+
+        // private units are hidden from every ip's documentation
+        let mut pages = Vec::new();
+        for (_, node) in ip_graph.get_map().iter() {
+            let ip = node.as_ref().as_ip();
+            if self.no_deps == true && ip != target {
+                continue;
+            }
+            pages.push(IpPages {
+                ip: ip,
+                units: ip.collect_units(true, true)?,
+            });
+        }
+        pages.sort_by_key(|p| p.get_dir());
+
+        // list every documented ip on the front page
+        let mut index = String::from("# Documentation\n\n");
+        for page in &pages {
+            let spec = page.ip.get_man().get_ip().into_ip_spec();
+            index.push_str(&format!("- [{}]({}/index.md)\n", spec, page.get_dir()));
+        }
+        fs::create_dir_all(&output)?;
+        fs::write(output.join("index.md"), index)?;
+
+        for page in &pages {
+            let dir = output.join(page.get_dir());
+            fs::create_dir_all(&dir)?;
+
+            let mut units: Vec<&LangUnit> = page
+                .units
+                .values()
+                .filter(|u| u.is_component() == true)
+                .collect();
+            units.sort_by_key(|u| u.get_name().to_string());
+
+            let man = page.ip.get_man().get_ip();
+            let mut index = format!("# {}\n\nVersion {}\n\n", man.get_name(), man.get_version());
+            if let Some(text) = man.get_description() {
+                index.push_str(&format!("{}\n\n", text));
+            }
+            index.push_str("## Units\n\n| Unit | Language | Summary |\n| --- | --- | --- |\n");
+            for unit in &units {
+                let name = unit.get_name().to_string();
+                let summary = Self::read_description(unit)
+                    .into_iter()
+                    .next()
+                    .unwrap_or_default();
+                index.push_str(&format!(
+                    "| [{}]({}.md) | {} | {} |\n",
+                    name,
+                    name,
+                    unit.get_lang(),
+                    Self::escape(&summary)
+                ));
+                fs::write(
+                    dir.join(format!("{}.md", name)),
+                    Self::write_unit(unit, page.ip, &pages, &global_graph, c)?,
+                )?;
+            }
+            fs::write(dir.join("index.md"), index)?;
+        }
+        Ok(())
+    }
+
+    /// Creates the page documenting the interface and dependencies of `unit`.
+    fn write_unit(
+        unit: &LangUnit,
+        ip: &Ip,
+        pages: &Vec<IpPages>,
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        c: &Context,
+    ) -> Result<String, Fault> {
+        let name = unit.get_name();
+        let mut text = format!("# {}\n\n", name);
+        text.push_str(&format!(
+            "_{} {} in [{}](index.md)_\n\n",
+            unit.get_lang(),
+            match unit.get_lang() {
+                Lang::Vhdl => "entity",
+                _ => "module",
+            },
+            ip.get_man().get_ip().into_ip_spec()
+        ));
+        if unit.get_visibility().is_public() == false {
+            text.push_str(&format!(
+                "> This unit is {} and may not be used outside of its ip.\n\n",
+                unit.get_visibility()
+            ));
+        }
+        let description = Self::read_description(unit);
+        if description.is_empty() == false {
+            text.push_str(&format!("{}\n\n", description.join("\n")));
+        }
+
+        // the interface is read from the same data as `orbit get --json`
+        let (interface, instance) = match unit.get_lang() {
+            Lang::Vhdl => {
+                let entity = unit.get_vhdl_symbol().unwrap().as_entity().unwrap();
+                let lib = ip.get_hdl_library().as_vhdl_name().cloned();
+                (
+                    serde_json::to_value(entity)?,
+                    entity.into_instance(&None, &lib, &c.get_vhdl_format(), "", "", "", ""),
+                )
+            }
+            _ => {
+                let module = match unit.get_lang() {
+                    Lang::Verilog => unit.get_verilog_symbol().unwrap().as_module(),
+                    _ => unit.get_systemverilog_symbol().unwrap().as_module(),
+                }
+                .unwrap();
+                (
+                    serde_json::to_value(module)?,
+                    module.into_instance(&None, "", "", &c.get_sv_format()),
+                )
+            }
+        };
+        if let Some(table) = Self::into_table(&interface["generics"], false) {
+            text.push_str(&format!("## Generics\n\n{}\n", table));
+        }
+        if let Some(table) = Self::into_table(&interface["ports"], true) {
+            text.push_str(&format!("## Ports\n\n{}\n", table));
+        }
+        text.push_str(&format!(
+            "## Instantiation\n\n```{}\n{}\n```\n",
+            unit.get_lang(),
+            instance.trim_end()
+        ));
+
+        // list the units instantiated beneath this unit
+        let root = global_graph.get_map().iter().find(|(_, n)| {
+            let n = n.as_ref();
+            &n.get_symbol().get_name() == &name
+                && n.get_associated_files()
+                    .first()
+                    .is_some_and(|f| f.get_ip() == ip)
+        });
+        if let Some((_, root)) = root {
+            let tree = global_graph.get_graph().treeview(root.index());
+            if tree.len() > 1 {
+                text.push_str("\n## Dependencies\n\n");
+            }
+            for (twig, i) in tree.iter().skip(1) {
+                let mut depth = 0;
+                let mut x = twig;
+                while let Some(upper) = x.get_upper() {
+                    depth += 1;
+                    x = upper;
+                }
+                let node = global_graph.get_node_by_index(*i).unwrap().as_ref();
+                let dep_name = node.get_symbol().get_name();
+                // link to the page of the unit when one is created
+                let page = node.get_associated_files().first().and_then(|f| {
+                    pages
+                        .iter()
+                        .find(|p| p.ip == f.get_ip() && p.has_page(&dep_name))
+                });
+                let label = match page {
+                    Some(p) => format!("[{}](../{}/{}.md)", dep_name, p.get_dir(), dep_name),
+                    None => format!("`{}`", dep_name),
+                };
+                text.push_str(&format!("{}- {}\n", "  ".repeat(depth - 1), label));
+            }
+        }
+        Ok(text)
+    }
+
+    /// Returns the lines of the comment immediately preceding the unit's declaration.
+    fn read_description(unit: &LangUnit) -> Vec<String> {
+        let contents = fs::read_to_string(unit.get_source_file()).unwrap_or_default();
+        let marker = match unit.get_lang() {
+            Lang::Vhdl => "--",
+            _ => "//",
+        };
+        let mut lines: Vec<String> = contents
+            .lines()
+            .take(unit.get_position().line().saturating_sub(1))
+            .collect::<Vec<&str>>()
+            .into_iter()
+            .rev()
+            .map(|l| l.trim())
+            .take_while(|l| l.starts_with(marker))
+            .map(|l| l.trim_start_matches(marker).trim().to_string())
+            .collect();
+        lines.reverse();
+        lines
+    }
+
+    /// Writes the list of interface declarations as a markdown table.
+    fn into_table(items: &serde_json::Value, with_mode: bool) -> Option<String> {
+        let items = items.as_array().filter(|a| a.is_empty() == false)?;
+        let mut table = match with_mode {
+            true => String::from("| Name | Mode | Type | Default |\n| --- | --- | --- | --- |\n"),
+            false => String::from("| Name | Type | Default |\n| --- | --- | --- |\n"),
+        };
+        let field = |item: &serde_json::Value, key: &str| match item[key].as_str() {
+            Some(s) => format!("`{}`", Self::escape(s)),
+            None => String::new(),
+        };
+        for item in items {
+            table.push_str(&format!("| {} ", field(item, "identifier")));
+            if with_mode == true {
+                table.push_str(&format!("| {} ", item["mode"].as_str().unwrap_or_default()));
+            }
+            table.push_str(&format!(
+                "| {} | {} |\n",
+                field(item, "type"),
+                field(item, "default")
+            ));
+        }
+        Some(table)
+    }
+
+    /// Escapes characters that would break a markdown table cell.
+    fn escape(s: &str) -> String {
+        s.replace('|', "\\|")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn into_table() {
+        let ports = serde_json::json!([
+            {"identifier": "clk", "mode": "in", "type": "std_logic", "default": null},
+            {"identifier": "sel", "mode": "in", "type": "bit", "default": "a|b"},
+        ]);
+        assert_eq!(
+            Doc::into_table(&ports, true).unwrap(),
+            "| Name | Mode | Type | Default |
+| --- | --- | --- | --- |
+| `clk` | in | `std_logic` |  |
+| `sel` | in | `bit` | `a\\|b` |
+"
+        );
+        assert_eq!(Doc::into_table(&serde_json::json!([]), false), None);
+    }
+}
@@ -0,0 +1,419 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::plan::{self, Plan};
+use crate::commands::helps::exec;
+use crate::core::blueprint::{Blueprint, Scheme};
+use crate::core::catalog::Catalog;
+use crate::core::context::{self, Context};
+use crate::core::ip::Ip;
+use crate::core::lang::vhdl::token::Identifier;
+use crate::core::swap;
+use crate::core::swap::StrSwapTable;
+use crate::core::target::{Process, Target};
+use crate::error::{Error, LastError};
+use crate::util::anyerror::{AnyError, Fault};
+use crate::util::environment::{EnvVar, Environment};
+use crate::util::environment::{ORBIT_BLUEPRINT, ORBIT_OUT_DIR, ORBIT_TARGET, ORBIT_TARGET_DIR};
+use std::path::PathBuf;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Exec {
+    target: Option<String>,
+    plan: Option<Scheme>,
+    top: Option<Identifier>,
+    all: bool,
+    force: bool,
+    target_dir: Option<String>,
+    verbose: bool,
+    command: String,
+    args: Vec<String>,
+}
+
+impl Subcommand<Context> for Exec {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(exec::HELP))?;
+        Ok(Exec {
+            // Flags
+            verbose: cli.check(Arg::flag("verbose"))?,
+            force: cli.check(Arg::flag("force"))?,
+            all: cli.check(Arg::flag("all"))?,
+            // Options
+            top: cli.get(Arg::option("top").value("unit"))?,
+            plan: cli.get(Arg::option("plan").value("format"))?,
+            target: cli.get(Arg::option("target").value("name").switch('t'))?,
+            target_dir: cli.get(Arg::option("target-dir").value("dir"))?,
+            // Positionals
+            command: cli.require(Arg::positional("command"))?,
+            // Remaining args
+            args: cli.remainder()?,
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // relative paths on the command-line are resolved from where orbit was called
+        let caller_dir = std::env::current_dir()?;
+
+        // select the target whose output directory is used
+        let target = self.select_target(c)?;
+
+        // verify running from an ip directory and enter ip's root directory
+        c.jump_to_working_ip()?;
+
+        let working_ip = Ip::load(c.get_ip_path().unwrap().to_path_buf(), true, false)?;
+
+        let (program, cwd, envs) = self.prepare(c, &working_ip, target, caller_dir)?;
+
+        match program.execute(&None, &[], self.verbose, &cwd, envs.into_map()) {
+            Ok(()) => Ok(()),
+            Err(e) => Err(Error::CommandProcFailed(
+                self.command.clone(),
+                LastError(e.to_string()),
+            ))?,
+        }
+    }
+}
+
+impl Exec {
+    /// Selects the target whose output directory the command runs from, which is
+    /// only used when a target is explicitly given.
+    fn select_target<'c>(&self, c: &'c Context) -> Result<Option<&'c Target>, Fault> {
+        let target = match &self.target {
+            Some(_) => c.select_target(&self.target, true, true)?,
+            None => None,
+        };
+        if target.is_none() == true && self.plan.is_some() == true {
+            return Err(AnyError(format!(
+                "planning a blueprint requires a target to be set with \"--target\""
+            )))?;
+        }
+        Ok(target)
+    }
+
+    /// Prepares the command to run within the `working_ip`, returning the process,
+    /// the directory to run it from, and its environment.
+    fn prepare(
+        &self,
+        c: &Context,
+        working_ip: &Ip,
+        target: Option<&Target>,
+        caller_dir: PathBuf,
+    ) -> Result<(Program, PathBuf, Environment), Fault> {
+        // determine the build directory based on cli priority
+        let default_target_dir = c.get_target_dir();
+        let target_dir = self.target_dir.as_ref().unwrap_or(&default_target_dir);
+
+        let mut envs = Environment::new()
+            // read config.toml for setting any env variables
+            .from_config(c.get_config())?
+            // read ip manifest for env variables
+            .from_ip(working_ip)?
+            .add(EnvVar::with(ORBIT_TARGET_DIR, target_dir));
+
+        // the process runs from the target's output directory, otherwise the ip's root
+        let cwd = match target {
+            Some(target) => {
+                let target_path = working_ip.get_root().join(target_dir);
+                let output_path = target_path.join(target.get_name());
+                let blueprint_name = match &self.plan {
+                    Some(_) => {
+                        // gather the catalog and resolve any missing dependencies
+                        let catalog = Catalog::new()
+                            .installations(c.get_cache_path())?
+                            .downloads(c.get_downloads_path())?;
+                        let catalog =
+                            plan::resolve_missing_deps(c, working_ip, catalog, self.force)?;
+
+                        Plan::run(
+                            working_ip,
+                            target_dir,
+                            target,
+                            target.get_name(),
+                            catalog,
+                            true,
+                            self.force,
+                            false,
+                            self.all,
+                            &None,
+                            &self.top,
+                            &None,
+                            &Vec::new(),
+                            &target.coordinate_plan(&self.plan)?,
+                            false,
+                            false,
+                        )?
+                        .map(|(name, _)| name)
+                    }
+                    // use the blueprint from the target's last plan, if it exists
+                    None => {
+                        let name = Blueprint::new(target.coordinate_plan(&None)?).get_filename();
+                        match output_path.join(&name).exists() {
+                            true => Some(name),
+                            false => None,
+                        }
+                    }
+                };
+
+                // create the output directory if the target was never planned
+                if output_path.exists() == false {
+                    std::fs::create_dir_all(&output_path)?;
+                }
+                if let Err(tag) = Context::is_cache_tag_valid(&target_path) {
+                    std::fs::write(&tag, context::CACHE_TAG)?;
+                }
+
+                envs = envs
+                    .add(EnvVar::with(ORBIT_TARGET, target.get_name()))
+                    .add(EnvVar::with(ORBIT_OUT_DIR, target.get_name()));
+                if let Some(name) = blueprint_name {
+                    envs = envs.add(EnvVar::with(ORBIT_BLUEPRINT, &name));
+                }
+                envs = envs.from_env_file(&output_path)?;
+                output_path
+            }
+            None => working_ip.get_root().clone(),
+        };
+
+        // perform variable substitution on the command and its arguments
+        let swap_table = StrSwapTable::new().load_environment(&envs)?;
+        let program = Program {
+            root: caller_dir,
+            command: swap::substitute(self.command.clone(), &swap_table),
+            args: self
+                .args
+                .iter()
+                .map(|a| swap::substitute(a.clone(), &swap_table))
+                .collect(),
+        };
+        Ok((program, cwd, envs))
+    }
+}
+
+/// An arbitrary command entered on the command-line.
+struct Program {
+    root: PathBuf,
+    command: String,
+    args: Vec<String>,
+}
+
+impl Process for Program {
+    fn get_root(&self) -> &PathBuf {
+        &self.root
+    }
+
+    fn get_command(&self) -> &String {
+        &self.command
+    }
+
+    fn get_args(&self) -> Vec<&String> {
+        self.args.iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::config::Config;
+    use crate::util::filesystem;
+    use std::str::FromStr;
+    use tempfile::tempdir;
+
+    const CONFIG: &str = r#"
+[env]
+greeting = "hello"
+
+[[target]]
+name = "sim"
+command = "python"
+"#;
+
+    fn exec(target: Option<&str>, plan: Option<Scheme>, command: &str, args: &[&str]) -> Exec {
+        Exec {
+            target: target.map(|t| t.to_string()),
+            plan: plan,
+            top: None,
+            all: false,
+            force: false,
+            target_dir: None,
+            verbose: false,
+            command: command.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    fn context() -> Context {
+        let mut c = Context::new();
+        *c.get_config_mut() = Config::from_str(CONFIG).unwrap();
+        c
+    }
+
+    /// Copies an ip into a temporary directory so its output directories can be created.
+    fn working_ip(dir: &PathBuf) -> Ip {
+        filesystem::copy(&PathBuf::from("./tests/t5"), dir, false, None).unwrap();
+        Ip::load(dir.clone(), true, false).unwrap()
+    }
+
+    #[test]
+    fn select_target() {
+        let c = context();
+        assert_eq!(
+            exec(None, None, "ls", &[])
+                .select_target(&c)
+                .unwrap()
+                .is_none(),
+            true
+        );
+        assert_eq!(
+            exec(Some("sim"), None, "ls", &[])
+                .select_target(&c)
+                .unwrap()
+                .unwrap()
+                .get_name(),
+            "sim"
+        );
+        assert_eq!(
+            exec(Some("syn"), None, "ls", &[])
+                .select_target(&c)
+                .is_err(),
+            true
+        );
+        // a blueprint can only be planned for a target
+        let err = exec(None, Some(Scheme::Tsv), "ls", &[])
+            .select_target(&c)
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "planning a blueprint requires a target to be set with \"--target\""
+        );
+    }
+
+    #[test]
+    fn prepare_without_target() {
+        let c = context();
+        let dir = tempdir().unwrap();
+        let ip = working_ip(&dir.path().to_path_buf());
+        let cmd = exec(
+            None,
+            None,
+            "echo",
+            &["{{ orbit.ip.name }}", "{{ orbit.env.greeting }}"],
+        );
+        let (program, cwd, envs) = cmd.prepare(&c, &ip, None, PathBuf::from("caller")).unwrap();
+        // the command runs from the ip's root
+        assert_eq!(&cwd, ip.get_root());
+        assert_eq!(program.command, "echo");
+        assert_eq!(program.args, vec!["vhdl", "hello"]);
+        assert_eq!(envs.get(ORBIT_TARGET).is_none(), true);
+        assert_eq!(envs.get(ORBIT_TARGET_DIR).unwrap().get_value(), "target");
+    }
+
+    #[test]
+    fn prepare_with_target() {
+        let c = context();
+        let dir = tempdir().unwrap();
+        let ip = working_ip(&dir.path().to_path_buf());
+        let cmd = exec(
+            Some("sim"),
+            None,
+            "{{ orbit.target }}.sh",
+            &["{{ orbit.blueprint }}"],
+        );
+        let target = cmd.select_target(&c).unwrap();
+
+        // the output directory is created when the target was never planned
+        let (program, cwd, envs) = cmd
+            .prepare(&c, &ip, target, PathBuf::from("caller"))
+            .unwrap();
+        assert_eq!(cwd, ip.get_root().join("target").join("sim"));
+        assert_eq!(cwd.exists(), true);
+        assert_eq!(envs.get(ORBIT_TARGET).unwrap().get_value(), "sim");
+        assert_eq!(envs.get(ORBIT_BLUEPRINT).is_none(), true);
+        assert_eq!(program.command, "sim.sh");
+        // unknown variables are left as they are
+        assert_eq!(program.args, vec!["{{ orbit.blueprint }}"]);
+
+        // the blueprint from the target's last plan is used
+        std::fs::write(cwd.join("blueprint.tsv"), "").unwrap();
+        let (program, _, envs) = cmd
+            .prepare(&c, &ip, target, PathBuf::from("caller"))
+            .unwrap();
+        assert_eq!(
+            envs.get(ORBIT_BLUEPRINT).unwrap().get_value(),
+            "blueprint.tsv"
+        );
+        assert_eq!(program.args, vec!["blueprint.tsv"]);
+    }
+
+    #[test]
+    fn prepare_with_plan() {
+        let mut c = Context::new().cache().unwrap().archive().unwrap();
+        *c.get_config_mut() = Config::from_str(CONFIG).unwrap();
+        let dir = tempdir().unwrap();
+        let ip = working_ip(&dir.path().to_path_buf());
+        let target = Target::from_str("name = \"sim\"\ncommand = \"python\"")
+            .unwrap()
+            .root(ip.get_root().clone());
+        let mut cmd = exec(
+            Some("sim"),
+            Some(Scheme::Tsv),
+            "cat",
+            &["{{ orbit.blueprint }}"],
+        );
+        cmd.top = Some(Identifier::from_str("if_gen").unwrap());
+
+        // the blueprint is planned before the command runs
+        let (program, cwd, envs) = cmd
+            .prepare(&c, &ip, Some(&target), PathBuf::from("caller"))
+            .unwrap();
+        assert_eq!(cwd.join("blueprint.tsv").exists(), true);
+        assert_eq!(
+            envs.get(ORBIT_BLUEPRINT).unwrap().get_value(),
+            "blueprint.tsv"
+        );
+        assert_eq!(envs.get(ORBIT_OUT_DIR).unwrap().get_value(), "sim");
+        assert_eq!(program.args, vec!["blueprint.tsv"]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn run_within_environment() {
+        let c = context();
+        let dir = tempdir().unwrap();
+        let ip = working_ip(&dir.path().to_path_buf());
+        let cmd = exec(
+            None,
+            None,
+            "sh",
+            &[
+                "-c",
+                "echo \"$ORBIT_IP_NAME {{ orbit.env.greeting }}\" > $ORBIT_TARGET_DIR.txt",
+            ],
+        );
+        let (program, cwd, envs) = cmd.prepare(&c, &ip, None, PathBuf::from("caller")).unwrap();
+        program
+            .execute(&None, &[], false, &cwd, envs.into_map())
+            .unwrap();
+        // the process sees the ip's environment and runs from the ip's root
+        assert_eq!(
+            std::fs::read_to_string(ip.get_root().join("target.txt")).unwrap(),
+            "vhdl hello\n"
+        );
+    }
+}
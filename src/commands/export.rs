@@ -0,0 +1,187 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::commands::helps::export;
+use crate::commands::plan::Plan;
+use crate::core::catalog::Catalog;
+use crate::core::context::Context;
+use crate::core::fusesoc;
+use crate::core::ip::Ip;
+use crate::core::ipxact::Component;
+use crate::core::lang::vhdl::token::Identifier;
+use crate::core::lang::Lang;
+use crate::error::{Error, Hint};
+use crate::util::anyerror::{AnyError, Fault};
+use crate::util::filesystem;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+use colored::Colorize;
+
+const DEFAULT_VENDOR: &str = "orbit";
+
+#[derive(Debug, PartialEq)]
+pub enum Format {
+    IpXact,
+    Core,
+}
+
+impl FromStr for Format {
+    type Err = AnyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ipxact" => Ok(Self::IpXact),
+            "core" => Ok(Self::Core),
+            _ => Err(AnyError(format!(
+                "export formats are: \"ipxact\", \"core\""
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Export {
+    format: Format,
+    top: Option<Identifier>,
+    vendor: Option<String>,
+}
+
+impl Subcommand<Context> for Export {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(export::HELP))?;
+        Ok(Self {
+            top: cli.get(Arg::option("top").value("unit"))?,
+            vendor: cli.get(Arg::option("vendor").value("name"))?,
+            format: cli.require(Arg::positional("format"))?,
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // go to the ip directory
+        c.jump_to_working_ip()?;
+
+        // get the ip manifest
+        let ip = Ip::load(c.get_ip_path().unwrap().clone(), true, false)?;
+
+        // gather the catalog
+        let catalog = Catalog::new().installations(c.get_cache_path())?;
+
+        self.run(&ip, &catalog)
+    }
+}
+
+impl Export {
+    fn run(&self, ip: &Ip, catalog: &Catalog) -> Result<(), Fault> {
+        match self.format {
+            Format::IpXact => self.export_ipxact(ip, catalog),
+            Format::Core => self.export_core(ip, catalog),
+        }
+    }
+
+    /// Writes a FuseSoC CAPI2 core file describing the ip's top-level unit to stdout.
+    fn export_core(&self, ip: &Ip, catalog: &Catalog) -> Result<(), Fault> {
+        let (top, files) = Plan::compute_file_order(ip, catalog, &self.top)?;
+
+        let pkg = ip.get_man().get_ip();
+        let vlnv = format!(
+            "{}:{}:{}:{}",
+            self.vendor.as_deref().unwrap_or(DEFAULT_VENDOR),
+            ip.get_hdl_library(),
+            pkg.get_name(),
+            pkg.get_version()
+        );
+        let files = files
+            .iter()
+            .map(|(lib, file)| (lib.to_string(), Self::relative_path(ip, file)))
+            .collect();
+        print!(
+            "{}",
+            fusesoc::write_core(
+                &vlnv,
+                pkg.get_description().as_ref(),
+                &files,
+                &top.to_string()
+            )
+        );
+        Ok(())
+    }
+
+    /// Writes an IP-XACT component describing the ip's top-level unit to stdout.
+    fn export_ipxact(&self, ip: &Ip, catalog: &Catalog) -> Result<(), Fault> {
+        let (top, files) = Plan::compute_file_order(ip, catalog, &self.top)?;
+
+        let unit = match ip.collect_units(true, false)?.remove(&top) {
+            Some(u) => u,
+            None => return Err(AnyError(format!("failed to find unit \"{}\"", top)))?,
+        };
+
+        let pkg = ip.get_man().get_ip();
+        let library = ip.get_hdl_library().to_string();
+        let component = Component::new(
+            self.vendor.as_deref().unwrap_or(DEFAULT_VENDOR),
+            &library,
+            &pkg.get_name().to_string(),
+            &pkg.get_version().to_string(),
+        )
+        .description(pkg.get_description().as_ref());
+
+        // only entities and modules have an interface to describe
+        let entity = unit.get_vhdl_symbol().and_then(|s| s.as_entity());
+        let module = match unit.get_lang() {
+            Lang::Verilog => unit.get_verilog_symbol().and_then(|s| s.as_module()),
+            _ => unit.get_systemverilog_symbol().and_then(|s| s.as_module()),
+        };
+        let mut component = match (entity, module) {
+            (Some(e), _) => component.entity(e, &library),
+            (None, Some(m)) => component.module(m, unit.get_lang(), &library),
+            (None, None) => {
+                return Err(Error::GetUnitNotComponent(
+                    top.to_string(),
+                    Hint::ShowAvailableUnitsLocal,
+                ))?
+            }
+        };
+
+        for (lib, file) in &files {
+            component.add_file(&Self::relative_path(ip, file), &lib.to_string());
+        }
+        // report to stderr to keep the generated xml clean on stdout
+        for name in component.get_unset_parameters() {
+            eprintln!(
+                "{}: skipping parameter \"{}\" of unit \"{}\" without a default value",
+                "warning".yellow(),
+                name,
+                top
+            );
+        }
+        print!("{}", component);
+        Ok(())
+    }
+
+    /// Returns the path to `file` relative to the ip's root directory if it
+    /// exists within the ip, otherwise it returns the full path.
+    fn relative_path(ip: &Ip, file: &str) -> String {
+        let path = PathBuf::from(file);
+        match path.strip_prefix(ip.get_root()) {
+            Ok(p) => filesystem::into_std_str(p.to_path_buf()),
+            Err(_) => filesystem::into_std_str(path),
+        }
+    }
+}
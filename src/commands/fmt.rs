@@ -0,0 +1,145 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::commands::helps::fmt;
+use crate::core::context::Context;
+use crate::core::fileset;
+use crate::core::ip::Ip;
+use crate::core::lang::sv;
+use crate::core::lang::vhdl;
+use crate::error::Error;
+use crate::info;
+use crate::util::anyerror::Fault;
+use crate::warn;
+use std::fs;
+use std::path::PathBuf;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Fmt {
+    check: bool,
+    paths: Vec<PathBuf>,
+}
+
+impl Subcommand<Context> for Fmt {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(fmt::HELP))?;
+        Ok(Fmt {
+            // Flags
+            check: cli.check(Arg::flag("check"))?,
+            // Arguments
+            paths: cli.get_all(Arg::positional("path"))?.unwrap_or_default(),
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // relative paths on the command-line are resolved from where orbit was called
+        let caller_dir = std::env::current_dir()?;
+        let mut paths = Vec::with_capacity(self.paths.len());
+        for p in &self.paths {
+            match caller_dir.join(p).canonicalize() {
+                Ok(full) => paths.push(full),
+                Err(_) => return Err(Error::PathNotFound(p.clone()))?,
+            }
+        }
+
+        // go to the ip directory
+        c.jump_to_working_ip()?;
+
+        // get the ip manifest
+        let ip = Ip::load(c.get_ip_path().unwrap().clone(), true, false)?;
+
+        self.run(&ip, &paths, c)
+    }
+}
+
+impl Fmt {
+    fn run(&self, ip: &Ip, paths: &Vec<PathBuf>, c: &Context) -> Result<(), Fault> {
+        let root = ip.get_root().canonicalize()?;
+
+        let mut files: Vec<PathBuf> = ip
+            .gather_current_files()
+            .into_iter()
+            .filter(|f| {
+                fileset::is_vhdl(f) || fileset::is_verilog(f) || fileset::is_systemverilog(f)
+            })
+            .filter_map(|f| PathBuf::from(f).canonicalize().ok())
+            .filter(|f| paths.is_empty() == true || paths.iter().any(|p| f.starts_with(p)))
+            .collect();
+        files.sort();
+
+        let mut unformatted = Vec::new();
+        let mut skipped = 0;
+        for file in &files {
+            let display = file
+                .strip_prefix(&root)
+                .unwrap_or(file)
+                .display()
+                .to_string();
+            let source = match fs::read_to_string(file) {
+                Ok(s) => s,
+                Err(e) => {
+                    warn!("skipping file {:?}: {}", display, e);
+                    skipped += 1;
+                    continue;
+                }
+            };
+            let result = match Self::format(&display, &source, c) {
+                Some(r) => r,
+                None => {
+                    warn!(
+                        "skipping file {:?}: formatting would change its source code",
+                        display
+                    );
+                    skipped += 1;
+                    continue;
+                }
+            };
+            if result != source {
+                if self.check == true {
+                    println!("{}", display);
+                } else {
+                    fs::write(file, result)?;
+                }
+                unformatted.push(display);
+            }
+        }
+
+        match self.check {
+            // files that were skipped are not known to be formatted
+            true => match (unformatted.is_empty(), skipped) {
+                (true, 0) => Ok(()),
+                (true, _) => Err(Error::FilesNotChecked(skipped))?,
+                (false, _) => Err(Error::FilesNeedFormatting(unformatted.len()))?,
+            },
+            false => {
+                info!("formatted {} of {} file(s)", unformatted.len(), files.len());
+                Ok(())
+            }
+        }
+    }
+
+    /// Formats the `source` code of the HDL file named `file`.
+    fn format(file: &str, source: &str, c: &Context) -> Option<String> {
+        match fileset::is_vhdl(file) {
+            true => vhdl::formatter::format(source, &c.get_vhdl_format()),
+            false => sv::formatter::format(source, &c.get_sv_format()),
+        }
+    }
+}
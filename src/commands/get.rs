@@ -22,6 +22,7 @@ use crate::core::ip::Ip;
 use crate::core::ip::IpSpec;
 use crate::core::ip::PartialIpSpec;
 use crate::core::lang::sv::format::SystemVerilogFormat;
+use crate::core::lang::translate;
 use crate::core::lang::verilog::symbols::module::Module;
 use crate::core::lang::vhdl::format::VhdlFormat;
 use crate::core::lang::vhdl::interface;
@@ -49,6 +50,8 @@ pub struct Get {
     instance: bool,
     library: bool,
     architectures: bool,
+    testbench: bool,
+    lang: Option<Lang>,
     json: bool,
     signal_prefix: String,
     signal_suffix: String,
@@ -67,7 +70,9 @@ impl Subcommand<Context> for Get {
             instance: cli.check(Arg::flag("instance").switch('i'))?,
             library: cli.check(Arg::flag("library").switch('l'))?,
             architectures: cli.check(Arg::flag("architecture").switch('a'))?,
+            testbench: cli.check(Arg::flag("testbench"))?,
             json: cli.check(Arg::flag("json"))?,
+            lang: cli.get(Arg::option("lang").value("lang"))?,
             signal_prefix: cli
                 .get(Arg::option("signal-prefix").value("str"))?
                 .unwrap_or_default(),
@@ -88,11 +93,12 @@ impl Subcommand<Context> for Get {
 
     fn execute(self, c: &Context) -> proc::Result {
         // --name can only be used with --instance is set
-        if self.name.is_some() && self.instance == false {
+        if self.name.is_some() && self.instance == false && self.testbench == false {
             return Err(AnyError(format!(
-                "'{}' can only be used with '{}'",
+                "'{}' can only be used with '{}' or '{}'",
                 "--name".yellow(),
-                "--instance".yellow()
+                "--instance".yellow(),
+                "--testbench".yellow()
             )))?;
         }
 
@@ -168,32 +174,79 @@ impl Get {
                 return Err(Error::GetUnitNotFound(self.unit.to_string(), hint))?;
             }
         };
-        // determine how to handle unit display
-        match unit.get_lang() {
-            Lang::Vhdl => self.display_vhdl_entity(
-                &ip,
-                unit.get_vhdl_symbol().unwrap().as_entity().unwrap(),
-                is_local,
-                &c.get_vhdl_format(),
-            ),
-            Lang::Verilog => self.display_verilog_module(
-                &ip,
-                unit.get_verilog_symbol().unwrap().as_module().unwrap(),
-                &c.get_sv_format(),
-            ),
-            Lang::SystemVerilog => self.display_verilog_module(
-                &ip,
-                unit.get_systemverilog_symbol()
-                    .unwrap()
-                    .as_module()
-                    .unwrap(),
-                &c.get_sv_format(),
-            ),
+        // resolve the unit into the language requested for the output
+        let vhdl_symbol = unit.get_vhdl_symbol().and_then(|s| s.as_entity());
+        let verilog_symbol = match unit.get_lang() {
+            Lang::Verilog => unit.get_verilog_symbol().and_then(|s| s.as_module()),
+            _ => unit.get_systemverilog_symbol().and_then(|s| s.as_module()),
+        };
+        let target = self.lang.clone().unwrap_or(unit.get_lang());
+        match (unit.get_lang(), &target) {
+            (Lang::Vhdl, Lang::Vhdl) => self.display_vhdl(&ip, vhdl_symbol.unwrap(), is_local, c),
+            (Lang::Vhdl, _) => {
+                let translation = translate::entity_to_module(vhdl_symbol.unwrap(), &target)?;
+                Self::report_translation(translation.get_warnings());
+                self.display_verilog(&ip, translation.get_unit(), &target, c)
+            }
+            (_, Lang::Vhdl) => {
+                let translation = translate::module_to_entity(verilog_symbol.unwrap())?;
+                Self::report_translation(translation.get_warnings());
+                self.display_vhdl(&ip, translation.get_unit(), is_local, c)
+            }
+            (_, _) => self.display_verilog(&ip, verilog_symbol.unwrap(), &target, c),
         }?;
 
         Ok(())
     }
 
+    /// Prints the parts of the interface that could not be translated to stderr
+    /// to keep the generated code clean on stdout.
+    fn report_translation(warnings: &Vec<String>) {
+        for w in warnings {
+            eprintln!("{}: {}", "warning".yellow(), w);
+        }
+    }
+
+    fn display_vhdl(
+        &self,
+        ip: &Ip,
+        entity: &Entity,
+        is_local: bool,
+        c: &Context,
+    ) -> Result<(), Fault> {
+        match self.testbench {
+            // generate a complete testbench instead of code snippets
+            true => self.display_vhdl_testbench(&ip, entity, is_local, c),
+            false => self.display_vhdl_entity(&ip, entity, is_local, &c.get_vhdl_format()),
+        }
+    }
+
+    fn display_verilog(
+        &self,
+        ip: &Ip,
+        module: &Module,
+        lang: &Lang,
+        c: &Context,
+    ) -> Result<(), Fault> {
+        match self.testbench {
+            // generate a complete testbench instead of code snippets
+            true => {
+                // the testbench is meant to be written to a file
+                colored::control::set_override(false);
+                print!(
+                    "{}",
+                    module.into_testbench(
+                        &self.name,
+                        lang == &Lang::SystemVerilog,
+                        &c.get_sv_format()
+                    )
+                );
+                Ok(())
+            }
+            false => self.display_verilog_module(&ip, module, &c.get_sv_format()),
+        }
+    }
+
     fn display_vhdl_entity(
         &self,
         ip: &Ip,
@@ -327,6 +380,32 @@ impl Get {
         Ok(())
     }
 
+    fn display_vhdl_testbench(
+        &self,
+        ip: &Ip,
+        entity: &Entity,
+        is_local: bool,
+        c: &Context,
+    ) -> Result<(), Fault> {
+        // the testbench is meant to be written to a file
+        colored::control::set_override(false);
+
+        let lib = match is_local {
+            true => VhdlIdentifier::new_working(),
+            false => ip
+                .get_man()
+                .get_hdl_library()
+                .as_vhdl_name()
+                .unwrap()
+                .clone(),
+        };
+        print!(
+            "{}",
+            entity.into_testbench(&self.name, &lib, &c.get_vhdl_format())
+        );
+        Ok(())
+    }
+
     fn fetch_entity(ip: &Ip, name: &LangIdentifier) -> Result<Option<LangUnit>, Fault> {
         let mut files = ip.collect_units(true, false)?;
         let result = files.remove(name);
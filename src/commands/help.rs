@@ -50,11 +50,20 @@ enum Topic {
     Read,
     Get,
     Tree,
+    Doc,
+    Fmt,
     Lock,
+    Add,
+    Rm,
     Build,
     Test,
+    Exec,
+    Export,
+    Version,
     Publish,
+    Yank,
     Search,
+    Diff,
     Install,
     Env,
     Config,
@@ -64,8 +73,9 @@ enum Topic {
 impl Topic {
     fn list_all() -> String {
         let list = [
-            "new", "init", "info", "read", "get", "tree", "lock", "test", "build", "publish",
-            "search", "install", "env", "config", "remove",
+            "new", "init", "info", "read", "get", "tree", "doc", "fmt", "lock", "add", "rm",
+            "test", "build", "exec", "export", "version", "publish", "yank", "search", "diff",
+            "install", "env", "config", "remove",
         ];
         list.into_iter().fold(String::new(), |mut acc, x| {
             acc.push_str(&format!("{}\n", x));
@@ -84,11 +94,20 @@ impl std::str::FromStr for Topic {
             "read" => Self::Read,
             "get" => Self::Get,
             "tree" => Self::Tree,
+            "doc" => Self::Doc,
+            "fmt" => Self::Fmt,
             "lock" => Self::Lock,
+            "add" => Self::Add,
+            "rm" => Self::Rm,
             "test" => Self::Test,
             "build" => Self::Build,
+            "exec" => Self::Exec,
+            "export" => Self::Export,
+            "version" => Self::Version,
             "publish" => Self::Publish,
+            "yank" => Self::Yank,
             "search" => Self::Search,
+            "diff" => Self::Diff,
             "install" => Self::Install,
             "env" => Self::Env,
             "config" => Self::Config,
@@ -109,11 +128,20 @@ impl Topic {
             Read => manuals::read::MANUAL,
             Get => manuals::get::MANUAL,
             Tree => manuals::tree::MANUAL,
+            Doc => manuals::doc::MANUAL,
+            Fmt => manuals::fmt::MANUAL,
             Lock => manuals::lock::MANUAL,
+            Add => manuals::add::MANUAL,
+            Rm => manuals::rm::MANUAL,
             Test => manuals::test::MANUAL,
             Build => manuals::build::MANUAL,
+            Exec => manuals::exec::MANUAL,
+            Export => manuals::export::MANUAL,
+            Version => manuals::version::MANUAL,
             Publish => manuals::publish::MANUAL,
+            Yank => manuals::yank::MANUAL,
             Search => manuals::search::MANUAL,
+            Diff => manuals::diff::MANUAL,
             Install => manuals::install::MANUAL,
             Env => manuals::env::MANUAL,
             Config => manuals::config::MANUAL,
@@ -33,6 +33,10 @@ Options:
                           a glob-style pattern identified by name to include in the blueprint
     --force               force the target to execute 
     --no-clean            do not clean the target folder before execution
+    --no-cache            execute the target even if nothing has changed
+    --watch               execute the target again whenever a file changes
+    --generic <name=value>...
+                          set a generic on the top level unit, or sweep a list of values
     --verbose             display the command being executed
     args                  arguments to pass to the target
 
@@ -0,0 +1,30 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// Automatically generated from the mansync.py script.
+pub const HELP: &str = r#"Compare two versions of an ip.
+
+Usage:
+    orbit diff [options] <ip> <from> [<to>]
+
+Options:
+    <ip>                  ip's name
+    <from>                version to compare from
+    <to>                  version to compare to (default: latest)
+    --json                export the report in json format
+
+Use 'orbit help diff' to read more about the command."#;
@@ -0,0 +1,37 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// Automatically generated from the mansync.py script.
+pub const HELP: &str = r#"Run a command within orbit's environment.
+
+Usage:
+    orbit exec [options] <command> [--] [args]...
+
+Arguments:
+    <command>             the program to run
+
+Options:
+    --target, -t <name>   run from the target's output directory
+    --plan <format>       plan a blueprint in the format before running
+    --top <unit>          set the top level design unit when planning
+    --all                 include all hdl files of the working ip when planning
+    --target-dir <dir>    the relative directory where the target starts
+    --force               plan the blueprint despite errors in the source code
+    --verbose             display the command being executed
+    args                  arguments to pass to the program
+
+Use 'orbit help exec' to read more about the command."#;
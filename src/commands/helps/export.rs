@@ -0,0 +1,31 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// Automatically generated from the mansync.py script.
+pub const HELP: &str = r#"Describe an ip in another tool's format.
+
+Usage:
+    orbit export [options] <format>
+
+Arguments:
+    <format>              export format: ipxact, core
+
+Options:
+    --top <unit>          set the top level design unit
+    --vendor <name>       set the vendor of the exported ip
+
+Use 'orbit help export' to read more about the command."#;
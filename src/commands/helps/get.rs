@@ -32,6 +32,8 @@ Options:
     --signals, -s         display the constant and signal declarations
     --instance, -i        display the unit's instantiation
     --architecture, -a    display the unit's architectures
+    --testbench           display a complete testbench for the unit
+    --lang <lang>         translate the snippets into vhdl, verilog, or sv
     --name <identifier>   set the instance's identifier
     --signal-prefix <str>
                           prepend information to the instance's signals
@@ -25,6 +25,8 @@ Options:
     <path>                directory to initialize
     --name <name>         set the resulting ip's name
     --lib <lib>           set the resulting ip's library
+    --from-core <file>    convert a FuseSoC core file into the manifest
+    --from-list <file>    include the files from an existing project's file list
     --uuid                print a new uuid and exit
 
 Use 'orbit help init' to read more about the command."#;
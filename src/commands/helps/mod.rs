@@ -15,9 +15,15 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+pub mod add;
 pub mod build;
 pub mod config;
+pub mod diff;
+pub mod doc;
 pub mod env;
+pub mod exec;
+pub mod export;
+pub mod fmt;
 pub mod get;
 pub mod info;
 pub mod init;
@@ -28,6 +34,9 @@ pub mod orbit;
 pub mod publish;
 pub mod read;
 pub mod remove;
+pub mod rm;
 pub mod search;
 pub mod test;
 pub mod tree;
+pub mod version;
+pub mod yank;
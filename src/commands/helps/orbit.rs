@@ -28,11 +28,20 @@ Commands:
     read                  lookup hdl source code
     get                   fetch an hdl unit for code integration
     tree                  show the dependency graph
+    doc                   generate documentation for an ip's units
+    fmt                   format an ip's hdl source files
     lock                  save the world state of an ip
+    add                   add a dependency to an ip
+    rm                    remove a dependency from an ip
     test, t               run a test
     build, b              plan and execute a target
+    exec                  run a command within orbit's environment
+    export                describe an ip in another tool's format
+    version               bump the version of an ip
     publish               post an ip to a channel
+    yank                  discourage the use of a published ip
     search                browse the ip catalog
+    diff                  compare two versions of an ip
     install               store an immutable reference to an ip
     remove                delete an ip from the catalog
     env                   print orbit environment information
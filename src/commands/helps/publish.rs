@@ -25,5 +25,6 @@ Options:
     --ready, -y           run the operation to completion
     --no-install          do not install the ip for future use
     --list                view available channels and exit
+    --allow-breaking      publish without enforcing the required version bump
 
 Use 'orbit help publish' to read more about the command."#;
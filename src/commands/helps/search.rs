@@ -29,5 +29,7 @@ Options:
     --keyword <term>...   include ip that have this keyword
     --limit <n>           maximum number of results to return
     --match               return results that pass each filter
+    --unit <name>         list the units matching this name
+    --add                 add the ip of the closest matching unit as a dependency
 
 Use 'orbit help search' to read more about the command."#;
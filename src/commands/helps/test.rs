@@ -34,6 +34,9 @@ Options:
                           a glob-style pattern identified by name to include in the blueprint
     --no-clean            do not clean the target folder before execution
     --force               force the target to execute 
+    --watch               execute the target again whenever a file changes
+    --generic <name=value>...
+                          set a generic on the testbench, or sweep a list of values
     --verbose             display the command being executed
     args                  arguments to pass to the target
 
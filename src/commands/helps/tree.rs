@@ -26,5 +26,6 @@ Options:
     --edges, -e <kind>    the kind of dependencies to display (unit, ip, all)
     --format <fmt>        determine how to display nodes (long, short)
     --ascii               limit the textual tree characters to the 128 ASCII set
+    --instances           list the full path of every instance and count them
 
 Use 'orbit help tree' to read more about the command."#;
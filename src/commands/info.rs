@@ -25,6 +25,7 @@ use crate::core::visibility::Visibility;
 use crate::error::{Error, Hint};
 use crate::util::anyerror::AnyError;
 use crate::util::anyerror::Fault;
+use crate::warn;
 use std::cmp::Ordering;
 use std::env::current_dir;
 
@@ -109,6 +110,18 @@ impl Subcommand<Context> for Info {
             }
         };
 
+        // warn about using an ip that is yanked or deprecated in a channel
+        let level = catalog.inner().get(ip.get_uuid());
+        if let Some(lvl) = level.filter(|_| is_local_ip == false) {
+            let spec = ip.get_man().get_ip().into_ip_spec();
+            if let Some(reason) = lvl.get_yanked(ip.get_man().get_ip().get_version()) {
+                warn!("ip {} is yanked: {}", spec, reason);
+            }
+            if let Some(message) = lvl.get_deprecated() {
+                warn!("ip {} is deprecated: {}", spec.get_name(), message);
+            }
+        }
+
         // load the ip's manifest
         if self.units == true {
             if ip.get_mapping().is_physical() == true {
@@ -160,9 +173,13 @@ impl Subcommand<Context> for Info {
                                 })
                                 .for_each(|v| {
                                     data.push_str(&format!(
-                                        "{:<14}{:<9}\n",
+                                        "{:<14}{:<9}{}\n",
                                         v.get_version().to_string(),
-                                        v.get_state().to_string()
+                                        v.get_state().to_string(),
+                                        match level.and_then(|l| l.get_yanked(v.get_version())) {
+                                            Some(_) => " yanked",
+                                            None => "",
+                                        }
                                     ));
                                 });
                             // pop the last \n
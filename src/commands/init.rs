@@ -18,20 +18,28 @@
 use super::lock::Lock;
 use super::new::New;
 use crate::commands::helps::init;
+use crate::commands::install::Install;
 use crate::commands::orbit::AnyResult;
+use crate::commands::plan;
+use crate::core::catalog::{Catalog, PkgName};
 use crate::core::context::Context;
-use crate::core::ip::Ip;
+use crate::core::filelist::FileList;
+use crate::core::fusesoc::CoreFile;
+use crate::core::ip::{Ip, PartialIpSpec};
 use crate::core::lang::vhdl::token::Identifier;
+use crate::core::lockfile::IP_LOCK_FILE;
 use crate::core::manifest::{Manifest, IP_MANIFEST_FILE};
 use crate::core::pkgid::PkgPart;
 use crate::core::uuid::Uuid;
+use crate::core::version::AnyVersion;
 use crate::error::{Error, LastError};
-use crate::util::anyerror::AnyError;
+use crate::util::anyerror::{AnyError, Fault};
 use crate::util::filesystem;
 use crate::util::filesystem::Standardize;
 use crate::*;
 use std::io::Write;
 use std::path::PathBuf;
+use std::str::FromStr;
 
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
@@ -42,6 +50,8 @@ pub struct Init {
     library: Option<Identifier>,
     path: PathBuf,
     uuid: bool,
+    from_core: Option<PathBuf>,
+    from_list: Option<PathBuf>,
 }
 
 impl Subcommand<Context> for Init {
@@ -51,13 +61,15 @@ impl Subcommand<Context> for Init {
             uuid: cli.check(Arg::flag("uuid"))?,
             name: cli.get(Arg::option("name"))?,
             library: cli.get(Arg::option("lib"))?,
+            from_core: cli.get(Arg::option("from-core").value("file"))?,
+            from_list: cli.get(Arg::option("from-list").value("file"))?,
             path: cli
                 .get(Arg::positional("path"))?
                 .unwrap_or(PathBuf::from(".")),
         })
     }
 
-    fn execute(self, _: &Context) -> proc::Result {
+    fn execute(self, c: &Context) -> proc::Result {
         // TODO: verify the pkgid is not taken
 
         if self.uuid == true {
@@ -76,9 +88,98 @@ impl Subcommand<Context> for Init {
             }
         }
 
-        let ip_name = New::extract_name(self.name.as_ref(), &dest)?;
+        if self.from_core.is_some() && self.from_list.is_some() {
+            return Err(AnyError(format!(
+                "options \"--from-core\" and \"--from-list\" cannot be used together"
+            )))?;
+        }
+
+        // read the existing fusesoc core file
+        let mut core = match &self.from_core {
+            Some(path) => {
+                let text = match std::fs::read_to_string(&path) {
+                    Ok(t) => t,
+                    Err(e) => {
+                        return Err(AnyError(format!(
+                            "failed to read core file {:?}: {}",
+                            path, e
+                        )))?
+                    }
+                };
+                let mut core = match CoreFile::from_str(&text) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        return Err(AnyError(format!(
+                            "failed to parse core file {:?}: {}",
+                            path, e
+                        )))?
+                    }
+                };
+                // core file paths are relative to the core file's directory
+                let core_dir = filesystem::full_normal(path)
+                    .parent()
+                    .unwrap()
+                    .to_path_buf();
+                if let Ok(rel) = core_dir.strip_prefix(&dest) {
+                    core.set_root(&filesystem::into_std_str(rel.to_path_buf()));
+                }
+                Some(core)
+            }
+            None => None,
+        };
+
+        // use the core's name when a name is not explicitly provided
+        let core_name = match (&self.name, &core) {
+            (None, Some(c)) => PkgPart::from_str(c.get_name()).ok(),
+            _ => None,
+        };
+
+        let ip_name = New::extract_name(self.name.as_ref().or(core_name.as_ref()), &dest)?;
+
+        let lib_str = match &self.library {
+            Some(s) => Some(s.to_string()),
+            None => None,
+        };
+
+        // read the existing project's file list
+        let mut list = match &self.from_list {
+            Some(path) => Some(FileList::read(path)?),
+            None => None,
+        };
+
+        let contents = match (&mut core, &mut list) {
+            (Some(core), _) => {
+                // look up the core's dependencies in the catalog
+                let catalog = Catalog::new()
+                    .installations(c.get_cache_path())?
+                    .downloads(c.get_downloads_path())?
+                    .available(&c.get_config().get_channels())?;
+                core.into_manifest(&ip_name, &lib_str, &catalog)
+            }
+            (_, Some(l)) => l.into_manifest(&ip_name, &lib_str, &dest),
+            _ => Manifest::write_empty_manifest(&ip_name, &lib_str),
+        };
+
+        if let Some(c) = &core {
+            for w in c.get_warnings() {
+                warn!("{}", w);
+            }
+        }
 
-        match self.create_ip(&ip_name) {
+        if let Some(l) = &list {
+            for w in l.get_warnings() {
+                warn!("{}", w);
+            }
+            for (file, source) in l.get_missing() {
+                warn!(
+                    "file {:?} referenced in {:?} was not found",
+                    PathBuf::standardize(file),
+                    PathBuf::standardize(source)
+                );
+            }
+        }
+
+        match self.create_ip(&contents, c) {
             Ok(r) => Ok(r),
             Err(e) => Err(Error::FailedToInitIp(LastError(e.to_string())))?,
         }
@@ -86,8 +187,53 @@ impl Subcommand<Context> for Init {
 }
 
 impl Init {
+    /// Loads the newly initialized ip at `path` and writes its lockfile.
+    fn lock_new_ip(path: &PathBuf, c: &Context) -> Result<Ip, Fault> {
+        let local_ip = Ip::load(path.clone(), true, false)?;
+        match local_ip.get_man().get_deps_list(true, false).is_empty() {
+            true => Lock::write_new_lockfile(&local_ip, true)?,
+            false => Self::install_deps_and_lock(&local_ip, c)?,
+        }
+        Ok(local_ip)
+    }
+
+    /// Installs the dependencies of the `local_ip` that are missing from the
+    /// cache and then writes its lockfile.
+    fn install_deps_and_lock(local_ip: &Ip, c: &Context) -> Result<(), Fault> {
+        let catalog = Catalog::new()
+            .installations(c.get_cache_path())?
+            .downloads(c.get_downloads_path())?
+            .available(&c.get_config().get_channels())?;
+        for (pkgid, dependency) in local_ip.get_man().get_deps_list(true, true) {
+            let level = match catalog.translate_name(&PkgName::new(pkgid, dependency.as_uuid()))? {
+                Some(lvl) => lvl,
+                None => continue,
+            };
+            let version = AnyVersion::Specific(dependency.get_version().clone());
+            if level.get_install(&version).is_some() == true {
+                continue;
+            }
+            if let Some(target) = level.get(true, true, &version) {
+                Install::install_from_catalog(
+                    PartialIpSpec::new(
+                        pkgid.clone(),
+                        Some(target.get_uuid().clone()),
+                        target.get_man().get_ip().get_version().to_partial_version(),
+                    ),
+                    c,
+                )?;
+            }
+        }
+        plan::install_unyanked_deps(c, local_ip)?;
+        let catalog = Catalog::new()
+            .installations(c.get_cache_path())?
+            .downloads(c.get_downloads_path())?
+            .statuses(&c.get_config().get_channels())?;
+        Lock::run(local_ip, &catalog, false)
+    }
+
     /// Initializes a project at an exising path.
-    fn create_ip(&self, ip: &PkgPart) -> AnyResult<()> {
+    fn create_ip(&self, contents: &str, c: &Context) -> AnyResult<()> {
         // verify the directory already exists
         if self.path.is_dir() == false || self.path.exists() == false {
             return Err(Box::new(AnyError(format!(
@@ -103,18 +249,32 @@ impl Init {
             p
         };
 
-        let lib_str = match &self.library {
-            Some(s) => Some(s.to_string()),
-            None => None,
-        };
+        // verify the manifest is valid before writing anything
+        if let Err(e) = Manifest::from_str(contents) {
+            return Err(Box::new(AnyError(format!(
+                "failed to compose a valid manifest: {}",
+                e
+            ))));
+        }
+        let lock_path = self.path.join(IP_LOCK_FILE);
+        let had_lock = lock_path.exists();
 
         // write the manifest
         let mut manifest = std::fs::File::create(&manifest_path)?;
-        manifest.write_all(Manifest::write_empty_manifest(&ip, &lib_str).as_bytes())?;
+        manifest.write_all(contents.as_bytes())?;
 
         // write the lockfile
-        let local_ip = Ip::load(self.path.clone(), true, false)?;
-        Lock::write_new_lockfile(&local_ip, true)?;
+        let local_ip = match Self::lock_new_ip(&self.path, c) {
+            Ok(ip) => ip,
+            Err(e) => {
+                // remove the new files so the ip can be initialized again
+                std::fs::remove_file(&manifest_path)?;
+                if had_lock == false && lock_path.exists() == true {
+                    std::fs::remove_file(&lock_path)?;
+                }
+                return Err(e);
+            }
+        };
 
         info!(
             "initialized ip \"{}\"",
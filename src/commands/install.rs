@@ -87,6 +87,26 @@ pub struct Install {
     all: bool,
 }
 
+impl Install {
+    /// Installs the ip matching `spec` from the catalog, downloading it if it is
+    /// only available from a channel.
+    pub fn install_from_catalog(spec: PartialIpSpec, c: &Context) -> proc::Result {
+        Install {
+            ip: Some(spec),
+            url: None,
+            path: None,
+            protocol: None,
+            offline: false,
+            tag: None,
+            list: false,
+            force: false,
+            verbose: false,
+            all: false,
+        }
+        .execute(c)
+    }
+}
+
 impl Subcommand<Context> for Install {
     fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
         cli.help(Help::with(install::HELP))?;
@@ -17,17 +17,20 @@
 
 use super::plan::{self, Plan};
 use crate::commands::helps::lock;
-use crate::core::algo;
+use crate::core::algo::{self, IpNode};
 use crate::core::catalog::Catalog;
 use crate::core::context::Context;
-use crate::core::ip::Ip;
+use crate::core::ip::{Ip, IpSpec};
 use crate::core::lockfile::LockEntry;
 use crate::core::swap::StrSwapTable;
 use crate::util::anyerror::Fault;
 use crate::util::environment::Environment;
+use crate::util::graphmap::GraphMap;
+use crate::warn;
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
 use colored::Colorize;
+use std::path::PathBuf;
 
 #[derive(Debug, PartialEq)]
 pub struct Lock {
@@ -85,6 +88,13 @@ impl Subcommand<Context> for Lock {
             // recollect the installations to update the catalog for dependency graphing
             catalog = catalog.installations(c.get_cache_path())?;
         }
+        // replace dependencies that were yanked since they were installed
+        if plan::install_unyanked_deps(c, &working_ip)? == true {
+            catalog = catalog.installations(c.get_cache_path())?;
+        }
+
+        // mark the versions yanked and ip deprecated in the channels
+        let catalog = catalog.statuses(&c.get_config().get_channels())?;
 
         Self::run(&working_ip, &catalog, self.force)
     }
@@ -99,11 +109,66 @@ impl Lock {
             Err(e) => return Err(e)?,
         };
 
+        Self::warn_statuses(&working_ip, &ip_graph, &catalog);
+
         // only write lockfile and exit if flag is raised
         Plan::write_lockfile(&working_ip, &ip_graph, force, true, &catalog)?;
         Ok(())
     }
 
+    /// Updates the lockfile of the ip at `root` after its manifest at `man_path`
+    /// was edited.
+    ///
+    /// The manifest is restored to its `original` contents if the lockfile cannot
+    /// be updated, so the manifest never disagrees with the lockfile.
+    pub fn run_after_edit(
+        c: &Context,
+        root: &PathBuf,
+        man_path: &PathBuf,
+        original: &str,
+    ) -> Result<(), Fault> {
+        let result = Ip::load(root.clone(), true, false).and_then(|local_ip| {
+            plan::install_unyanked_deps(c, &local_ip)?;
+            let catalog = Catalog::new()
+                .installations(c.get_cache_path())?
+                .downloads(c.get_downloads_path())?
+                .statuses(&c.get_config().get_channels())?;
+            Self::run(&local_ip, &catalog, false)
+        });
+        if result.is_err() == true {
+            std::fs::write(man_path, original)?;
+        }
+        result
+    }
+
+    /// Warns about any dependencies in the `ip_graph` that are yanked or deprecated
+    /// according to the `catalog`.
+    fn warn_statuses(working_ip: &Ip, ip_graph: &GraphMap<IpSpec, IpNode, ()>, catalog: &Catalog) {
+        let mut deps: Vec<&Ip> = ip_graph
+            .get_map()
+            .values()
+            .map(|n| n.as_ref().as_original_ip())
+            .filter(|ip| ip.get_uuid() != working_ip.get_uuid())
+            .collect();
+        deps.sort_by(|a, b| {
+            a.get_man()
+                .get_ip()
+                .get_name()
+                .cmp(b.get_man().get_ip().get_name())
+        });
+        for dep in deps {
+            if let Some(lvl) = catalog.inner().get(dep.get_uuid()) {
+                let spec = dep.get_man().get_ip().into_ip_spec();
+                if let Some(reason) = lvl.get_yanked(dep.get_man().get_ip().get_version()) {
+                    warn!("using yanked ip {}: {}", spec, reason);
+                }
+                if let Some(message) = lvl.get_deprecated() {
+                    warn!("ip {} is deprecated: {}", spec.get_name(), message);
+                }
+            }
+        }
+    }
+
     /// Writes a lockfile for a newly created ip (one that either was made with `new` or `init`).
     pub fn write_new_lockfile(local_ip: &Ip, warn: bool) -> Result<(), Fault> {
         // build entire ip graph and resolve with dynamic symbol transformation
@@ -122,3 +187,40 @@ impl Lock {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::manifest::IP_MANIFEST_FILE;
+
+    #[test]
+    fn restore_manifest_on_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("top");
+        let gates = dir.path().join("gates");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(&gates).unwrap();
+        // the relative dependency does not have a lockfile
+        std::fs::write(
+            gates.join(IP_MANIFEST_FILE),
+            "[ip]\nname = \"gates\"\nversion = \"0.1.0\"\nuuid = \"1111111111111111111111111\"\n",
+        )
+        .unwrap();
+        let original =
+            "[ip]\nname = \"top\"\nversion = \"0.1.0\"\nuuid = \"2222222222222222222222222\"\n";
+        let man_path = root.join(IP_MANIFEST_FILE);
+        std::fs::write(
+            &man_path,
+            format!(
+                "{}\n[dependencies]\ngates = {{ path = \"../gates\", version = \"0.1.0\" }}\n",
+                original
+            ),
+        )
+        .unwrap();
+
+        let c = Context::new().cache().unwrap().archive().unwrap();
+        let err = Lock::run_after_edit(&c, &root, &man_path, original).unwrap_err();
+        assert_eq!(err.to_string().contains("a lockfile does not exist"), true);
+        assert_eq!(std::fs::read_to_string(&man_path).unwrap(), original);
+    }
+}
@@ -0,0 +1,62 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    add - add a dependency to an ip
+
+SYNOPSIS
+    orbit add [options] [<ip>]
+
+DESCRIPTION
+    Adds the ip as a dependency to the local ip's manifest. The '<ip>' is looked up
+    in the catalog and its latest version is used as the version requirement, 
+    unless a version is given in the ip specification. If the name of the '<ip>'
+    is shared by more than one ip, its uuid must be included in the specification
+    to pin which ip to use.
+    
+    When the dependency is not installed, Orbit installs it before updating the 
+    lockfile.
+    
+    Use '--dev' to add the ip to the development dependencies instead. If the ip is
+    already listed in the other dependency table, it is moved.
+    
+    Use '--path' to add an ip found at a relative path on the local file system as
+    a dependency. The version requirement is the version found in that ip's 
+    manifest.
+    
+    The manifest is edited in place so its existing formatting and comments are 
+    kept. Afterward, the ip's lockfile is updated with the new dependency.
+    
+    To remove a dependency, see the 'rm' command.
+
+OPTIONS
+    <ip>
+        Ip specification
+
+    --path <path>
+        Relative path to the ip on the local file system
+
+    --dev
+        Add the ip as a development dependency
+
+EXAMPLES
+    orbit add gates
+    orbit add lcd_driver:2.0
+    orbit add uart+5h2kq7m2x0wdlc4ns8tbe1pzs --dev
+    orbit add --path ../gates
+"#;
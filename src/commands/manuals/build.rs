@@ -51,6 +51,39 @@ DESCRIPTION
     
     The target's process will spawn from the current working ip's output directory,
     which is $ORBIT_TARGET_DIR/$ORBIT_TARGET.
+    
+    A target can require other targets to execute before it, forming a pipeline.
+    Each target in the pipeline is executed in order, and the pipeline stops at the
+    first target that fails. When listing all targets with '--list', each target
+    that requires other targets is followed by its pipeline.
+    
+    Generics (VHDL) or parameters (Verilog) can be set on the top level unit with
+    '--generic', given as 'NAME=VALUE'. Each name must be declared by the top level
+    unit's interface. The values are passed to the target through the
+    ORBIT_GENERICS environment variable. Giving a comma-separated list of values,
+    such as '--generic WIDTH=8,16,32', sweeps the generic: the target is executed
+    once for every combination of values, each in its own output directory, and a
+    summary of the results is displayed at the end.
+    
+    A target is skipped when nothing has changed since its last successful
+    execution. After a target executes successfully, a fingerprint of its blueprint,
+    the files referenced in the blueprint, its command and arguments, and its
+    environment variables is stored in the '.fingerprint' file of its output
+    directory. If the next build computes the same fingerprint, the target is
+    reported as up to date and its output directory is left untouched. Use
+    '--no-cache' or '--force' to execute the target regardless. The output
+    directory of a ninja blueprint is only cleaned with '--force', since ninja
+    relies on the files from previous runs to analyze only what changed.
+    
+    With '--watch', the targets are executed again every time a file of the
+    working ip or one of its relative dependencies changes, until the command is
+    interrupted. Files are checked for changes by polling, and a burst of writes is
+    treated as a single change. The design is planned again only when a change
+    affects its structure: adding or removing an hdl file, editing the design
+    units or references within an hdl file, or editing the manifest. Otherwise,
+    the existing blueprint is reused. Each run ends with a line reporting whether
+    it passed or failed. Watching always executes the targets, regardless of their
+    fingerprints.
 
 OPTIONS
     --target, -t <name>
@@ -83,6 +116,15 @@ OPTIONS
     --no-clean
         Do not clean the target folder before execution
 
+    --no-cache
+        Execute the target even if nothing has changed
+
+    --watch
+        Execute the target again whenever a file changes
+
+    --generic <name=value>...
+        Set a generic on the top level unit, or sweep a list of values
+
     --verbose
         Display the command being executed
 
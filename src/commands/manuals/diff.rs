@@ -0,0 +1,65 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    diff - compare two versions of an ip
+
+SYNOPSIS
+    orbit diff [options] <ip> <from> [<to>]
+
+DESCRIPTION
+    Reports the differences between two versions of an ip in the catalog. This
+    command is useful to see what changed at the hdl level before upgrading a
+    dependency.
+    
+    Each version is read from the highest catalog level that has it. Installed ip
+    have higher priority over downloaded ip, and downloaded ip have higher priority
+    over available ip. When '<to>' is omitted, the latest version is compared.
+    
+    The report lists the files that were added or removed, the units that were
+    added or removed, the changes to the ports and generics of each unit, and the
+    changes to the dependencies listed in the manifests. It also includes the
+    smallest version bump that the changes to public units require under semantic
+    versioning.
+    
+    Files of ip only available via channels are unknown, and their units are read 
+    from the unit list recorded in the channel when the ip was published.
+    
+    Use '--json' to write the report in json format. Each change to a unit's
+    interface is written with its 'unit', its 'kind' (such as "port-added" or
+    "generic-type-changed"), the 'item' that changed, and the 'from' and 'to' modes
+    or types, along with the version 'bump' it requires.
+
+OPTIONS
+    <ip>
+        Ip's name
+
+    <from>
+        Version to compare from
+
+    <to>
+        Version to compare to (default: latest)
+
+    --json
+        Export the report in json format
+
+EXAMPLES
+    orbit diff gates 1.0.0 2.0.0
+    orbit diff gates 1.0
+    orbit diff gates 1.0.0 1.2.0 --json
+"#;
@@ -0,0 +1,54 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    doc - generate documentation for an ip's units
+
+SYNOPSIS
+    orbit doc [options]
+
+DESCRIPTION
+    Generates markdown pages documenting the design units of the local ip and its
+    dependencies.
+    
+    A page is written for every entity and module. Each page includes the comment
+    immediately preceding the unit's declaration as its description, tables of the
+    unit's generics and ports, an instantiation template, and the tree of units
+    instantiated beneath it. Units in the tree that have their own page are linked,
+    including units from dependency ips. Each ip receives an index page listing
+    its units along with the first line of their descriptions.
+    
+    Units that are private to an ip are omitted from the documentation. Protected
+    units of an ip are documented with a note that they cannot be used outside of
+    their ip.
+    
+    By default, the pages are written to the "doc" directory within the local
+    ip's target directory. Use '--output' to write them to a different directory.
+    To only document the local ip, use '--no-deps'.
+
+OPTIONS
+    --output <dir>
+        The directory to write the pages to
+
+    --no-deps
+        Do not document the units of dependency ips
+
+EXAMPLES
+    orbit doc
+    orbit doc --no-deps --output ./docs/units
+"#;
@@ -0,0 +1,82 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    exec - run a command within orbit's environment
+
+SYNOPSIS
+    orbit exec [options] <command> [--] [args]...
+
+DESCRIPTION
+    This command runs any program with the same environment variables, working
+    directory, and variable substitution that a target receives, without having to
+    define a target in a configuration file. It is useful for running helper
+    scripts on an ip ad hoc.
+    
+    The environment variables are set from the configuration files and the local
+    ip's manifest. Variable substitution is performed on the command and its
+    arguments, so a value such as '{{ orbit.ip.name }}' is replaced before the
+    program is run. Any relative path on the command-line that exists is resolved
+    from the directory where orbit was called.
+    
+    Without '--target', the program spawns from the local ip's root directory. With
+    '--target', the program spawns from that target's output directory, which is
+    $ORBIT_TARGET_DIR/$ORBIT_TARGET, and also receives the environment variables
+    saved from the target's last plan. If the target's blueprint exists, its file
+    name is set in ORBIT_BLUEPRINT.
+    
+    Using '--plan' plans a blueprint in the given format for the target before
+    running the program. The top level design unit is automatically detected
+    unless it is set with '--top'.
+    
+    Any command-line arguments entered after the terminating flag '--' are passed
+    in the received order as arguments to the program.
+
+OPTIONS
+    <command>
+        The program to run
+
+    --target, -t <name>
+        Run from the target's output directory
+
+    --plan <format>
+        Plan a blueprint in the format before running
+
+    --top <unit>
+        Set the top level design unit when planning
+
+    --all
+        Include all hdl files of the working ip when planning
+
+    --target-dir <dir>
+        The relative directory where the target starts
+
+    --force
+        Plan the blueprint despite errors in the source code
+
+    --verbose
+        Display the command being executed
+
+    args
+        Arguments to pass to the program
+
+EXAMPLES
+    orbit exec python3 -- ./scripts/report.py {{ orbit.ip.name }}
+    orbit exec --target ghdl --plan tsv python3 -- ./scripts/lint.py
+    orbit exec --target xsim ls
+"#;
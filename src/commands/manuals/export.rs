@@ -0,0 +1,67 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    export - describe an ip in another tool's format
+
+SYNOPSIS
+    orbit export [options] <format>
+
+DESCRIPTION
+    This command writes a description of the local ip in a format that can be
+    consumed by other tools. The description is written to the console so it can be
+    redirected to a file.
+    
+    The "ipxact" format produces an IEEE 1685-2014 (IP-XACT) component description.
+    The component's VLNV is set by the vendor, the ip's library, the ip's name, and
+    the ip's version. If '--vendor' is omitted, the vendor is set to "orbit".
+    
+    The component's model is created from the top-level design unit. Its generics
+    or parameters are written as module parameters and its ports are written as
+    wire ports. Default values and vector bounds are written as SystemVerilog
+    expressions. A module parameter requires a value, so generics or parameters
+    without a default value are skipped with a warning. The top-level design unit is automatically detected unless it is
+    set with '--top'.
+    
+    The "core" format produces a FuseSoC CAPI2 core file. The core's VLNV is set
+    the same way as the IP-XACT component. The core has a single fileset with the
+    in-order list of hdl files, where each file's 'logical_name' is its hdl
+    library, and a default target with the top-level design unit as its toplevel.
+    Files from dependencies are listed directly in the fileset instead of as
+    dependencies.
+    
+    The exported file set is the same in-order list of hdl files that would be
+    written to a blueprint when planning the top-level design unit. Files found
+    within the local ip are written relative to the ip's root directory, while
+    files from dependencies are written with their full paths.
+
+OPTIONS
+    <format>
+        Export format: ipxact, core
+
+    --top <unit>
+        Set the top level design unit
+
+    --vendor <name>
+        Set the vendor of the exported ip
+
+EXAMPLES
+    orbit export ipxact > component.xml
+    orbit export ipxact --top soc --vendor acme
+    orbit export core > gates.core
+"#;
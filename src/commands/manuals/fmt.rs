@@ -0,0 +1,59 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    fmt - format an ip's hdl source files
+
+SYNOPSIS
+    orbit fmt [options] [<path>...]
+
+DESCRIPTION
+    Rewrites the local ip's VHDL, Verilog, and SystemVerilog source files into a
+    consistent layout.
+    
+    Each line is indented according to its nesting within the design's blocks and
+    statements, and lines continuing a statement are indented one level further.
+    Declarations within port and generic lists are aligned at their colons, and
+    connections within port and generic maps are aligned at their arrows (VHDL) or
+    parentheses (Verilog and SystemVerilog). Keywords in VHDL files can be written
+    in a consistent case.
+    
+    The layout is driven by the 'vhdl-format' and 'verilog-format' sections of the
+    configuration. Comments are preserved exactly, and the lines of compiler
+    directives in Verilog and SystemVerilog files are kept as they are written. A
+    file is skipped with a warning if formatting it would change its source code.
+    
+    By default, every hdl file of the local ip is formatted. Provide one or more
+    paths to only format the files found at those paths.
+    
+    Use '--check' to list the files that are not formatted without modifying them.
+    This mode exits with an error if any file needs formatting or was skipped,
+    which makes it suitable for continuous integration.
+
+OPTIONS
+    <path>
+        Files or directories to format
+
+    --check
+        List the unformatted files without modifying them
+
+EXAMPLES
+    orbit fmt
+    orbit fmt ./rtl/counter.vhd
+    orbit fmt --check
+"#;
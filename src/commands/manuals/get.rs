@@ -60,6 +60,28 @@ DESCRIPTION
     Exporting the unit's declaration information can be accomplished by using the
     '--json' option. The valid json is produced with minimal formatting for
     encouragement to be processed by other programs.
+    
+    A complete testbench for the design unit can be generated with the
+    '--testbench' option. The testbench declares the unit's generics/parameters as
+    constants, declares a signal for every port, instantiates the unit, and
+    provides a stimulus process to be filled in. Single-bit inputs named like
+    clocks ('clk', 'clock') and resets ('rst', 'reset') are given generators, where
+    resets ending in '_n' or 'n' are treated as active-low. A clock's name must
+    begin or end with the clock word, and inputs that qualify a clock, such as
+    'clk_en' or 'clk_sel', are left as regular inputs. Generics without default
+    values are given a placeholder value and marked with a '@todo' comment. All other
+    display options are ignored when generating a testbench, and the output can be
+    redirected to a new file.
+    
+    The code snippets can be returned in a language other than the unit's native
+    language with the '--lang' option, which accepts "vhdl", "verilog", or "sv".
+    The unit's generics/parameters and ports are translated into the requested
+    language by mapping common types, such as 'std_logic_vector' ranges to packed
+    ranges and 'integer' generics to integer parameters. Sized Verilog literals keep
+    their width as VHDL-2008 bit string literals, such as '5'h1F' to '5x"1F"'. Any
+    type, mode, or default value that cannot be translated is reported as a warning
+    and left for the user to complete. The '--lang' option can be combined with
+    '--testbench' to generate a testbench in another language.
 
 OPTIONS
     <unit>
@@ -86,6 +108,12 @@ OPTIONS
     --architecture, -a
         Display the unit's architectures
 
+    --testbench
+        Display a complete testbench for the unit
+
+    --lang <lang>
+        Translate the snippets into vhdl, verilog, or sv
+
     --name <identifier>
         Set the instance's identifier
 
@@ -99,5 +127,7 @@ EXAMPLES
     orbit get and_gate --ip gates:1.0.0 --component
     orbit get ram --ip mem:2 -csi
     orbit get uart -si --name uart_inst0
+    orbit get fifo --testbench > fifo_tb.vhd
+    orbit get uart_tx --ip serial --lang vhdl -csi
     orbit get or_gate --ip gates --json
 "#;
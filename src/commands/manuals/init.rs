@@ -36,6 +36,29 @@ DESCRIPTION
     options are ignored when this option is present. Keep in mind that an ip's uuid
     is not intended to change over the course of its lifetime.
     
+    An existing FuseSoC CAPI2 core file can be converted into the manifest with the
+    '--from-core' option. The ip's name, version, and description are read from the
+    core file, and the ip's library is set to the first 'logical_name' found among
+    the core's files. The files from filesets used by the core's targets are
+    written to the 'include' list, while files from filesets used by no target are
+    written to the 'exclude' list. The core's top-level and VLNV are kept under the
+    '[ip.metadata.fusesoc]' table. The core's dependencies are looked up in the
+    catalog and written to the '[dependencies]' table, using the latest version
+    when the core does not give one, and are installed to create the lockfile.
+    Dependencies that are not found in the catalog are written as comments to be
+    uncommented once they are available. Any part of the core file that cannot be
+    converted, such as a conditional file, is reported as a warning.
+    
+    The source files of an existing project can be imported with the '--from-list'
+    option, which reads a plain '.f' file list, a Vivado project ('.xpr') or Tcl
+    script ('.tcl'), or a Quartus settings file ('.qsf'). Nested file lists given by
+    '-f', include directories given by '+incdir+', files added by 'read_vhdl',
+    'read_verilog', and 'add_files', and files assigned by 'set_global_assignment'
+    are written to the manifest's 'include' list. The ip's library is set to the
+    first library assigned to the files unless '--lib' is given. Any referenced
+    file that cannot be found or that is outside of the ip's directory is reported
+    as a warning.
+    
     The newly created manifest file is intended to be edited by the user. See more
     'Orbit.toml' keys and their definitions at:
     
@@ -53,6 +76,12 @@ OPTIONS
     --lib <lib>
         Set the resulting ip's library
 
+    --from-core <file>
+        Convert a fusesoc core file into the manifest
+
+    --from-list <file>
+        Include the files from an existing project's file list
+
     --uuid
         Print a new uuid and exit
 
@@ -60,5 +89,7 @@ EXAMPLES
     orbit init
     orbit init projects/gates
     orbit init --name adder
+    orbit init --from-core uart.core
+    orbit init --from-list sim/files.f
     orbit init --uuid
 "#;
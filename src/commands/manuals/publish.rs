@@ -33,6 +33,15 @@ DESCRIPTION
     Finally, the ip is downloaded from its source url and temporarily installed
     to verify its contents match those of the local ip.
     
+    If an earlier version of the ip is already published to its channel(s), the 
+    public units of the ip are compared against those of the newest earlier 
+    version. Removing a public unit, removing or renaming a port or generic, and 
+    changing the mode or type of a port require a major version bump. Adding a 
+    public unit, port, or generic requires a minor version bump, unless the added 
+    generic or input port has no default value, which requires a major version 
+    bump. Publishing stops when the ip's version is not a large enough bump. To 
+    publish the ip regardless, use the '--allow-breaking' option.
+    
     Posting an ip to a channel involves copying the ip's manifest file to a path 
     within the channel known as the index. For every publish of an ip, the index 
     corresponds to a unique path within the channel that gets created by Orbit.
@@ -53,7 +62,11 @@ OPTIONS
     --list
         View available channels and exit
 
+    --allow-breaking
+        Publish without enforcing the required version bump
+
 EXAMPLES
     orbit publish
     orbit publish --ready
+    orbit publish --ready --allow-breaking
 "#;
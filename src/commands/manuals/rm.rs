@@ -0,0 +1,41 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    rm - remove a dependency from an ip
+
+SYNOPSIS
+    orbit rm [options] <ip>
+
+DESCRIPTION
+    Removes the ip from the local ip's dependencies and development dependencies in 
+    its manifest. 
+    
+    The manifest is edited in place so its existing formatting and comments are 
+    kept. Afterward, the ip's lockfile is updated without the dependency.
+    
+    This command does not delete the ip from the catalog. To remove an ip from the
+    catalog, see the 'remove' command.
+
+OPTIONS
+    <ip>
+        Name of the dependency to remove
+
+EXAMPLES
+    orbit rm gates
+"#;
@@ -47,6 +47,21 @@ DESCRIPTION
     If an ip has a higher version that exists and is not currently installed, then
     an asterisk character "*" will appear next the ip's version. To update the ip
     to the latest version, see the 'install' command.
+    
+    To find which ip provides a design unit, use the '--unit' option. Every
+    primary design unit of the resulting ip is compared against the provided name,
+    and each matching unit is listed with its kind, language, visibility, and the
+    ip, version, and level it was found in. Units are matched by their full name,
+    by containing the provided name, or by being only a few edits away from it to
+    account for typos. Exact matches are listed first. To only return units with
+    the exact name, use the '--match' option. Private units are never listed.
+    
+    Units of installed and downloaded ip are read from their source code. Units of
+    ip only available via channels are read from the unit list recorded in the
+    channel when the ip was published.
+    
+    Use '--add' along with '--unit' to add the ip providing the closest matching
+    unit to the local ip's dependencies after confirming the prompt.
 
 OPTIONS
     <ip>
@@ -70,8 +85,16 @@ OPTIONS
     --match
         Return results that pass each filter
 
+    --unit <name>
+        List the units matching this name
+
+    --add
+        Add the ip of the closest matching unit as a dependency
+
 EXAMPLES
     orbit search axi
     orbit search --keyword memory --keyword ecc
     orbit search --keyword cdc --limit 20 -i
+    orbit search --unit fifo_async
+    orbit search --unit fifo_async --add
 "#;
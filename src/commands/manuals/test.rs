@@ -51,6 +51,23 @@ DESCRIPTION
     
     The target's process will spawn from the current working ip's output directory,
     which is $ORBIT_TARGET_DIR/$ORBIT_TARGET.
+    
+    Generics (VHDL) or parameters (Verilog) can be set on the testbench with
+    '--generic', given as 'NAME=VALUE'. Each name must be declared by the
+    testbench's interface. The values are passed to the target through the
+    ORBIT_GENERICS environment variable. Giving a comma-separated list of values,
+    such as '--generic WIDTH=8,16,32', sweeps the generic: the target is executed
+    once for every combination of values, each in its own output directory, and a
+    summary of the results is displayed at the end.
+    
+    With '--watch', the target is executed again every time a file of the working
+    ip or one of its relative dependencies changes, until the command is
+    interrupted. Files are checked for changes by polling, and a burst of writes is
+    treated as a single change. The design is planned again only when a change
+    affects its structure: adding or removing an hdl file, editing the design
+    units or references within an hdl file, or editing the manifest. Otherwise,
+    the existing blueprint is reused. Each run ends with a line reporting whether
+    it passed or failed.
 
 OPTIONS
     --target, -t <name>
@@ -86,6 +103,12 @@ OPTIONS
     --force
         Force the target to execute 
 
+    --watch
+        Execute the target again whenever a file changes
+
+    --generic <name=value>...
+        Set a generic on the testbench, or sweep a list of values
+
     --verbose
         Display the command being executed
 
@@ -94,4 +117,6 @@ OPTIONS
 
 EXAMPLES
     orbit test --dut adder --tb adder_tb --target modelsim -- --lint
+    orbit test --tb adder_tb --target modelsim --watch
+    orbit test --tb adder_tb --target modelsim --generic WIDTH=8,16,32
 "#;
@@ -50,6 +50,16 @@ DESCRIPTION
     
     To display the ip dependency graph, use the '--ip' option.
     
+    To list the instance hierarchy instead of the unit hierarchy, use the
+    '--instances' option. Every instance is printed by its full path of instance
+    and generate block labels, such as "top/u_core/gen_lane[0]/u_alu", alongside
+    the unit it instantiates and any generic or parameter associations. Loop
+    generate blocks with literal bounds are expanded into one path per index,
+    while other loops and loops with more than 256 iterations are shown with the
+    index "[*]". A unit that instantiates itself, directly or indirectly, is
+    marked as recursive and its instances are not listed again. Afterward, the
+    number of instances of each unit is listed.
+    
     If the tree's character output is not displaying properly, then the tree can
     be displayed using a set of standard ASCII characters with the '--ascii'
     option.
@@ -67,8 +77,12 @@ OPTIONS
     --ascii
         Limit the textual tree characters to the 128 ascii set
 
+    --instances
+        List the full path of every instance and count them
+
 EXAMPLES
     orbit tree
     orbit tree top --format long
     orbit tree -e ip --ascii
+    orbit tree top --instances
 "#;
@@ -0,0 +1,60 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    version - bump the version of an ip
+
+SYNOPSIS
+    orbit version [options] [<level>]
+
+DESCRIPTION
+    Increments the version of the local ip in its manifest. The '<level>' is either
+    'major', 'minor', or 'micro'. Incrementing a level resets the levels below it 
+    and removes any pre-release label. Without a '<level>' or '--label', the ip's
+    current version is printed.
+    
+    Use the '--label' option to set a pre-release label on the new version. When 
+    no '<level>' is provided, the label is set on the current version.
+    
+    The manifest is edited in place so its existing formatting and comments are 
+    kept. Afterward, the ip's lockfile is updated to match the new version.
+    
+    A new version is not allowed to go backwards; it must be greater than every 
+    version of the ip already known in the catalog, including those available via
+    channels.
+    
+    Use '--changelog' to add a section for the new version with today's date at the
+    top of the ip's changelog. If the ip does not have a changelog, one is created 
+    as "CHANGELOG.md" at the ip's root directory.
+
+OPTIONS
+    <level>
+        Version level to increment: major, minor, or micro
+
+    --label <label>
+        Pre-release label to set for the new version
+
+    --changelog
+        Add a dated section for the new version to the changelog
+
+EXAMPLES
+    orbit version
+    orbit version minor --changelog
+    orbit version major --label rc1
+    orbit version --label rc2
+"#;
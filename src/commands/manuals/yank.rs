@@ -0,0 +1,61 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+// This manual page was automatically generated from the mangen.py tool.
+pub const MANUAL: &str = r#"NAME
+    yank - discourage the use of a published ip
+
+SYNOPSIS
+    orbit yank [options] <ip>
+
+DESCRIPTION
+    Marks a version of an ip published to the configured channels as yanked. The
+    '<ip>' must include the full version to yank. A yanked version remains in its
+    channels, but it is skipped when resolving dependencies and finding the latest
+    version of the ip. An ip that already has the yanked version in its lockfile 
+    continues to use it, with a warning when the lockfile is updated.
+    
+    Use '--deprecate' to mark the entire ip as deprecated instead. Every published
+    version of a deprecated ip can still be used, but a warning is displayed when
+    it is locked as a dependency or viewed with 'orbit search' and 'orbit info'.
+    Versions published after the deprecation are deprecated with the same reason.
+    
+    A reason must be provided with '--reason', which is shown to users in these 
+    warnings. To remove a yank or deprecation, use '--undo'.
+    
+    A channel's pre-publish and post-publish hooks run around updating each 
+    channel. When yanking a version, the hooks can get the path to the version's 
+    index by reading the ORBIT_CHAN_INDEX environment variable.
+
+OPTIONS
+    <ip>
+        Ip specification
+
+    --reason <text>
+        Explanation shown to users of the ip
+
+    --deprecate
+        Mark every version of the ip as deprecated
+
+    --undo
+        Remove the yank or deprecation
+
+EXAMPLES
+    orbit yank gates:1.0.2 --reason "reset is stuck high"
+    orbit yank gates:1.0.2 --undo
+    orbit yank uart --deprecate --reason "use uart2 instead"
+"#;
@@ -19,10 +19,16 @@
 pub mod orbit;
 
 // commands
+mod add;
 mod build;
 mod config;
+mod diff;
+mod doc;
 mod download;
 mod env;
+mod exec;
+mod export;
+mod fmt;
 mod get;
 mod help;
 mod info;
@@ -33,9 +39,12 @@ mod plan;
 mod publish;
 mod read;
 mod remove;
+mod rm;
 mod search;
 mod test;
 mod tree;
+mod version;
+mod yank;
 
 // informational content for help about commands
 mod helps;
@@ -153,9 +153,15 @@ impl Command for Orbit {
     }
 }
 
+use crate::commands::add::Add;
 use crate::commands::build::Build;
 use crate::commands::config::Config;
+use crate::commands::diff::Diff;
+use crate::commands::doc::Doc;
 use crate::commands::env::Env;
+use crate::commands::exec::Exec;
+use crate::commands::export::Export;
+use crate::commands::fmt::Fmt;
 use crate::commands::get::Get;
 use crate::commands::help::Help;
 use crate::commands::info::Info;
@@ -166,21 +172,33 @@ use crate::commands::new::New;
 use crate::commands::publish::Publish;
 use crate::commands::read::Read;
 use crate::commands::remove::Remove;
+use crate::commands::rm::Rm;
 use crate::commands::search::Search;
 use crate::commands::test::Test;
 use crate::commands::tree::Tree;
+use crate::commands::version::Version as SetVersion;
+use crate::commands::yank::Yank;
 
 #[derive(Debug, PartialEq)]
 enum OrbitSubcommand {
     Help(Help),
     New(New),
     Search(Search),
+    Diff(Diff),
     Lock(Lock),
+    Add(Add),
+    Rm(Rm),
     Build(Build),
     Test(Test),
+    Exec(Exec),
+    Export(Export),
     Publish(Publish),
+    Yank(Yank),
+    Version(SetVersion),
     Install(Install),
     Tree(Tree),
+    Doc(Doc),
+    Fmt(Fmt),
     Get(Get),
     Init(Init),
     Info(Info),
@@ -195,7 +213,8 @@ impl Subcommand<Context> for OrbitSubcommand {
         match cli
             .select(&[
                 "help", "new", "search", "lock", "build", "test", "t", "publish", "install", "get",
-                "init", "tree", "info", "b", "env", "config", "remove", "read",
+                "init", "tree", "doc", "fmt", "info", "b", "env", "config", "remove", "read",
+                "export", "exec", "diff", "version", "add", "rm", "yank",
             ])?
             .as_ref()
         {
@@ -203,13 +222,22 @@ impl Subcommand<Context> for OrbitSubcommand {
             "help" => Ok(OrbitSubcommand::Help(Help::interpret(cli)?)),
             "new" => Ok(OrbitSubcommand::New(New::interpret(cli)?)),
             "search" => Ok(OrbitSubcommand::Search(Search::interpret(cli)?)),
+            "diff" => Ok(OrbitSubcommand::Diff(Diff::interpret(cli)?)),
             "lock" => Ok(OrbitSubcommand::Lock(Lock::interpret(cli)?)),
+            "add" => Ok(OrbitSubcommand::Add(Add::interpret(cli)?)),
+            "rm" => Ok(OrbitSubcommand::Rm(Rm::interpret(cli)?)),
             "b" | "build" => Ok(OrbitSubcommand::Build(Build::interpret(cli)?)),
             "t" | "test" => Ok(OrbitSubcommand::Test(Test::interpret(cli)?)),
+            "exec" => Ok(OrbitSubcommand::Exec(Exec::interpret(cli)?)),
+            "export" => Ok(OrbitSubcommand::Export(Export::interpret(cli)?)),
             "init" => Ok(OrbitSubcommand::Init(Init::interpret(cli)?)),
             "publish" => Ok(OrbitSubcommand::Publish(Publish::interpret(cli)?)),
+            "yank" => Ok(OrbitSubcommand::Yank(Yank::interpret(cli)?)),
+            "version" => Ok(OrbitSubcommand::Version(SetVersion::interpret(cli)?)),
             "install" => Ok(OrbitSubcommand::Install(Install::interpret(cli)?)),
             "tree" => Ok(OrbitSubcommand::Tree(Tree::interpret(cli)?)),
+            "doc" => Ok(OrbitSubcommand::Doc(Doc::interpret(cli)?)),
+            "fmt" => Ok(OrbitSubcommand::Fmt(Fmt::interpret(cli)?)),
             "info" => Ok(OrbitSubcommand::Info(Info::interpret(cli)?)),
             "env" => Ok(OrbitSubcommand::Env(Env::interpret(cli)?)),
             "config" => Ok(OrbitSubcommand::Config(Config::interpret(cli)?)),
@@ -223,14 +251,23 @@ impl Subcommand<Context> for OrbitSubcommand {
         match self {
             OrbitSubcommand::Get(sub) => sub.execute(context),
             OrbitSubcommand::Search(sub) => sub.execute(context),
+            OrbitSubcommand::Diff(sub) => sub.execute(context),
             OrbitSubcommand::Lock(sub) => sub.execute(context),
+            OrbitSubcommand::Add(sub) => sub.execute(context),
+            OrbitSubcommand::Rm(sub) => sub.execute(context),
             OrbitSubcommand::Build(sub) => sub.execute(context),
             OrbitSubcommand::Install(sub) => sub.execute(context),
             OrbitSubcommand::Help(sub) => sub.execute(&()),
             OrbitSubcommand::New(sub) => sub.execute(context),
             OrbitSubcommand::Test(sub) => sub.execute(context),
+            OrbitSubcommand::Exec(sub) => sub.execute(context),
+            OrbitSubcommand::Export(sub) => sub.execute(context),
             OrbitSubcommand::Publish(sub) => sub.execute(context),
+            OrbitSubcommand::Yank(sub) => sub.execute(context),
+            OrbitSubcommand::Version(sub) => sub.execute(context),
             OrbitSubcommand::Tree(sub) => sub.execute(context),
+            OrbitSubcommand::Doc(sub) => sub.execute(context),
+            OrbitSubcommand::Fmt(sub) => sub.execute(context),
             OrbitSubcommand::Init(sub) => sub.execute(context),
             OrbitSubcommand::Info(sub) => sub.execute(context),
             OrbitSubcommand::Env(sub) => sub.execute(context),
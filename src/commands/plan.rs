@@ -21,11 +21,15 @@ use crate::commands::download::Download;
 use crate::core::blueprint::{Blueprint, Instruction, Scheme};
 use crate::core::context::{self, Context};
 use crate::core::fileset::Fileset;
+use crate::core::generic::{self, Combination};
 use crate::core::iparchive::IpArchive;
+use crate::core::lang::instance::Instance;
 use crate::core::lang::parser::ParseError;
 use crate::core::lang::reference::CompoundIdentifier;
 use crate::core::lang::sv::symbols::{SystemVerilogParser, SystemVerilogSymbol};
+use crate::core::lang::verilog::symbols::config::{Binding, Config};
 use crate::core::lang::verilog::symbols::{VerilogParser, VerilogSymbol};
+use crate::core::lang::verilog::token::identifier::Identifier as VerilogIdentifier;
 use crate::core::lang::vhdl::subunit::SubUnit;
 use crate::core::lang::vhdl::symbols::{VHDLParser, VhdlSymbol};
 use crate::core::lang::vhdl::token::Identifier;
@@ -42,18 +46,20 @@ use crate::util::environment::Environment;
 use crate::util::filesystem;
 use crate::util::graph::EdgeStatus;
 use crate::util::graphmap::GraphMap;
+use crate::warn;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::hash::Hash;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use crate::commands::install::Install;
 use crate::core::algo;
 use crate::core::algo::IpFileNode;
 use crate::core::algo::IpNode;
-use crate::core::catalog::Catalog;
-use crate::core::ip::Ip;
+use crate::core::catalog::{Catalog, PkgName};
 use crate::core::ip::IpSpec;
+use crate::core::ip::{Ip, PartialIpSpec};
 use crate::core::lockfile::LockEntry;
 use crate::core::lockfile::LockFile;
 use crate::util::graphmap::Node;
@@ -75,11 +81,13 @@ pub struct Plan {
 impl Plan {
     /// Performs the backend logic for creating a blueprint file (planning a design).
     ///
-    /// If a blueprint was created, it will return the file name for that blueprint.
+    /// If a blueprint was created, it will return the file name for that blueprint
+    /// along with every file the blueprint references.
     pub fn run(
         working_ip: &Ip,
         target_dir: &str,
         target: &Target,
+        out_dir: &str,
         catalog: Catalog,
         clean: bool,
         force: bool,
@@ -88,14 +96,15 @@ impl Plan {
         bench_name: &Option<Identifier>,
         top_name: &Option<Identifier>,
         filesets: &Option<Vec<Fileset>>,
+        generics: &Combination,
         scheme: &Scheme,
         require_bench: bool,
         allow_bench: bool,
-    ) -> Result<Option<String>, Fault> {
+    ) -> Result<Option<(String, Vec<String>)>, Fault> {
         // create the output path to know where to begin storing files
         let working_ip_path = working_ip.get_root().clone();
         let target_path = working_ip_path.join(target_dir);
-        let output_path = target_path.join(target.get_name());
+        let output_path = target_path.join(out_dir);
 
         // build entire ip graph and resolve with dynamic symbol transformation
         let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog) {
@@ -107,7 +116,7 @@ impl Plan {
                     let ip_file_node = IpFileNode::new(
                         e.as_source_file().unwrap().to_string(),
                         &working_ip,
-                        LangIdentifier::new_working(),
+                        working_ip.get_hdl_library(),
                     );
                     blueprint.add(Instruction::Hdl(&ip_file_node));
 
@@ -120,6 +129,8 @@ impl Plan {
                         &String::new(),
                         &String::new(),
                         target,
+                        out_dir,
+                        generics,
                         require_bench,
                     )?;
                     // create a blueprint file
@@ -128,7 +139,7 @@ impl Plan {
                         "warning".yellow(),
                         filesystem::into_std_str(blueprint_path)
                     );
-                    return Ok(Some(blueprint_name));
+                    return Ok(Some((blueprint_name, blueprint.get_files())));
                 } else {
                     return match e.is_source_err() {
                         true => Err(Error::SourceCodeInvalidSyntax(
@@ -248,6 +259,32 @@ impl Plan {
             return Err(Error::TestbenchRequired)?;
         }
 
+        // verify the generics exist on the unit that receives them
+        if generics.is_empty() == false {
+            let sym = match bench.or(top) {
+                Some(i) => global_graph
+                    .get_node_by_index(i)
+                    .unwrap()
+                    .as_ref()
+                    .get_symbol(),
+                None => return Err(Error::GenericsWithoutTop)?,
+            };
+            let names = sym.get_generic_names();
+            for (name, _) in generics {
+                let exists = match sym {
+                    // vhdl identifiers are case insensitive
+                    HdlSymbol::Vhdl(_) => names.iter().any(|n| n.eq_ignore_ascii_case(name)),
+                    _ => names.contains(name),
+                };
+                if exists == false {
+                    return Err(Error::GenericNotFound(
+                        name.clone(),
+                        sym.get_name().to_string(),
+                    ))?;
+                }
+            }
+        }
+
         // [!] write the lock file
         Self::write_lockfile(&working_ip, &ip_graph, true, true, &catalog)?;
 
@@ -289,14 +326,30 @@ impl Plan {
                         None => return Err(AnyError(format!("no top-level unit exists")))?,
                     },
                 };
-                global_graph
-                    .get_graph()
-                    .minimal_topological_sort(highest_point)
+                match global_graph
+                    .get_node_by_index(highest_point)
+                    .unwrap()
+                    .as_ref()
+                    .get_symbol()
+                    .as_config()
+                {
+                    // only order the cells selected by the configuration
+                    Some(_) => Self::bind_config(&global_graph, highest_point)?,
+                    None => global_graph
+                        .get_graph()
+                        .minimal_topological_sort(highest_point),
+                }
             }
         };
 
         // println!("{:?}", min_order);
 
+        // report instantiations that do not match the interfaces of their units
+        Self::check_connections(&global_graph, &min_order);
+
+        // map every file to the files it depends on
+        let file_deps = Self::determine_file_deps(&global_graph, &min_order);
+
         // generate the file order while merging dependencies for common file path names together
         let file_order = Self::determine_file_order(&global_graph, min_order);
 
@@ -366,7 +419,9 @@ impl Plan {
         }
 
         // store data in blueprint
-        let mut blueprint = Blueprint::new(scheme.clone());
+        let mut blueprint = Blueprint::new(scheme.clone())
+            .filelist(target.get_filelist())
+            .ninja(target.get_ninja());
 
         // [!] collect user-defined filesets
         {
@@ -439,6 +494,43 @@ impl Plan {
             }
         }
 
+        // collect the directories of included header files
+        if scheme == &Scheme::Filelist {
+            let headers = Self::collect_headers(&files);
+            for dir in Self::find_include_dirs(&file_order, &headers) {
+                blueprint.add_include_dir(dir);
+            }
+        }
+
+        // collect the files each file must be analyzed after
+        if scheme == &Scheme::Ninja {
+            let config = target.get_ninja().cloned().unwrap_or_default();
+            let headers = Self::collect_headers(&files);
+            for ip_file_node in &file_order {
+                let file = ip_file_node.get_file();
+                if let Err(field) = config.get_command(file) {
+                    return Err(AnyError(format!(
+                        "target \"{}\" is missing a command to analyze {} files; set its \"ninja.{}\" field",
+                        target.get_name(),
+                        field,
+                        field
+                    )))?;
+                }
+                let mut deps: Vec<String> = match file_deps.get(file) {
+                    Some(d) => d.iter().map(|f| f.to_string()).collect(),
+                    None => Vec::new(),
+                };
+                if fileset::is_vhdl(file) == false {
+                    deps.extend(
+                        Self::find_headers(file, &headers)
+                            .into_iter()
+                            .map(|(h, _)| h),
+                    );
+                }
+                blueprint.set_file_deps(file.to_string(), deps);
+            }
+        }
+
         // collect in-order HDL file list
         for ip_file_node in file_order {
             if fileset::is_rtl(&ip_file_node.get_file()) == true {
@@ -458,6 +550,8 @@ impl Plan {
             &bench_name,
             &bench_file,
             target,
+            out_dir,
+            generics,
             require_bench,
         )?;
         // create a blueprint file
@@ -465,7 +559,65 @@ impl Plan {
             "info: blueprint created at: {:?}",
             filesystem::into_std_str(blueprint_path)
         );
-        Ok(Some(blueprint_name))
+        Ok(Some((blueprint_name, blueprint.get_files())))
+    }
+
+    /// Computes the in-order list of hdl files required by the top-level unit of
+    /// the working ip without writing a blueprint.
+    ///
+    /// Returns the top-level unit's name along with each file's hdl library and path.
+    pub fn compute_file_order(
+        working_ip: &Ip,
+        catalog: &Catalog,
+        top_name: &Option<Identifier>,
+    ) -> Result<(LangIdentifier, Vec<(LangIdentifier, String)>), Fault> {
+        let ip_graph = match algo::compute_final_ip_graph(&working_ip, &catalog) {
+            Ok(g) => g,
+            Err(e) => {
+                return match e.is_source_err() {
+                    true => Err(Error::SourceCodeInvalidSyntax(
+                        e.as_source_file().unwrap().clone().into(),
+                        LastError(e.into_fault().to_string()),
+                    ))?,
+                    false => Err(Error::IpGraphFailed(LastError(e.into_fault().to_string())))?,
+                };
+            }
+        };
+
+        let files = algo::build_ip_file_list(&ip_graph, &working_ip);
+        let global_graph = Self::build_full_graph(&files)?;
+        let working_lib = working_ip.get_hdl_library();
+        let local_graph = Self::compute_local_graph(&global_graph, &working_ip);
+
+        let (top, _) = Self::detect_top(
+            &global_graph,
+            &local_graph,
+            &working_lib,
+            None,
+            None,
+            &top_name,
+            false,
+        )?;
+        let top = match top {
+            Some(i) => Self::local_to_global(i, &global_graph, &local_graph).index(),
+            None => return Err(AnyError(format!("no top-level unit exists")))?,
+        };
+
+        let min_order = global_graph.get_graph().minimal_topological_sort(top);
+        let file_order = Self::determine_file_order(&global_graph, min_order);
+        let file_order = Self::remove_multi_occurences(&file_order);
+
+        Ok((
+            global_graph
+                .get_key_by_index(top)
+                .unwrap()
+                .get_suffix()
+                .clone(),
+            file_order
+                .into_iter()
+                .map(|f| (f.get_library(), f.get_file().clone()))
+                .collect(),
+        ))
     }
 }
 
@@ -491,10 +643,14 @@ pub fn resolve_missing_deps<'a>(
 
         install_missing_deps(&lf, &le, &catalog)?;
         // recollect the installations to update the catalog for dependency graphing
-        catalog.installations(c.get_cache_path())
-    } else {
-        Ok(catalog)
+        catalog = catalog.installations(c.get_cache_path())?;
+    }
+    // replace dependencies that were yanked since they were installed
+    if install_unyanked_deps(c, working_ip)? == true {
+        catalog = catalog.installations(c.get_cache_path())?;
     }
+    // mark the versions yanked and ip deprecated in the channels
+    catalog.statuses(&c.get_config().get_channels())
 }
 
 pub fn download_missing_deps(
@@ -577,6 +733,71 @@ pub fn download_missing_deps(
     Ok(())
 }
 
+/// Installs a compatible version that is not yanked for every dependency of the
+/// `working_ip` whose installed compatible versions are all yanked.
+///
+/// A yanked version is still used when it is the version in the lockfile.
+/// Returns `true` if any ip was installed.
+pub fn install_unyanked_deps(c: &Context, working_ip: &Ip) -> Result<bool, Fault> {
+    let catalog = Catalog::new()
+        .installations(c.get_cache_path())?
+        .downloads(c.get_downloads_path())?
+        .available(&c.get_config().get_channels())?;
+
+    let mut installed = false;
+    let mut visited = HashSet::new();
+    let mut processing = vec![working_ip];
+    let mut is_root = true;
+    while let Some(ip) = processing.pop() {
+        for (pkgid, dependency) in ip.get_man().get_deps_list(is_root, true) {
+            if dependency.is_relative() == true {
+                if let Some(relative_ip) = dependency.as_ip() {
+                    if visited.insert(relative_ip.get_man().get_ip().into_ip_spec()) == true {
+                        processing.push(relative_ip);
+                    }
+                }
+                continue;
+            }
+            let entry = working_ip.get_lock().get(pkgid, dependency.get_version());
+            let uuid = match entry {
+                Some(e) => Some(e.get_uuid()),
+                None => dependency.as_uuid(),
+            };
+            let level = match catalog.translate_name(&PkgName::new(pkgid, uuid))? {
+                Some(lvl) => lvl,
+                None => continue,
+            };
+            let version = AnyVersion::Specific(dependency.get_version().clone());
+            match level.resolve_install(&version, entry.map(|e| e.get_version())) {
+                Some(dep) => {
+                    if visited.insert(dep.get_man().get_ip().into_ip_spec()) == true {
+                        processing.push(dep);
+                    }
+                }
+                None => {
+                    // only act when the installed compatible versions are yanked
+                    if level.get_install(&version).is_none() == true {
+                        continue;
+                    }
+                    if let Some(target) = level.get(true, true, &version) {
+                        Install::install_from_catalog(
+                            PartialIpSpec::new(
+                                pkgid.clone(),
+                                Some(target.get_uuid().clone()),
+                                target.get_man().get_ip().get_version().to_partial_version(),
+                            ),
+                            c,
+                        )?;
+                        installed = true;
+                    }
+                }
+            }
+        }
+        is_root = false;
+    }
+    Ok(installed)
+}
+
 pub fn install_missing_deps(lf: &LockFile, le: &LockEntry, catalog: &Catalog) -> Result<(), Fault> {
     // fill in the catalog with missing modules according the lock file if available
     for entry in lf.inner() {
@@ -1051,6 +1272,11 @@ impl Plan {
                 None => continue,
             };
             entity_node.as_ref_mut().add_file(node.get_file());
+            if let SubUnit::Architecture(arch) = node.get_sub() {
+                entity_node
+                    .as_ref_mut()
+                    .set_instances(arch.get_instances().iter().map(|i| i.to_lang()).collect());
+            }
             // create edges (this is very important)
             for dep in node.get_sub().get_edge_list() {
                 // println!("{:?}", dep);
@@ -1070,6 +1296,19 @@ impl Plan {
         }
 
         // go through all nodes and make the connections
+        Self::connect_edges_from_refs(&mut graph_map);
+        Ok(graph_map)
+    }
+
+    /// Connects every design unit in the graph to the design units it references,
+    /// such as packages brought in by use clauses, instantiated generic packages,
+    /// and contexts.
+    ///
+    /// References prefixed with the `work` library are resolved to the library of
+    /// the unit that made the reference.
+    pub fn connect_edges_from_refs<'a>(
+        graph_map: &mut GraphMap<CompoundIdentifier, HdlNode<'a>, ()>,
+    ) -> () {
         let idens: Vec<CompoundIdentifier> = graph_map
             .get_map()
             .into_iter()
@@ -1126,7 +1365,286 @@ impl Plan {
                 // }
             }
         }
-        Ok(graph_map)
+    }
+
+    /// Computes the order of the design units selected by the Verilog configuration
+    /// at index `config`, where every unit appears after the units it depends on.
+    ///
+    /// Each instance is bound by the first applicable source: an instance rule, a
+    /// cell rule, the library list inherited from a rule above it, the default
+    /// library list, and finally the library of its parent cell.
+    fn bind_config(
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        config: usize,
+    ) -> Result<Vec<usize>, Fault> {
+        let cfg_key = global_graph.get_key_by_index(config).unwrap();
+        let cfg = global_graph
+            .get_node_by_index(config)
+            .unwrap()
+            .as_ref()
+            .get_symbol()
+            .as_config()
+            .unwrap();
+        let cfg_lib = cfg_key.get_prefix().unwrap().clone();
+
+        let mut order = Vec::new();
+        for cell in cfg.get_design() {
+            // design cells without a library come from the configuration's library
+            let libs = match cell.get_lib() {
+                Some(lib) => Self::into_libs(&vec![lib.clone()], &cfg_lib),
+                None => vec![cfg_lib.clone()],
+            };
+            let node = match Self::find_cell(global_graph, &libs, cell.get_cell()) {
+                Some(n) => n,
+                None => {
+                    return Err(Error::ConfigCellNotFound(
+                        cell.get_cell().to_string(),
+                        cfg.get_name().to_string(),
+                    ))?
+                }
+            };
+            let mut path = vec![cell.get_cell().clone()];
+            Self::bind_instances(
+                global_graph,
+                cfg,
+                &cfg_lib,
+                node,
+                &mut path,
+                None,
+                &mut order,
+            );
+        }
+        order.push(config);
+
+        // keep the first occurrence of every unit
+        let mut visited = HashSet::new();
+        Ok(order.into_iter().filter(|i| visited.insert(*i)).collect())
+    }
+
+    /// Appends the units that the cell at index `node` depends on, and then the
+    /// cell itself, to the `order`.
+    fn bind_instances(
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        cfg: &Config,
+        cfg_lib: &LangIdentifier,
+        node: usize,
+        path: &mut Vec<VerilogIdentifier>,
+        liblist: Option<&Vec<LangIdentifier>>,
+        order: &mut Vec<usize>,
+    ) -> () {
+        let module = match global_graph
+            .get_node_by_index(node)
+            .unwrap()
+            .as_ref()
+            .get_symbol()
+            .as_module()
+        {
+            Some(m) => m,
+            // units from other languages keep their own dependencies
+            None => {
+                order.extend(global_graph.get_graph().minimal_topological_sort(node));
+                return;
+            }
+        };
+        // stop on recursive instantiations
+        if path.len() > 64 {
+            return;
+        }
+        let parent_lib = vec![global_graph
+            .get_key_by_index(node)
+            .unwrap()
+            .get_prefix()
+            .unwrap()
+            .clone()];
+        let default_libs = cfg
+            .get_default_liblist()
+            .map(|l| Self::into_libs(l, cfg_lib));
+        let search = liblist.or(default_libs.as_ref()).unwrap_or(&parent_lib);
+
+        for inst in module.get_instances() {
+            path.push(inst.get_name().clone());
+            let (child, inherited) = match cfg.find_binding(path, inst.get_unit()) {
+                Some(Binding::Use(cell)) => {
+                    let libs = match cell.get_lib() {
+                        Some(lib) => Self::into_libs(&vec![lib.clone()], cfg_lib),
+                        None => search.clone(),
+                    };
+                    (
+                        Self::find_cell(global_graph, &libs, cell.get_cell()),
+                        liblist.cloned(),
+                    )
+                }
+                Some(Binding::Liblist(libs)) => {
+                    let libs = Self::into_libs(libs, cfg_lib);
+                    (
+                        Self::find_cell(global_graph, &libs, inst.get_unit()),
+                        Some(libs),
+                    )
+                }
+                None => (
+                    Self::find_cell(global_graph, search, inst.get_unit()),
+                    liblist.cloned(),
+                ),
+            };
+            // fall back to the cell from any library, as done without a configuration
+            let child = child.or_else(|| {
+                global_graph
+                    .get_map()
+                    .iter()
+                    .find(|(k, _)| {
+                        k.get_suffix() == &LangIdentifier::Verilog(inst.get_unit().clone())
+                    })
+                    .map(|(_, n)| n.index())
+            });
+            if let Some(child) = child {
+                Self::bind_instances(
+                    global_graph,
+                    cfg,
+                    cfg_lib,
+                    child,
+                    path,
+                    inherited.as_ref(),
+                    order,
+                );
+            }
+            path.pop();
+        }
+
+        // include the packages, interfaces, and other units the cell references
+        global_graph
+            .get_graph()
+            .predecessors(node)
+            .filter(|p| {
+                global_graph
+                    .get_node_by_index(*p)
+                    .unwrap()
+                    .as_ref()
+                    .get_symbol()
+                    .is_component()
+                    == false
+            })
+            .collect::<Vec<usize>>()
+            .into_iter()
+            .for_each(|p| order.extend(global_graph.get_graph().minimal_topological_sort(p)));
+        order.push(node);
+    }
+
+    /// Warns about every instance within the units of `order` whose generic or
+    /// port associations do not fit the interface of its instantiated unit.
+    fn check_connections(
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        order: &Vec<usize>,
+    ) -> () {
+        for i in order {
+            let node = global_graph.get_node_by_index(*i).unwrap().as_ref();
+            if node.get_instances().is_empty() == true {
+                continue;
+            }
+            let lib = global_graph.get_key_by_index(*i).unwrap().get_prefix();
+            let parent = node.get_symbol().get_interface();
+            for inst in node.get_instances() {
+                let unit = match Self::resolve_instance(global_graph, lib, inst) {
+                    Some(c) => global_graph.get_node_by_index(c).unwrap().as_ref(),
+                    None => continue,
+                };
+                let interface = match unit.get_symbol().get_interface() {
+                    Some(x) => x,
+                    None => continue,
+                };
+                for issue in inst.check(&interface, parent.as_ref()) {
+                    warn!(
+                        "instance {:?} of {:?} in {:?} {}",
+                        inst.get_name().to_string(),
+                        unit.get_symbol().get_name().to_string(),
+                        node.get_symbol().get_name().to_string(),
+                        issue
+                    );
+                }
+            }
+        }
+    }
+
+    /// Finds the index of the unit instantiated by `inst` within a unit of library `lib`.
+    pub fn resolve_instance(
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        lib: Option<&LangIdentifier>,
+        inst: &Instance<LangIdentifier>,
+    ) -> Option<usize> {
+        // an explicit library of "work" refers to the instantiating unit's library
+        let lib = match inst.get_library() {
+            Some(l) if l == &LangIdentifier::new_working() => lib,
+            Some(l) => Some(l),
+            None => lib,
+        };
+        if let Some(lib) = lib {
+            if let Some(n) = global_graph.get_node_by_key(&CompoundIdentifier::new(
+                lib.clone(),
+                inst.get_unit().clone(),
+            )) {
+                return Some(n.index());
+            }
+        }
+        // fall back to the unit from any library
+        global_graph
+            .get_map()
+            .iter()
+            .filter(|(k, n)| {
+                k.get_suffix() == inst.get_unit() && n.as_ref().get_symbol().is_component()
+            })
+            .map(|(_, n)| n.index())
+            .min()
+    }
+
+    /// Returns the index of the first library in `libs` that defines the `cell`.
+    ///
+    /// Cells and libraries renamed by dynamic symbol transformation are found by
+    /// their original names.
+    fn find_cell(
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        libs: &Vec<LangIdentifier>,
+        cell: &VerilogIdentifier,
+    ) -> Option<usize> {
+        libs.iter().find_map(|lib| {
+            match global_graph.get_node_by_key(&CompoundIdentifier::new(
+                lib.clone(),
+                LangIdentifier::Verilog(cell.clone()),
+            )) {
+                Some(n) => Some(n.index()),
+                None => global_graph
+                    .get_map()
+                    .iter()
+                    .find(|(k, _)| {
+                        k.get_prefix().is_some_and(|p| {
+                            Self::is_original_name(&p.to_string(), &lib.to_string())
+                        }) && Self::is_original_name(&k.get_suffix().to_string(), cell.as_str())
+                    })
+                    .map(|(_, n)| n.index()),
+            }
+        })
+    }
+
+    /// Checks if `name` is the identifier `original`, or `original` after it
+    /// received a checksum suffix from dynamic symbol transformation.
+    fn is_original_name(name: &str, original: &str) -> bool {
+        match name.strip_prefix(original) {
+            Some("") => true,
+            Some(rest) => match rest.strip_prefix('_') {
+                Some(sum) => sum.len() == 10 && sum.chars().all(|c| c.is_ascii_hexdigit()),
+                None => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Converts the library names of a configuration into library identifiers,
+    /// where `work` refers to the configuration's own library `cfg_lib`.
+    fn into_libs(libs: &Vec<VerilogIdentifier>, cfg_lib: &LangIdentifier) -> Vec<LangIdentifier> {
+        libs.iter()
+            .filter_map(|l| match l.as_str() {
+                "work" => Some(cfg_lib.clone()),
+                _ => LangIdentifier::from_str(l.as_str()).ok(),
+            })
+            .collect()
     }
 
     /// Writes the lockfile according to the constructed `ip_graph`. Only writes if the lockfile is
@@ -1355,17 +1873,21 @@ impl Plan {
                 Some(nt) => Some(nt),
                 None => {
                     if let Some(b) = bench {
+                        let bench_sym = local.get_node_by_index(b).unwrap().as_ref().get_symbol();
                         let entities: Vec<(usize, &HdlSymbol)> = local
                             .get_graph()
                             .predecessors(b)
                             .filter_map(|f| {
-                                if local
-                                    .get_node_by_index(f)
-                                    .unwrap()
-                                    .as_ref()
-                                    .get_symbol()
-                                    .is_component()
-                                {
+                                let sym = local.get_node_by_index(f).unwrap().as_ref().get_symbol();
+                                // modules only bound into the testbench are not the design under test
+                                let is_instantiated = match bench_sym.as_module() {
+                                    Some(m) => m
+                                        .get_deps()
+                                        .iter()
+                                        .any(|d| d.get_suffix() == &sym.get_name()),
+                                    None => true,
+                                };
+                                if sym.is_component() == true && is_instantiated == true {
                                     Some((
                                         f,
                                         local.get_node_by_index(f).unwrap().as_ref().get_symbol(),
@@ -1473,6 +1995,118 @@ impl Plan {
         Ok((top, bench))
     }
 
+    /// Collects the verilog and systemverilog files that can be included as headers.
+    fn collect_headers(files: &Vec<IpFileNode>) -> Vec<String> {
+        files
+            .iter()
+            .filter(|f| {
+                fileset::is_verilog(f.get_file()) || fileset::is_systemverilog(f.get_file())
+            })
+            .map(|f| f.get_file().replace('\\', "/"))
+            .collect()
+    }
+
+    /// Finds the header files included by the `file`, searching through nested includes.
+    ///
+    /// Each header is paired with the name it was included by. Included files
+    /// that cannot be found among the `headers` are skipped.
+    fn find_headers(file: &str, headers: &Vec<String>) -> Vec<(String, String)> {
+        let mut found: Vec<(String, String)> = Vec::new();
+        let mut queue = vec![file.replace('\\', "/")];
+        let mut i = 0;
+        while i < queue.len() {
+            let text = fs::read_to_string(&queue[i]).unwrap_or_default();
+            for name in Self::find_includes(&text) {
+                let suffix = format!("/{}", name);
+                if let Some(header) = headers.iter().find(|h| h.ends_with(&suffix)) {
+                    if queue.contains(header) == false {
+                        queue.push(header.clone());
+                        found.push((header.clone(), name));
+                    }
+                }
+            }
+            i += 1;
+        }
+        found
+    }
+
+    /// Finds the directories of the header files that are included by the verilog
+    /// and systemverilog files in `file_order`.
+    fn find_include_dirs(file_order: &Vec<&&IpFileNode>, headers: &Vec<String>) -> Vec<String> {
+        let mut dirs = Vec::new();
+        for node in file_order {
+            if fileset::is_vhdl(node.get_file()) == true {
+                continue;
+            }
+            for (header, name) in Self::find_headers(node.get_file(), headers) {
+                let dir = header[..header.len() - name.len() - 1].to_string();
+                if dirs.contains(&dir) == false {
+                    dirs.push(dir);
+                }
+            }
+        }
+        dirs
+    }
+
+    /// Maps each file in the `min_order` to the files containing the units it
+    /// depends on according to the edges in the `global_graph`.
+    fn determine_file_deps<'a>(
+        global_graph: &'a GraphMap<CompoundIdentifier, HdlNode, ()>,
+        min_order: &Vec<usize>,
+    ) -> HashMap<&'a String, Vec<&'a String>> {
+        let mut file_deps = HashMap::<&String, Vec<&String>>::new();
+        for i in min_order {
+            let preds: Vec<&HdlNode> = global_graph
+                .predecessors(*i)
+                .into_iter()
+                .map(|p| p.1)
+                .collect();
+            for ip_file_node in global_graph
+                .get_node_by_index(*i)
+                .unwrap()
+                .as_ref()
+                .get_associated_files()
+            {
+                let deps = file_deps.entry(ip_file_node.get_file()).or_default();
+                for pred in &preds {
+                    for pred_file in pred.get_associated_files() {
+                        if pred_file.get_file() != ip_file_node.get_file()
+                            && deps.contains(&pred_file.get_file()) == false
+                        {
+                            deps.push(pred_file.get_file());
+                        }
+                    }
+                }
+            }
+        }
+        file_deps
+    }
+
+    /// Collects the file names referenced by `` `include`` directives in the `text`.
+    fn find_includes(text: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        for line in text.lines() {
+            let line = match line.find("//") {
+                Some(i) => &line[..i],
+                None => line,
+            };
+            let mut rest = line;
+            while let Some(i) = rest.find("`include") {
+                rest = rest[i + "`include".len()..].trim_start();
+                let close = match rest.chars().next() {
+                    Some('"') => '"',
+                    Some('<') => '>',
+                    _ => continue,
+                };
+                if let Some(end) = rest[1..].find(close) {
+                    names.push(rest[1..end + 1].to_string());
+                    rest = &rest[end + 2..];
+                }
+            }
+        }
+        names
+    }
+
     /// Modifies the `list` to only have a list of unique elements while preserving their original
     /// order.
     ///
@@ -1505,6 +2139,8 @@ impl Plan {
         // gather the files from each node in-order (multiple files can exist for a node)
         let mut file_map = HashMap::<String, (&IpFileNode, Vec<&HdlNode>)>::new();
         let mut file_order = Vec::<String>::new();
+        // only consider dependencies on units that are part of the order
+        let included: HashSet<usize> = min_order.iter().copied().collect();
 
         for i in &min_order {
             // access the node key and access the files associated with this key (the dependencies)
@@ -1517,9 +2153,10 @@ impl Plan {
             ipfs.into_iter().for_each(|&ip_file_node| {
                 // collect all dependencies in the graph from this node
                 let mut preds: Vec<&HdlNode> = global_graph
+                    .get_graph()
                     .predecessors(*i)
-                    .into_iter()
-                    .map(|ip_file_node| ip_file_node.1)
+                    .filter(|p| included.contains(p) == true)
+                    .map(|p| global_graph.get_node_by_index(p).unwrap().as_ref())
                     .collect();
                 // merge dependencies together from various primary design units
                 match file_map.get_mut(ip_file_node.get_file()) {
@@ -1604,9 +2241,11 @@ impl Plan {
         bench_name: &str,
         bench_file: &str,
         target: &Target,
+        out_dir: &str,
+        generics: &Combination,
         require_bench: bool,
     ) -> Result<PathBuf, Fault> {
-        let output_path = target_path.join(target.get_name());
+        let output_path = target_path.join(out_dir);
         // create a output build directorie(s) if they do not exist
         if output_path.exists() == false {
             fs::create_dir_all(&output_path).expect("could not create output directory");
@@ -1649,6 +2288,9 @@ impl Plan {
             EnvVar::new()
                 .key(environment::ORBIT_TB_FILE)
                 .value(&bench_file),
+            EnvVar::new()
+                .key(environment::ORBIT_GENERICS)
+                .value(&generic::to_string(generics, ";")),
         ]);
         // conditionally set the plugin used to plan
         envs.insert(
@@ -1726,6 +2368,8 @@ impl Plan {
             // recollect the installations to update the catalog for dependency graphing
             catalog = catalog.installations(c.get_cache_path())?;
         }
+        // mark the versions yanked and ip deprecated in the channels
+        let catalog = catalog.statuses(&c.get_config().get_channels())?;
 
         // determine the build directory (command-line arg overrides configuration setting)
         let default_target_dir = c.get_target_dir();
@@ -1738,6 +2382,7 @@ impl Plan {
             &working_ip,
             target_dir,
             target,
+            target.get_name(),
             catalog,
             self.clean,
             self.force,
@@ -1746,6 +2391,7 @@ impl Plan {
             &self.bench,
             &self.top,
             &self.filesets,
+            &Vec::new(),
             &Scheme::default(),
             false,
             true,
@@ -1838,4 +2484,95 @@ mod test {
             vec![&9, &8, &7, &6, &5, &4]
         );
     }
+
+    #[test]
+    fn find_includes() {
+        let text = r#"`include "defs.svh"
+`include <pkg/types.vh> // system header
+// `include "unused.svh"
+module top; `include "body.svh" endmodule
+"#;
+        assert_eq!(
+            Plan::find_includes(text),
+            vec!["defs.svh", "pkg/types.vh", "body.svh"]
+        );
+    }
+
+    #[test]
+    fn is_original_name() {
+        assert_eq!(Plan::is_original_name("dupe", "dupe"), true);
+        assert_eq!(Plan::is_original_name("dupe_044588b88a", "dupe"), true);
+        assert_eq!(Plan::is_original_name("dupe2_044588b88a", "dupe"), false);
+        assert_eq!(Plan::is_original_name("dupe_reg", "dupe"), false);
+        assert_eq!(Plan::is_original_name("dup", "dupe"), false);
+    }
+
+    #[test]
+    fn config_selects_mapped_cell() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let write = |path: &str, text: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, text).unwrap();
+        };
+        write(
+            "Orbit.toml",
+            "[ip]\nname = \"cells\"\nversion = \"0.1.0\"\nuuid = \"0000000000000000000000000\"\n",
+        );
+        write("lib.map", "library lib_a a/*.v;\nlibrary lib_b b/*.v;\n");
+        // both libraries define a module with the same name
+        write(
+            "a/bufx.v",
+            "module bufx(input i, output o);\n  assign o = i;\nendmodule\n",
+        );
+        write(
+            "b/bufx.v",
+            "module bufx(input i, output o);\n  assign o = ~i;\nendmodule\n",
+        );
+        write(
+            "rtl/top.v",
+            "module top(input i, output o);\n  bufx u0(.i(i), .o(o));\nendmodule\n",
+        );
+        write(
+            "rtl/cfg.v",
+            "config cfg;\n  design cells.top;\n  default liblist lib_b lib_a;\nendconfig\n",
+        );
+
+        let ip = Ip::load(root.clone(), true, false).unwrap();
+        let target = Target::from_str("name = \"sim\"\ncommand = \"true\"\n")
+            .unwrap()
+            .root(root.clone());
+        let (name, files) = Plan::run(
+            &ip,
+            "target",
+            &target,
+            "sim",
+            Catalog::new(),
+            true,
+            false,
+            false,
+            false,
+            &None,
+            &Some(Identifier::from_str("cfg").unwrap()),
+            &None,
+            &Vec::new(),
+            &Scheme::Tsv,
+            false,
+            false,
+        )
+        .unwrap()
+        .unwrap();
+
+        // the configuration selects the cell from the first library in its list
+        let b_file = filesystem::into_std_str(root.join("b").join("bufx.v"));
+        assert_eq!(files.contains(&b_file), true);
+        assert_eq!(
+            files.contains(&filesystem::into_std_str(root.join("a").join("bufx.v"))),
+            false
+        );
+        let blueprint =
+            std::fs::read_to_string(root.join("target").join("sim").join(name)).unwrap();
+        assert!(blueprint.contains(&format!("VLOG\tlib_b\t{}\n", b_file)));
+    }
 }
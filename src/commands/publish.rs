@@ -23,14 +23,18 @@ use crate::commands::plan::Plan;
 use crate::core::algo;
 use crate::core::catalog::{Catalog, PointerSlot};
 use crate::core::channel::Channel;
+use crate::core::compat::{self, Bump};
 use crate::core::context::Context;
-use crate::core::ip::Ip;
+use crate::core::ip::{Ip, UnitEntry};
 use crate::core::iparchive::IpArchive;
+use crate::core::ippointer::PointerStatus;
 use crate::core::manifest::IP_MANIFEST_FILE;
+use crate::core::version::AnyVersion;
 use crate::error::{Error, Hint, LastError};
 use crate::util::anyerror::Fault;
 use crate::util::environment::{EnvVar, Environment, ORBIT_CHAN_INDEX};
 use crate::util::filesystem;
+use crate::warn;
 
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
@@ -44,6 +48,7 @@ pub struct Publish {
     ready: bool,
     no_install: bool,
     list: bool,
+    allow_breaking: bool,
 }
 
 impl Subcommand<Context> for Publish {
@@ -53,6 +58,7 @@ impl Subcommand<Context> for Publish {
             list: cli.check(Arg::flag("list"))?,
             no_install: cli.check(Arg::flag("no-install"))?,
             ready: cli.check(Arg::flag("ready").switch('y'))?,
+            allow_breaking: cli.check(Arg::flag("allow-breaking"))?,
         })
     }
 
@@ -154,6 +160,14 @@ impl Subcommand<Context> for Publish {
             ))));
         }
 
+        if let Err(e) =
+            Self::check_version_bump(&local_ip, &catalog, &channels, self.allow_breaking)
+        {
+            return Err(Box::new(Error::PublishFailedCheckpoint(LastError(
+                e.to_string(),
+            ))));
+        }
+
         // verify the package is available to be downloaded
         println!("info: {}", "verifying coherency with ip's source  ...");
         let remove = self.ready == false || self.no_install == true;
@@ -213,6 +227,96 @@ impl Publish {
         Ok(())
     }
 
+    /// Compares the public units of the ip against those of the newest version
+    /// previously published to its channels.
+    ///
+    /// Errors if the change in version is smaller than what the changes to the
+    /// public interface require, unless `allow_breaking` is set.
+    pub fn check_version_bump(
+        local_ip: &Ip,
+        catalog: &Catalog,
+        channels: &HashMap<&String, &Channel>,
+        allow_breaking: bool,
+    ) -> Result<(), Fault> {
+        let version = local_ip.get_man().get_ip().get_version();
+        let level = match catalog.inner().get(local_ip.get_uuid()) {
+            Some(l) => l,
+            None => return Ok(()),
+        };
+        // find the newest version published before this one
+        let prev = match level
+            .get_availability()
+            .iter()
+            .filter(|ip| ip.get_man().get_ip().get_version() < version)
+            .max_by(|a, b| {
+                a.get_man()
+                    .get_ip()
+                    .get_version()
+                    .cmp(b.get_man().get_ip().get_version())
+            }) {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+        let prev_version = prev.get_man().get_ip().get_version();
+        println!(
+            "info: verifying public interface is compatible with version {} ...",
+            prev_version
+        );
+
+        // read the units of the previous version from the highest level that has it
+        let target = AnyVersion::Specific(prev_version.to_partial_version());
+        let prev_units = match level.get_install(&target).or(level.get_download(&target)) {
+            Some(ip) => ip.list_units()?.unwrap_or_default(),
+            None => channels
+                .values()
+                .find_map(|chan| {
+                    Ip::read_cache_metadata(
+                        &chan.get_root().join(Self::create_pointer_directory(prev)),
+                    )
+                })
+                .map(|meta| meta.get_units().clone())
+                .unwrap_or_default(),
+        };
+        if prev_units.is_empty() == true {
+            warn!(
+                "skipping compatibility check: no units are known for version {}",
+                prev_version
+            );
+            return Ok(());
+        }
+
+        let mut units: Vec<UnitEntry> = local_ip
+            .collect_units(true, true)?
+            .values()
+            .map(|u| UnitEntry::from(u))
+            .collect();
+        units.sort_by(|a, b| a.get_name().cmp(b.get_name()));
+
+        let changes = compat::compare(&prev_units, &units);
+        for change in &changes {
+            println!("    {} ({})", change, change.get_bump());
+        }
+        let required = compat::required_bump(&changes);
+        match Bump::between(prev_version, version) >= required {
+            true => Ok(()),
+            false => match allow_breaking {
+                true => {
+                    warn!(
+                        "publishing with a smaller version bump than the {} bump required by the interface changes",
+                        required
+                    );
+                    Ok(())
+                }
+                false => Err(Error::PublishVersionBumpTooSmall(
+                    version.clone(),
+                    required.to_string(),
+                    prev_version.clone(),
+                    Hint::PublishAllowBreaking,
+                ))?,
+            },
+        }
+    }
+
     pub fn test_download_and_install(
         local_ip: &Ip,
         c: &Context,
@@ -343,7 +447,7 @@ impl Publish {
     /// Creates the path where an ip will place its pointer contents.
     ///
     /// The directory is something like this: `uuid[0]/uuid-version`.
-    fn create_pointer_directory(ip: &Ip) -> PathBuf {
+    pub fn create_pointer_directory(ip: &Ip) -> PathBuf {
         let name = ip.get_man().get_ip().get_name();
         let version = ip.get_man().get_ip().get_version();
         let uuid = ip.get_uuid();
@@ -365,6 +469,10 @@ impl Publish {
         )?;
         // copy the (raw) lockfile there
         local_ip.get_lock().save_to_disk(&output_path)?;
+        // record the ip's units so they can be searched without its source code
+        local_ip.write_metadata(&output_path)?;
+        // keep the ip deprecated if its previous versions are deprecated
+        PointerStatus::inherit(channel.get_root(), local_ip.get_uuid())?.write(&output_path)?;
         Ok(())
     }
 
@@ -0,0 +1,63 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::commands::helps::rm;
+use crate::commands::lock::Lock;
+use crate::core::context::Context;
+use crate::core::ip::Ip;
+use crate::core::manifest::{self, IP_MANIFEST_FILE};
+use crate::core::pkgid::PkgPart;
+use crate::error::Error;
+use crate::info;
+use std::fs;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Rm {
+    ip: PkgPart,
+}
+
+impl Subcommand<Context> for Rm {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(rm::HELP))?;
+        Ok(Rm {
+            // Arguments
+            ip: cli.require(Arg::positional("ip"))?,
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // go to the ip directory
+        c.jump_to_working_ip()?;
+        let local_ip = Ip::load(c.get_ip_path().unwrap().clone(), true, false)?;
+
+        // edit the manifest
+        let man_path = local_ip.get_root().join(IP_MANIFEST_FILE);
+        let contents = fs::read_to_string(&man_path)?;
+        match manifest::remove_dependency(&contents, &self.ip)? {
+            Some(result) => fs::write(&man_path, result)?,
+            None => return Err(Error::DependencyNotFound(self.ip))?,
+        }
+
+        // update the lockfile without the dependency
+        Lock::run_after_edit(c, local_ip.get_root(), &man_path, &contents)?;
+        info!("removed {} from the dependencies", self.ip);
+        Ok(())
+    }
+}
@@ -15,11 +15,19 @@
 //  along with this program.  If not, see <http://www.gnu.org/licenses/>.
 //
 
+use crate::commands::publish::Publish;
 use crate::core::context::Context;
-use crate::core::ip::Mapping;
+use crate::core::ip::{Ip, Mapping, UnitEntry};
+use crate::core::manifest::{self, IP_MANIFEST_FILE};
 use crate::core::pkgid::PkgPart;
+use crate::error::Error;
+use crate::info;
 use crate::util::anyerror::Fault;
+use crate::util::prompt;
+use crate::util::seqalin;
+use crate::warn;
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 
 use cliproc::{cli, proc, stage::*};
 use cliproc::{Arg, Cli, Help, Subcommand};
@@ -37,6 +45,15 @@ pub struct Search {
     keywords: Vec<String>,
     limit: Option<usize>,
     hard_match: bool,
+    unit: Option<String>,
+    add: bool,
+}
+
+/// A design unit found in the catalog that matches the search.
+struct UnitMatch<'a> {
+    unit: UnitEntry,
+    ip: &'a Ip,
+    score: usize,
 }
 
 impl Subcommand<Context> for Search {
@@ -47,10 +64,12 @@ impl Subcommand<Context> for Search {
             cached: cli.check(Arg::flag("install").switch('i'))?,
             available: cli.check(Arg::flag("available").switch('a'))?,
             hard_match: cli.check(Arg::flag("match"))?,
+            add: cli.check(Arg::flag("add"))?,
             limit: cli.get(Arg::option("limit").value("n"))?,
             keywords: cli
                 .get_all(Arg::option("keyword").value("term"))?
                 .unwrap_or(Vec::new()),
+            unit: cli.get(Arg::option("unit").value("name"))?,
             ip: cli.get(Arg::positional("ip"))?,
         })
     }
@@ -64,12 +83,27 @@ impl Subcommand<Context> for Search {
         // collect available IP
         catalog = catalog.available(&c.get_config().get_channels())?;
 
-        self.run(&catalog)
+        if self.add == true && self.unit.is_none() == true {
+            return Err(Error::AddWithoutUnit)?;
+        }
+
+        let channels: Vec<PathBuf> = c
+            .get_config()
+            .get_channels()
+            .values()
+            .map(|chan| chan.get_root().clone())
+            .collect();
+        self.run(&catalog, &channels, c.get_ip_path())
     }
 }
 
 impl Search {
-    fn run(&self, catalog: &Catalog) -> Result<(), Fault> {
+    fn run(
+        &self,
+        catalog: &Catalog,
+        channels: &Vec<PathBuf>,
+        local_ip: Option<&PathBuf>,
+    ) -> Result<(), Fault> {
         // transform into a BTreeMap for alphabetical ordering
         let mut tree = BTreeMap::new();
 
@@ -153,26 +187,205 @@ impl Search {
                 tree.insert(PkgName::new(name, Some(key)), status);
             });
 
-        println!(
-            "{}",
-            Self::fmt_table(
-                tree,
-                self.limit,
-                self.cached,
-                self.downloaded,
-                self.available
-            )
+        if let Some(query) = &self.unit {
+            return self.run_units(query, tree, channels, local_ip);
+        }
+
+        let (table, notices) = Self::fmt_table(
+            tree,
+            self.limit,
+            self.cached,
+            self.downloaded,
+            self.available,
         );
+        println!("{}", table);
+        for notice in notices {
+            warn!("{}", notice);
+        }
+        Ok(())
+    }
+
+    /// Searches the units of the ips in `tree` for names matching `query`.
+    fn run_units(
+        &self,
+        query: &str,
+        tree: BTreeMap<PkgName, &IpLevel>,
+        channels: &Vec<PathBuf>,
+        local_ip: Option<&PathBuf>,
+    ) -> Result<(), Fault> {
+        let mut matches = Vec::new();
+        for (_, status) in &tree {
+            let ip = match self.select_ip(status) {
+                Some(ip) => ip,
+                None => continue,
+            };
+            // channels only hold the unit list that was recorded when publishing
+            let units = match ip.list_units()? {
+                Some(units) => units,
+                None => channels
+                    .iter()
+                    .find_map(|root| {
+                        Ip::read_cache_metadata(&root.join(Publish::create_pointer_directory(ip)))
+                    })
+                    .map(|meta| meta.get_units().clone())
+                    .unwrap_or_default(),
+            };
+            for unit in units {
+                if let Some(score) = self.score_unit(query, unit.get_name()) {
+                    matches.push(UnitMatch {
+                        unit: unit,
+                        ip: ip,
+                        score: score,
+                    });
+                }
+            }
+        }
+        matches.sort_by(|a, b| {
+            a.score
+                .cmp(&b.score)
+                .then(a.unit.get_name().cmp(b.unit.get_name()))
+        });
+        if let Some(cap) = self.limit {
+            matches.truncate(cap);
+        }
+
+        println!("{}", Self::fmt_units_table(&matches));
+
+        match self.add {
+            true => Self::add_dependency(query, &matches, local_ip),
+            false => Ok(()),
+        }
+    }
+
+    /// Returns the ip to display from its catalog level according to the status filters.
+    fn select_ip<'a>(&self, status: &'a IpLevel) -> Option<&'a Ip> {
+        let default = !(self.cached || self.downloaded || self.available);
+        let latest = &AnyVersion::Latest;
+        if default == true {
+            return status
+                .get_install(latest)
+                .or(status.get_download(latest))
+                .or(status.get_available(latest));
+        }
+        let ins = status.get_install(latest).filter(|_| self.cached == true);
+        let dld = status
+            .get_download(latest)
+            .filter(|_| self.downloaded == true);
+        let ava = status
+            .get_available(latest)
+            .filter(|_| self.available == true);
+        ins.or(dld).or(ava)
+    }
+
+    /// Determines how closely the unit `name` matches the `query`, where a lower
+    /// score is a closer match.
+    ///
+    /// Returns `None` if the unit does not match.
+    fn score_unit(&self, query: &str, name: &str) -> Option<usize> {
+        let query = query.to_lowercase();
+        let name = name.to_lowercase();
+        if query == name {
+            return Some(0);
+        } else if self.hard_match == true {
+            return None;
+        } else if name.contains(&query) == true {
+            return Some(1);
+        }
+        // allow for typos proportional to the length of the query, where even
+        // short names allow a single swapped or mistyped character
+        let dist = seqalin::edit_distance(&query, &name);
+        match dist <= (query.len() / 3).max(2) {
+            true => Some(1 + dist),
+            false => None,
+        }
+    }
+
+    /// Adds the ip providing the closest matching unit as a dependency to the
+    /// local ip once the user confirms.
+    fn add_dependency(
+        query: &str,
+        matches: &Vec<UnitMatch>,
+        local_ip: Option<&PathBuf>,
+    ) -> Result<(), Fault> {
+        let local_ip = match local_ip {
+            Some(p) => p,
+            None => return Err(Error::NoAssumedWorkingIpFound)?,
+        };
+        let best = match matches.first() {
+            Some(m) => m,
+            None => return Err(Error::UnitNotFoundInCatalog(query.to_string()))?,
+        };
+        // the closest matches must all come from the same ip
+        let mut providers: Vec<String> = matches
+            .iter()
+            .filter(|m| m.score == best.score)
+            .map(|m| m.ip.get_man().get_ip().into_ip_spec().to_string())
+            .collect();
+        providers.sort();
+        providers.dedup();
+        if providers.len() > 1 {
+            return Err(Error::UnitFoundInManyIps(
+                query.to_string(),
+                providers.join(", "),
+            ))?;
+        }
+
+        let name = best.ip.get_man().get_ip().get_name();
+        let version = best.ip.get_man().get_ip().get_version().to_string();
+        if prompt::prompt(&format!(
+            "Add {} {} as a dependency for unit {:?}",
+            name,
+            version,
+            best.unit.get_name()
+        ))? == false
+        {
+            return Ok(());
+        }
+        let path = local_ip.join(IP_MANIFEST_FILE);
+        let contents = std::fs::read_to_string(&path)?;
+        std::fs::write(
+            &path,
+            manifest::insert_dependency(&contents, name, &version, None, None, false)?,
+        )?;
+        info!("added dependency {} = {:?} to {:?}", name, version, path);
         Ok(())
     }
 
+    fn fmt_units_table(matches: &Vec<UnitMatch>) -> String {
+        let mut body = String::new();
+        for m in matches {
+            let man = m.ip.get_man().get_ip();
+            body.push_str(&format!(
+                "{:<32}{:<15}{:<15}{:<11}{:<24}{:<16}{}\n",
+                m.unit.get_name(),
+                m.unit.get_kind(),
+                m.unit.get_language(),
+                m.unit.get_visibility(),
+                man.get_name().to_string(),
+                man.get_version().to_string(),
+                match m.ip.get_mapping() {
+                    Mapping::Physical => "install",
+                    Mapping::Virtual(_) => "download",
+                    Mapping::Imaginary => "available",
+                    Mapping::Relative(_) => "local",
+                },
+            ));
+        }
+        // remove final \n from body
+        body.pop();
+        body
+    }
+
+    /// Formats the table of ips to display.
+    ///
+    /// Also returns the notices for any displayed ip that is yanked or deprecated.
     fn fmt_table(
         table: BTreeMap<PkgName, &IpLevel>,
         limit: Option<usize>,
         cached: bool,
         downloaded: bool,
         available: bool,
-    ) -> String {
+    ) -> (String, Vec<String>) {
         //         let header = format!(
         //             "\
         // {:<28}{:<10}{:<9}
@@ -181,6 +394,7 @@ impl Search {
         //         );
         let header = String::new();
         let mut body = String::new();
+        let mut notices = Vec::new();
         let mut index = 0;
 
         let default = !(cached || downloaded || available);
@@ -283,9 +497,52 @@ impl Search {
                 },
                 name.get_uuid().unwrap().encode()
             ));
+
+            let spec = ip.get_man().get_ip().into_ip_spec();
+            if let Some(reason) = status.get_yanked(ip.get_man().get_ip().get_version()) {
+                notices.push(format!("ip {} is yanked: {}", spec, reason));
+            }
+            if let Some(message) = status.get_deprecated() {
+                notices.push(format!("ip {} is deprecated: {}", spec.get_name(), message));
+            }
         }
         // remove final \n from body
         body.pop();
-        header + &body
+        (header + &body, notices)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn search(hard_match: bool) -> Search {
+        Search {
+            ip: None,
+            cached: true,
+            downloaded: true,
+            available: true,
+            keywords: Vec::new(),
+            limit: None,
+            hard_match: hard_match,
+            unit: None,
+            add: false,
+        }
+    }
+
+    #[test]
+    fn score_units() {
+        let s = search(false);
+        assert_eq!(s.score_unit("fifo_async", "FIFO_ASYNC"), Some(0));
+        assert_eq!(s.score_unit("fifo", "fifo_async"), Some(1));
+        assert_eq!(s.score_unit("fifo_asnyc", "fifo_async"), Some(3));
+        // short names still allow a swapped character
+        assert_eq!(s.score_unit("adn2", "and2"), Some(3));
+        assert_eq!(s.score_unit("or2", "mux4"), None);
+        assert_eq!(s.score_unit("uart", "fifo_async"), None);
+
+        let s = search(true);
+        assert_eq!(s.score_unit("and2", "AND2"), Some(0));
+        assert_eq!(s.score_unit("adn2", "and2"), None);
     }
 }
@@ -19,15 +19,18 @@ use cliproc::{cli, proc, stage::Memory, Arg, Cli, Help, Subcommand};
 use colored::Colorize;
 
 use crate::commands::helps::test;
+use crate::core::blueprint::Blueprint;
 use crate::core::blueprint::Scheme;
 use crate::core::catalog::Catalog;
 use crate::core::context::Context;
 use crate::core::fileset::Fileset;
+use crate::core::generic::{self, Combination, Generic};
 use crate::core::ip::Ip;
 use crate::core::lang::vhdl::token::Identifier;
 use crate::core::swap::StrSwapTable;
 use crate::core::target::Process;
 use crate::core::target::Target;
+use crate::core::watch;
 use crate::error::Error;
 use crate::error::LastError;
 use crate::util::anyerror::Fault;
@@ -52,6 +55,8 @@ pub struct Test {
     command: Option<String>,
     filesets: Option<Vec<Fileset>>,
     bench: Option<Identifier>,
+    watch: bool,
+    generics: Vec<Generic>,
 }
 
 impl Subcommand<Context> for Test {
@@ -64,6 +69,7 @@ impl Subcommand<Context> for Test {
             force: cli.check(Arg::flag("force"))?,
             all: cli.check(Arg::flag("all"))?,
             dirty: cli.check(Arg::flag("no-clean"))?,
+            watch: cli.check(Arg::flag("watch"))?,
             // Options
             dut: cli.get(Arg::option("dut").value("unit"))?,
             bench: cli.get(Arg::option("tb").value("unit"))?,
@@ -72,6 +78,9 @@ impl Subcommand<Context> for Test {
             target_dir: cli.get(Arg::option("target-dir"))?,
             command: cli.get(Arg::option("command").value("path"))?,
             filesets: cli.get_all(Arg::option("fileset").value("key=glob"))?,
+            generics: cli
+                .get_all(Arg::option("generic").value("name=value"))?
+                .unwrap_or_default(),
             // Remaining args
             args: cli.remainder()?,
         })
@@ -126,21 +135,18 @@ impl Subcommand<Context> for Test {
             None => &default_build_dir,
         };
 
-        // gather the catalog and resolve any missing dependencies
-        let catalog = Catalog::new()
-            .installations(c.get_cache_path())?
-            .downloads(c.get_downloads_path())?;
-        let catalog = plan::resolve_missing_deps(c, &ip, catalog, self.force)?;
-
-        self.run(
-            &ip,
-            target_dir,
-            target.get_name(),
-            target,
-            catalog,
-            &c,
-            &plan,
-        )
+        // execute the target for every combination of generics
+        let run = |ip: &Ip, replan: bool| {
+            generic::sweep(&self.generics, target.get_name(), |combo| {
+                let out_dir = generic::to_out_dir(target.get_name(), &self.generics, combo);
+                self.run(ip, target_dir, &out_dir, target, c, &plan, combo, replan)
+            })
+        };
+
+        match self.watch {
+            true => watch::repeat(ip, run),
+            false => run(&ip, true),
+        }
     }
 }
 
@@ -151,28 +157,45 @@ impl Test {
         target_dir: &str,
         out_dir: &str,
         target: &Target,
-        catalog: Catalog,
         c: &Context,
         scheme: &Scheme,
+        generics: &Combination,
+        replan: bool,
     ) -> Result<(), Fault> {
-        // plan the target
-        let blueprint_name = Plan::run(
-            &working_ip,
-            target_dir,
-            target,
-            catalog,
-            self.dirty == false,
-            self.force,
-            false,
-            self.all,
-            &self.bench,
-            &self.dut,
-            &self.filesets,
-            &scheme,
-            true,
-            true,
-        )?
-        .unwrap_or_default();
+        let blueprint_name = match replan {
+            true => {
+                // gather the catalog and resolve any missing dependencies
+                let catalog = Catalog::new()
+                    .installations(c.get_cache_path())?
+                    .downloads(c.get_downloads_path())?;
+                let catalog = plan::resolve_missing_deps(c, &working_ip, catalog, self.force)?;
+
+                // plan the target
+                Plan::run(
+                    &working_ip,
+                    target_dir,
+                    target,
+                    out_dir,
+                    catalog,
+                    // an incremental blueprint relies on the outputs of the previous runs
+                    self.dirty == false && (scheme.is_incremental() == false || self.force),
+                    self.force,
+                    false,
+                    self.all,
+                    &self.bench,
+                    &self.dut,
+                    &self.filesets,
+                    generics,
+                    &scheme,
+                    true,
+                    true,
+                )?
+                .map(|(name, _)| name)
+                .unwrap_or_default()
+            }
+            // reuse the blueprint from the last plan
+            false => Blueprint::new(scheme.clone()).get_filename(),
+        };
 
         let output_path = working_ip.get_root().join(target_dir).join(out_dir);
 
@@ -28,6 +28,7 @@ use crate::core::lang::node::HdlSymbol;
 use crate::core::lang::node::IdentifierFormat;
 use crate::core::lang::node::SubUnitNode;
 use crate::core::lang::reference::CompoundIdentifier;
+use crate::core::lang::vhdl::subunit::SubUnit;
 use crate::core::lang::vhdl::token::Identifier as VhdlIdentifier;
 use crate::core::lang::Lang;
 use crate::core::lang::LangIdentifier;
@@ -36,7 +37,8 @@ use crate::error::Hint;
 use crate::util::anyerror::Fault;
 use crate::util::graph::EdgeStatus;
 use crate::util::graphmap::GraphMap;
-use std::collections::HashMap;
+use colored::Colorize;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
 
 use cliproc::{cli, proc, stage::*};
@@ -68,6 +70,7 @@ pub struct Tree {
     // compress: bool,
     format: Option<IdentifierFormat>,
     ascii: bool,
+    instances: bool,
     edges: Kind,
 }
 
@@ -78,6 +81,7 @@ impl Subcommand<Context> for Tree {
             // TODO: implement compression logic
             // compress: cli.check(Arg::flag("compress"))?,
             ascii: cli.check(Arg::flag("ascii"))?,
+            instances: cli.check(Arg::flag("instances"))?,
             edges: cli
                 .get(Arg::option("edges").switch('e').value("kind"))?
                 .unwrap_or(Kind::Unit),
@@ -102,6 +106,9 @@ impl Subcommand<Context> for Tree {
 
 impl Tree {
     fn run(&self, target: Ip, catalog: Catalog) -> Result<(), Fault> {
+        if self.instances == true {
+            return self.run_instance_paths(target, catalog);
+        }
         // Determine how to display the dependencies for the project
         match &self.edges {
             Kind::Unit => self.run_hdl_graph(target, catalog, true),
@@ -116,8 +123,6 @@ impl Tree {
     /// within other entity/modules. If false, then any type of primary design unit reference will
     /// be included.
     fn run_hdl_graph(&self, target: Ip, catalog: Catalog, only_modules: bool) -> Result<(), Fault> {
-        let working_lib = target.get_hdl_library();
-
         // build graph again but with entire set of all files available from all depdendencies
         let ip_graph = algo::compute_final_ip_graph(&target, &catalog)?;
         let files = algo::build_ip_file_list(&ip_graph, &target);
@@ -125,10 +130,54 @@ impl Tree {
         // build the complete graph (using entities as the nodes)
         let global_graph = Self::build_graph(&files, only_modules)?;
 
+        let roots = self.find_roots(&global_graph, &target)?;
+
+        // display each root's tree to the console
+        roots
+            .iter()
+            .filter(|k| {
+                global_graph
+                    .get_node_by_index(**k)
+                    .unwrap()
+                    .as_ref()
+                    .get_symbol()
+                    .is_component()
+                    || only_modules == false
+            })
+            .for_each(|n| {
+                let tree = global_graph.get_graph().treeview(*n);
+                for twig in &tree {
+                    let branch_str = match self.ascii {
+                        true => Self::to_ascii(&twig.0.to_string()),
+                        false => twig.0.to_string(),
+                    };
+                    println!(
+                        "{}{}",
+                        branch_str,
+                        global_graph
+                            .get_node_by_index(twig.1)
+                            .unwrap()
+                            .as_ref()
+                            .display(self.format.as_ref().unwrap_or(&IdentifierFormat::Short))
+                    );
+                }
+            });
+
+        Ok(())
+    }
+
+    /// Determines the root units of the graph, either by the user's request or by
+    /// detecting the uppermost units of the local ip.
+    fn find_roots(
+        &self,
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        target: &Ip,
+    ) -> Result<Vec<usize>, Fault> {
+        let working_lib = target.get_hdl_library();
         let roots = match &self.roots {
             Some(user_roots) => {
                 // restrict graph to units only found within the current IP
-                let local_graph = Plan::compute_local_graph(&global_graph, &target);
+                let local_graph = Plan::compute_local_graph(global_graph, &target);
                 let mut roots = Vec::new();
                 for root_name in user_roots {
                     // check if the identifier exists in the entity graph
@@ -150,65 +199,165 @@ impl Tree {
                             ))?
                         }
                     };
-                    roots.push(Plan::local_to_global(i, &global_graph, &local_graph).index())
+                    roots.push(Plan::local_to_global(i, global_graph, &local_graph).index())
                 }
                 roots
             }
             None => {
                 // restrict graph to units only found within the current IP
-                let local_graph = Plan::compute_local_graph(&global_graph, &target);
+                let local_graph = Plan::compute_local_graph(global_graph, &target);
                 // compile list of all roots
                 let mut roots = Vec::new();
                 match local_graph.find_root() {
-                    Ok(i) => roots.push(
-                        Plan::local_to_global(i.index(), &global_graph, &local_graph).index(),
-                    ),
+                    Ok(i) => roots
+                        .push(Plan::local_to_global(i.index(), global_graph, &local_graph).index()),
                     Err(e) => match e.len() {
                         0 => return Err(PlanError::Empty)?,
                         _ => e.into_iter().for_each(|f| {
-                            roots
-                                .push(Plan::local_to_global(f, &global_graph, &local_graph).index())
+                            roots.push(Plan::local_to_global(f, global_graph, &local_graph).index())
                         }),
                     },
                 }
                 roots
             }
         };
+        Ok(roots)
+    }
 
-        // display each root's tree to the console
-        roots
-            .iter()
-            .filter(|k| {
-                global_graph
-                    .get_node_by_index(**k)
-                    .unwrap()
-                    .as_ref()
-                    .get_symbol()
-                    .is_component()
-                    || only_modules == false
-            })
-            .for_each(|n| {
-                let tree = global_graph.get_graph().treeview(*n);
-                for twig in &tree {
-                    let branch_str = match self.ascii {
-                        true => Self::to_ascii(&twig.0.to_string()),
-                        false => twig.0.to_string(),
-                    };
-                    println!(
-                        "{}{}",
-                        branch_str,
-                        global_graph
-                            .get_node_by_index(twig.1)
-                            .unwrap()
-                            .as_ref()
-                            .display(self.format.as_ref().unwrap_or(&IdentifierFormat::Short))
-                    );
-                }
-            });
+    /// Prints the full path of every instance below the roots, followed by the
+    /// number of times each unit is instantiated.
+    fn run_instance_paths(&self, target: Ip, catalog: Catalog) -> Result<(), Fault> {
+        let ip_graph = algo::compute_final_ip_graph(&target, &catalog)?;
+        let files = algo::build_ip_file_list(&ip_graph, &target);
 
+        let global_graph = Self::build_graph(&files, true)?;
+        let roots = self.find_roots(&global_graph, &target)?;
+        let fmt = self.format.as_ref().unwrap_or(&IdentifierFormat::Short);
+
+        let mut paths = Vec::new();
+        let mut counts = BTreeMap::<String, usize>::new();
+        for root in roots {
+            let node = global_graph.get_node_by_index(root).unwrap().as_ref();
+            if node.get_symbol().is_component() == false {
+                continue;
+            }
+            let name = node.get_symbol().get_name().to_string();
+            paths.push((name.clone(), node.display(fmt), String::new()));
+            Self::walk_instances(
+                &global_graph,
+                root,
+                &name,
+                &mut vec![root],
+                fmt,
+                &mut paths,
+                &mut counts,
+            );
+        }
+
+        let width = paths.iter().map(|p| p.0.len()).max().unwrap_or(0) + 2;
+        for (path, unit, generics) in &paths {
+            println!("{:<width$}{}{}", path, unit, generics, width = width);
+        }
+        if counts.is_empty() == false {
+            println!();
+            let width = counts.keys().map(|k| k.len()).max().unwrap_or(0) + 2;
+            for (unit, count) in &counts {
+                println!("{:<width$}{}", unit, count, width = width);
+            }
+        }
         Ok(())
     }
 
+    /// Appends the path of every instance within the unit at index `node`, and the
+    /// instances within those units, to `paths`.
+    ///
+    /// The `stack` holds the units along the current path. An instance of a unit
+    /// already on the `stack` is marked as recursive and is not walked again.
+    fn walk_instances(
+        global_graph: &GraphMap<CompoundIdentifier, HdlNode, ()>,
+        node: usize,
+        path: &str,
+        stack: &mut Vec<usize>,
+        fmt: &IdentifierFormat,
+        paths: &mut Vec<(String, String, String)>,
+        counts: &mut BTreeMap<String, usize>,
+    ) -> () {
+        let instances = global_graph
+            .get_node_by_index(node)
+            .unwrap()
+            .as_ref()
+            .get_instances();
+        let lib = global_graph
+            .get_key_by_index(node)
+            .unwrap()
+            .get_prefix()
+            .cloned();
+
+        for inst in instances {
+            let child = Plan::resolve_instance(global_graph, lib.as_ref(), inst);
+            let is_recursive = child.is_some_and(|c| stack.contains(&c));
+            let unit = match child {
+                Some(c) => {
+                    let name = global_graph
+                        .get_node_by_index(c)
+                        .unwrap()
+                        .as_ref()
+                        .display(fmt);
+                    match is_recursive {
+                        true => format!("{} {}", name, "(recursive)".yellow()),
+                        false => name,
+                    }
+                }
+                None => format!("{} {}", inst.get_unit().to_string().yellow(), "?".yellow()),
+            };
+            let generics = match inst.get_generics().is_empty() {
+                true => String::new(),
+                false => format!(
+                    " #({})",
+                    inst.get_generics()
+                        .iter()
+                        .map(|g| g.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            };
+            // elaborate every combination of the enclosing generate blocks
+            let mut blocks = vec![String::new()];
+            for scope in inst.get_scopes() {
+                blocks = blocks
+                    .iter()
+                    .flat_map(|b| {
+                        scope
+                            .expand()
+                            .into_iter()
+                            .map(move |s| format!("{}{}/", b, s))
+                    })
+                    .collect();
+            }
+            for block in blocks {
+                let inst_path = format!("{}/{}{}", path, block, inst.get_name());
+                paths.push((inst_path.clone(), unit.clone(), generics.clone()));
+                *counts.entry(inst.get_unit().to_string()).or_insert(0) += 1;
+                match child {
+                    Some(c) if is_recursive == false => {
+                        stack.push(c);
+                        Self::walk_instances(
+                            global_graph,
+                            c,
+                            &inst_path,
+                            stack,
+                            fmt,
+                            paths,
+                            counts,
+                        );
+                        stack.pop();
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
     /// Construct and print the graph at an IP dependency level.
     fn run_ip_graph(&self, target: Ip, catalog: Catalog) -> Result<(), Fault> {
         let ip_graph = algo::compute_final_ip_graph(&target, &catalog)?;
@@ -254,7 +403,7 @@ impl Tree {
     }
 
     /// Constructs a graph of the design heirarchy with entity nodes.
-    fn build_graph<'a>(
+    pub fn build_graph<'a>(
         files: &'a Vec<IpFileNode>,
         only_modules: bool,
     ) -> Result<GraphMap<CompoundIdentifier, HdlNode<'a>, ()>, Fault> {
@@ -305,6 +454,11 @@ impl Tree {
                 None => continue,
             };
             entity_node.as_ref_mut().add_file(node.get_file());
+            if let SubUnit::Architecture(arch) = node.get_sub() {
+                entity_node
+                    .as_ref_mut()
+                    .set_instances(arch.get_instances().iter().map(|i| i.to_lang()).collect());
+            }
             // grab the list of the primary design unit's references to be used when getting "all" dependencies
             let pri_node = entity_node.as_ref().get_symbol().copy_refs();
 
@@ -382,6 +536,11 @@ impl Tree {
             }
         }
 
+        // add edges to referenced packages and contexts
+        if only_modules == false {
+            Plan::connect_edges_from_refs(&mut graph_map);
+        }
+
         Ok(graph_map)
     }
 }
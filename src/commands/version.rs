@@ -0,0 +1,224 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::commands::helps::version;
+use crate::commands::lock::Lock;
+use crate::commands::plan;
+use crate::core::catalog::Catalog;
+use crate::core::compat::Bump;
+use crate::core::context::Context;
+use crate::core::ip::Ip;
+use crate::core::manifest::{self, IpVersion, IP_MANIFEST_FILE};
+use crate::core::version::VerStr;
+use crate::error::{Error, Hint};
+use crate::info;
+use crate::util::anyerror::Fault;
+use crate::util::date;
+use std::fs;
+use std::path::PathBuf;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+/// The file created to hold the changelog when the ip does not have one.
+const CHANGELOG_FILE: &str = "CHANGELOG.md";
+
+#[derive(Debug, PartialEq)]
+pub struct Version {
+    changelog: bool,
+    label: Option<VerStr>,
+    level: Option<Bump>,
+}
+
+impl Subcommand<Context> for Version {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(version::HELP))?;
+        Ok(Version {
+            // Flags
+            changelog: cli.check(Arg::flag("changelog"))?,
+            // Options
+            label: cli.get(Arg::option("label").value("label"))?,
+            // Arguments
+            level: cli.get(Arg::positional("level"))?,
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // verify running from an ip directory and enter ip's root directory
+        c.jump_to_working_ip()?;
+
+        let local_ip = Ip::load(c.get_ip_path().unwrap().clone(), true, false)?;
+        let current = local_ip.get_man().get_ip().get_version().clone();
+
+        // display the current version and exit
+        if self.level.is_none() == true && self.label.is_none() == true {
+            println!("{}", current);
+            return Ok(());
+        }
+
+        let next = self.compute_next(&current);
+
+        // verify the new version does not go backwards from a known version
+        let known = Catalog::new()
+            .installations(c.get_cache_path())?
+            .downloads(c.get_downloads_path())?
+            .available(&c.get_config().get_channels())?;
+        if let Some(highest) = known
+            .get_possible_versions(local_ip.get_uuid())
+            .and_then(|vers| vers.into_iter().map(|v| v.get_version().clone()).max())
+        {
+            if next <= highest {
+                return Err(Error::VersionNotIncreasing(
+                    next,
+                    highest,
+                    Hint::ShowVersions,
+                ))?;
+            }
+        }
+
+        // edit the manifest
+        let man_path = local_ip.get_root().join(IP_MANIFEST_FILE);
+        let contents = fs::read_to_string(&man_path)?;
+        fs::write(&man_path, manifest::set_version(&contents, &next)?)?;
+        info!("updated version from {} to {}", current, next);
+
+        if self.changelog == true {
+            let path = Self::write_changelog(local_ip.get_root(), &next)?;
+            info!(
+                "added section for version {} to {:?}",
+                next,
+                path.file_name().unwrap_or_default()
+            );
+        }
+
+        // keep the lockfile in sync with the new version
+        let local_ip = Ip::load(local_ip.get_root().clone(), true, false)?;
+        plan::install_unyanked_deps(c, &local_ip)?;
+        let catalog = Catalog::new()
+            .installations(c.get_cache_path())?
+            .downloads(c.get_downloads_path())?
+            .statuses(&c.get_config().get_channels())?;
+        Lock::run(&local_ip, &catalog, false)
+    }
+}
+
+impl Version {
+    /// Determines the version that follows `current`.
+    fn compute_next(&self, current: &IpVersion) -> IpVersion {
+        let mut next = current.clone();
+        match self.level {
+            Some(Bump::Major) => next.inc_major(),
+            Some(Bump::Minor) => next.inc_minor(),
+            Some(Bump::Micro) => next.inc_micro(),
+            None => (),
+        }
+        match &self.label {
+            Some(label) => next.label(Some(label.clone())),
+            None => next,
+        }
+    }
+
+    /// Adds a section for the `version` to the changelog within the ip's `root`
+    /// directory, creating the changelog if it does not exist.
+    ///
+    /// Returns the path to the changelog.
+    fn write_changelog(root: &PathBuf, version: &IpVersion) -> Result<PathBuf, Fault> {
+        let path = fs::read_dir(root)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .find(|p| {
+                p.is_file() == true
+                    && p.file_stem()
+                        .is_some_and(|s| s.to_string_lossy().eq_ignore_ascii_case("changelog"))
+            })
+            .unwrap_or(root.join(CHANGELOG_FILE));
+        let contents = match path.exists() {
+            true => fs::read_to_string(&path)?,
+            false => String::new(),
+        };
+        let heading = format!("## {} - {}", version, date::today());
+        fs::write(&path, Self::prepend_section(&contents, &heading))?;
+        Ok(path)
+    }
+
+    /// Inserts the section `heading` before the first existing section of the
+    /// changelog's `contents`.
+    fn prepend_section(contents: &str, heading: &str) -> String {
+        if contents.trim().is_empty() == true {
+            return format!("# Changelog\n\n{}\n\n", heading);
+        }
+        let mut offset = 0;
+        for line in contents.split_inclusive('\n') {
+            if line.starts_with("## ") == true {
+                return format!(
+                    "{}{}\n\n{}",
+                    &contents[..offset],
+                    heading,
+                    &contents[offset..]
+                );
+            }
+            offset += line.len();
+        }
+        // no sections exist yet
+        format!("{}\n\n{}\n", contents.trim_end(), heading)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn next_version() {
+        let current = IpVersion::from_str("1.2.3-rc1").unwrap();
+        let cmd = |level: Option<Bump>, label: Option<&str>| Version {
+            changelog: false,
+            label: label.map(|l| VerStr::from_str(l).unwrap()),
+            level: level,
+        };
+        assert_eq!(
+            cmd(Some(Bump::Major), None).compute_next(&current),
+            IpVersion::from_str("2.0.0").unwrap()
+        );
+        assert_eq!(
+            cmd(Some(Bump::Minor), Some("alpha")).compute_next(&current),
+            IpVersion::from_str("1.3.0-alpha").unwrap()
+        );
+        assert_eq!(
+            cmd(None, Some("rc2")).compute_next(&current),
+            IpVersion::from_str("1.2.3-rc2").unwrap()
+        );
+    }
+
+    #[test]
+    fn prepend_changelog_section() {
+        let heading = "## 1.1.0 - 2026-10-19";
+        assert_eq!(
+            Version::prepend_section("", heading),
+            "# Changelog\n\n## 1.1.0 - 2026-10-19\n\n"
+        );
+        assert_eq!(
+            Version::prepend_section("# Changelog\n\nNotes.\n\n## 1.0.0\n\n- first\n", heading),
+            "# Changelog\n\nNotes.\n\n## 1.1.0 - 2026-10-19\n\n## 1.0.0\n\n- first\n"
+        );
+        assert_eq!(
+            Version::prepend_section("# Changelog\n\nNotes.\n", heading),
+            "# Changelog\n\nNotes.\n\n## 1.1.0 - 2026-10-19\n"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::commands::helps::yank;
+use crate::commands::publish::Publish;
+use crate::core::catalog::Catalog;
+use crate::core::channel::Channel;
+use crate::core::context::Context;
+use crate::core::ip::{Ip, PartialIpSpec};
+use crate::core::ippointer::PointerStatus;
+use crate::error::{Error, Hint};
+use crate::info;
+use crate::util::anyerror::Fault;
+use crate::util::environment::{EnvVar, Environment, ORBIT_CHAN_INDEX};
+use crate::util::filesystem;
+use std::path::PathBuf;
+
+use cliproc::{cli, proc, stage::*};
+use cliproc::{Arg, Cli, Help, Subcommand};
+
+#[derive(Debug, PartialEq)]
+pub struct Yank {
+    deprecate: bool,
+    undo: bool,
+    reason: Option<String>,
+    ip: PartialIpSpec,
+}
+
+impl Subcommand<Context> for Yank {
+    fn interpret<'c>(cli: &'c mut Cli<Memory>) -> cli::Result<Self> {
+        cli.help(Help::with(yank::HELP))?;
+        Ok(Yank {
+            // Flags
+            deprecate: cli.check(Arg::flag("deprecate"))?,
+            undo: cli.check(Arg::flag("undo"))?,
+            // Options
+            reason: cli.get(Arg::option("reason").value("text"))?,
+            // Arguments
+            ip: cli.require(Arg::positional("ip"))?,
+        })
+    }
+
+    fn execute(self, c: &Context) -> proc::Result {
+        // a reason is required to explain why the ip should not be used
+        let reason = match (&self.reason, self.undo) {
+            (_, true) => None,
+            (Some(r), false) => Some(r.clone()),
+            (None, false) => return Err(Error::YankWithoutReason)?,
+        };
+
+        // initialize environment
+        let mut env = Environment::new()
+            // read config.toml for setting any env variables
+            .from_config(c.get_config())?;
+
+        // run the synchronizations for each channel to see the latest pointers
+        let channels = c.get_config().get_channels();
+        let mut names: Vec<&&String> = channels.keys().collect();
+        names.sort();
+        for chan in channels.values() {
+            chan.run_sync(&env)?;
+        }
+
+        // only ip published to a channel can be marked
+        let catalog = Catalog::new().available(&channels)?;
+        if self.ip.as_uuid().is_none() == true
+            && catalog.mappings().contains_key(self.ip.get_name()) == false
+        {
+            return Err(Error::IpNotPublished(self.ip.to_string()))?;
+        }
+        let level = match catalog.translate_name(&self.ip.to_pkg_name())? {
+            Some(lvl) => lvl,
+            None => return Err(Error::IpNotPublished(self.ip.to_string()))?,
+        };
+
+        // collect the published versions to mark
+        let targets: Vec<&Ip> = match self.deprecate {
+            true => level.get_availability().iter().collect(),
+            false => vec![Self::find_version(&self.ip, level.get_availability())?],
+        };
+
+        for name in names {
+            let chan = channels.get(*name).unwrap();
+            let dirs: Vec<PathBuf> = targets
+                .iter()
+                .map(|ip| chan.get_root().join(Publish::create_pointer_directory(ip)))
+                .filter(|dir| dir.exists() == true)
+                .collect();
+            if dirs.is_empty() == true {
+                continue;
+            }
+            // a single version is yanked, so point the channel's hooks to it
+            if self.deprecate == false {
+                let index_path = filesystem::into_std_str(dirs.first().unwrap().clone());
+                env = env.overwrite(EnvVar::with(ORBIT_CHAN_INDEX, index_path.as_str()));
+            }
+            self.mark(chan, &dirs, &reason, &env)?;
+            info!("updated {:?} channel", name);
+        }
+
+        let spec = match self.deprecate {
+            true => self.ip.get_name().to_string(),
+            false => self.ip.to_string(),
+        };
+        match (self.deprecate, self.undo) {
+            (false, false) => info!("yanked ip {}", spec),
+            (false, true) => info!("restored yanked ip {}", spec),
+            (true, false) => info!("deprecated ip {}", spec),
+            (true, true) => info!("restored deprecated ip {}", spec),
+        }
+        Ok(())
+    }
+}
+
+impl Yank {
+    /// Finds the published ip that exactly matches the version of `spec`.
+    fn find_version<'a>(spec: &PartialIpSpec, space: &'a Vec<Ip>) -> Result<&'a Ip, Fault> {
+        let version = match spec
+            .get_version()
+            .as_specific()
+            .and_then(|v| v.as_version())
+        {
+            Some(v) => v,
+            None => return Err(Error::YankWithoutVersion(spec.to_string()))?,
+        };
+        match space
+            .iter()
+            .find(|ip| ip.get_man().get_ip().get_version() == &version)
+        {
+            Some(ip) => Ok(ip),
+            None => Err(Error::VersionNotFound(
+                spec.get_version().clone(),
+                Hint::ShowVersions,
+            ))?,
+        }
+    }
+
+    /// Updates the status of each pointer directory in `dirs` for the channel `chan`.
+    fn mark(
+        &self,
+        chan: &Channel,
+        dirs: &Vec<PathBuf>,
+        reason: &Option<String>,
+        env: &Environment,
+    ) -> Result<(), Fault> {
+        chan.run_pre(&env)?;
+        for dir in dirs {
+            let mut status = PointerStatus::read(dir);
+            match self.deprecate {
+                true => status.set_deprecated(reason.clone()),
+                false => status.set_yanked(reason.clone()),
+            }
+            status.write(dir)?;
+        }
+        chan.run_post(&env)?;
+        Ok(())
+    }
+}
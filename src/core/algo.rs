@@ -21,6 +21,7 @@ use std::path::PathBuf;
 use crate::util::anyerror::{AnyError, CodeFault, Fault};
 use crate::util::graphmap::GraphMap;
 use std::hash::Hash;
+use std::str::FromStr;
 use tempfile::tempdir;
 
 use crate::core::lang::vhdl::primaryunit::HdlNamingError;
@@ -37,6 +38,7 @@ use super::catalog::PkgName;
 use super::fileset;
 use super::ip::PartialIpSpec;
 use super::lang::sv::token::tokenizer::SystemVerilogTokenizer;
+use super::lang::verilog::libmap::LibraryMap;
 use super::lang::verilog::token::tokenizer::VerilogTokenizer;
 use super::lang::{sv, verilog, vhdl, Lang, LangIdentifier};
 
@@ -167,13 +169,19 @@ fn graph_ip<'a>(
                         },
                         false => dependency.as_uuid(),
                     };
+                    // a yanked version is only kept when it is already in the lockfile
+                    let locked = root
+                        .get_lock()
+                        .get(pkgid, dependency.get_version())
+                        .map(|entry| entry.get_version());
                     // resolve the uuid for this package... try to use existing lockfile from above code segment
                     match catalog.translate_name(&PkgName::new(pkgid, uuid))? {
                         Some(status) => {
                             // find this IP to read its dependencies
-                            match status.get_install(&AnyVersion::Specific(
-                                dependency.get_version().clone(),
-                            )) {
+                            match status.resolve_install(
+                                &AnyVersion::Specific(dependency.get_version().clone()),
+                                locked,
+                            ) {
                                 Some(cached_ip) => {
                                     // check if node is already in graph ????
                                     let s = if let Some(existing_node) = g.get_node_by_key(
@@ -227,6 +235,13 @@ fn graph_ip<'a>(
                                 }
                                 // todo: try to use the lock file to fill in missing pieces
                                 None => {
+                                    // only yanked versions are installed
+                                    if let Err(e) = status.check_yanked(
+                                        &AnyVersion::Specific(dependency.get_version().clone()),
+                                        locked,
+                                    ) {
+                                        return Err(CodeFault(None, Box::new(e)))?;
+                                    }
                                     return Err(CodeFault(
                                         None,
                                         Box::new(AnyError(format!(
@@ -237,7 +252,7 @@ fn graph_ip<'a>(
                                                 dependency.get_version().clone()
                                             )
                                         ))),
-                                    ))?
+                                    ))?;
                                 }
                             }
                         }
@@ -348,6 +363,8 @@ pub fn build_ip_file_list<'a>(
     ip_graph.get_map().iter().for_each(|(_, ip)| {
         let inner_ip = ip.as_ref().as_ip();
         let non_private_list = inner_ip.into_non_private_list();
+        // verilog files may be assigned to other libraries by a library map file
+        let lib_map = LibraryMap::load(inner_ip.get_root());
         inner_ip
             .gather_current_files()
             .into_iter()
@@ -360,11 +377,14 @@ pub fn build_ip_file_list<'a>(
                 (fileset::is_vhdl(f)) || (fileset::is_verilog(f)) || (fileset::is_systemverilog(f))
             })
             .for_each(|f| {
-                files.push(IpFileNode::new(
-                    f,
-                    inner_ip,
-                    ip.as_ref().get_library().clone(),
-                ));
+                let mapped_lib = match &lib_map {
+                    Some(map) if fileset::is_vhdl(&f) == false => map
+                        .lookup(&f)
+                        .and_then(|l| LangIdentifier::from_str(l).ok()),
+                    _ => None,
+                };
+                let lib = mapped_lib.unwrap_or(inner_ip.get_hdl_library());
+                files.push(IpFileNode::new(f, inner_ip, lib));
             })
     });
     files
@@ -606,6 +626,47 @@ impl<'a> IpFileNode<'a> {
 
     /// References the library identifier.
     pub fn get_library(&self) -> LangIdentifier {
-        self.ip.get_hdl_library()
+        self.library.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::filesystem;
+
+    #[test]
+    fn graph_ip_duplicate_units_across_ips() {
+        // install the dependencies of ip-c into a temporary cache
+        let cache = tempdir().unwrap();
+        let cache_path = cache.path().to_path_buf();
+        for name in ["ip-a", "ip-b"] {
+            filesystem::copy(
+                &PathBuf::from("./tests/s3").join(name),
+                &cache_path.join(name),
+                false,
+                None,
+            )
+            .unwrap();
+        }
+        let catalog = Catalog::new().installations(&cache_path).unwrap();
+        let root = Ip::load(PathBuf::from("./tests/s3/ip-c"), true, false).unwrap();
+
+        // ip-b defines `dupe` in its own library, but still collides with `dupe` from ip-c
+        let graph = graph_ip(&root, &catalog).unwrap();
+        let ip_b = graph
+            .get_map()
+            .iter()
+            .find(|(k, _)| k.get_name().to_string() == "ip-b")
+            .unwrap()
+            .1;
+        assert_eq!(ip_b.as_ref().is_direct_conflict(), true);
+        let ip_a = graph
+            .get_map()
+            .iter()
+            .find(|(k, _)| k.get_name().to_string() == "ip-a")
+            .unwrap()
+            .1;
+        assert_eq!(ip_a.as_ref().is_direct_conflict(), false);
     }
 }
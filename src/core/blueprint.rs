@@ -17,20 +17,35 @@
 
 use crate::core::fileset;
 use crate::util::anyerror::AnyError;
+use crate::util::filesystem;
 use cliproc::cli::Error;
 use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::io::Write;
+use std::path::Component;
 use std::{fs::File, path::PathBuf, str::FromStr};
 
 use super::algo::IpFileNode;
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "lowercase")]
 pub enum Scheme {
     Tsv,
+    #[serde(rename = "f")]
+    Filelist,
+    Ninja,
     // Json,
 }
 
+impl Scheme {
+    /// Checks if the blueprint's own build tool tracks which files need to be
+    /// analyzed again, so the outputs of previous runs must be kept.
+    pub fn is_incremental(&self) -> bool {
+        self == &Self::Ninja
+    }
+}
+
 impl Default for Scheme {
     fn default() -> Self {
         Self::Tsv
@@ -44,6 +59,8 @@ impl Display for Scheme {
             "{}",
             match self {
                 Self::Tsv => "tsv",
+                Self::Filelist => "f",
+                Self::Ninja => "ninja",
             }
         )
     }
@@ -55,6 +72,8 @@ impl FromStr for Scheme {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s.to_ascii_lowercase().as_ref() {
             "tsv" => Ok(Self::Tsv),
+            "f" => Ok(Self::Filelist),
+            "ninja" => Ok(Self::Ninja),
             // "json" => Ok(Self::Json),
             _ => Err(AnyError(format!("unknown file format: {}", s))),
         }
@@ -91,6 +110,10 @@ impl<'a, 'b> Instruction<'a, 'b> {
                 }
                 Self::Auxiliary(key, lib, file) => format!("{}\t{}\t{}", key, lib, file),
             },
+            Scheme::Filelist | Scheme::Ninja => match &self {
+                Self::Hdl(node) => node.get_file().to_string(),
+                Self::Auxiliary(_, _, file) => file.to_string(),
+            },
             // Scheme::Json => {
             //     todo!()
             // }
@@ -98,10 +121,87 @@ impl<'a, 'b> Instruction<'a, 'b> {
     }
 }
 
+/// The style of paths written to a `.f` blueprint.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+    Absolute,
+    Relative,
+}
+
+/// The target's settings for writing a `.f` blueprint.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct FilelistConfig {
+    defines: Option<Vec<String>>,
+    paths: Option<PathStyle>,
+    libraries: Option<bool>,
+}
+
+impl FilelistConfig {
+    /// Returns the macros to define, written as `NAME` or `NAME=VALUE`.
+    pub fn get_defines(&self) -> Vec<&String> {
+        match &self.defines {
+            Some(d) => d.iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Checks if paths are written relative to the blueprint's directory.
+    pub fn is_relative(&self) -> bool {
+        self.paths == Some(PathStyle::Relative)
+    }
+
+    /// Checks if files are grouped by their hdl library.
+    pub fn is_grouping_libraries(&self) -> bool {
+        self.libraries.unwrap_or(false)
+    }
+}
+
+/// The target's commands for analyzing each kind of hdl file in a `.ninja`
+/// blueprint.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
+pub struct NinjaConfig {
+    vhdl: Option<String>,
+    verilog: Option<String>,
+    systemverilog: Option<String>,
+}
+
+impl NinjaConfig {
+    /// Returns the name of the rule that analyzes the `file`.
+    fn get_rule_name(file: &str) -> &'static str {
+        if fileset::is_vhdl(file) == true {
+            "vhdl"
+        } else if fileset::is_systemverilog(file) == true {
+            "systemverilog"
+        } else {
+            "verilog"
+        }
+    }
+
+    /// Returns the command that analyzes the `file`.
+    ///
+    /// Errors with the name of the missing field if the command is not defined.
+    pub fn get_command(&self, file: &str) -> Result<&String, &'static str> {
+        let name = Self::get_rule_name(file);
+        match name {
+            "vhdl" => self.vhdl.as_ref(),
+            "systemverilog" => self.systemverilog.as_ref(),
+            _ => self.verilog.as_ref(),
+        }
+        .ok_or(name)
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Blueprint<'a, 'b> {
     scheme: Scheme,
     steps: Vec<Instruction<'a, 'b>>,
+    include_dirs: Vec<String>,
+    file_deps: HashMap<String, Vec<String>>,
+    filelist: FilelistConfig,
+    ninja: NinjaConfig,
 }
 
 impl<'a, 'b> Default for Blueprint<'a, 'b> {
@@ -109,6 +209,10 @@ impl<'a, 'b> Default for Blueprint<'a, 'b> {
         Self {
             scheme: Scheme::default(),
             steps: Vec::default(),
+            include_dirs: Vec::default(),
+            file_deps: HashMap::default(),
+            filelist: FilelistConfig::default(),
+            ninja: NinjaConfig::default(),
         }
     }
 }
@@ -118,12 +222,30 @@ impl<'a, 'b> Blueprint<'a, 'b> {
         Self {
             scheme: scheme,
             steps: Vec::new(),
+            include_dirs: Vec::new(),
+            file_deps: HashMap::new(),
+            filelist: FilelistConfig::default(),
+            ninja: NinjaConfig::default(),
         }
     }
 
+    /// Sets the settings used when writing a `.f` blueprint.
+    pub fn filelist(mut self, config: Option<&FilelistConfig>) -> Self {
+        self.filelist = config.cloned().unwrap_or_default();
+        self
+    }
+
+    /// Sets the commands used when writing a `.ninja` blueprint.
+    pub fn ninja(mut self, config: Option<&NinjaConfig>) -> Self {
+        self.ninja = config.cloned().unwrap_or_default();
+        self
+    }
+
     pub fn get_filename(&self) -> String {
         String::from(match self.scheme {
             Scheme::Tsv => "blueprint.tsv",
+            Scheme::Filelist => "blueprint.f",
+            Scheme::Ninja => "blueprint.ninja",
             // Scheme::Json => "blueprint.json",
         })
     }
@@ -133,17 +255,280 @@ impl<'a, 'b> Blueprint<'a, 'b> {
         self.steps.push(instr);
     }
 
+    /// Returns every file referenced by the blueprint.
+    pub fn get_files(&self) -> Vec<String> {
+        let mut files: Vec<String> = self
+            .steps
+            .iter()
+            .map(|i| match i {
+                Instruction::Hdl(node) => node.get_file().to_string(),
+                Instruction::Auxiliary(_, _, file) => file.to_string(),
+            })
+            .collect();
+        for deps in self.file_deps.values() {
+            for dep in deps {
+                if files.contains(dep) == false {
+                    files.push(dep.clone());
+                }
+            }
+        }
+        files
+    }
+
+    /// Add a directory to search for included header files.
+    pub fn add_include_dir(&mut self, dir: String) {
+        if self.include_dirs.contains(&dir) == false {
+            self.include_dirs.push(dir);
+        }
+    }
+
+    /// Sets the files that must be analyzed before the `file`, including any
+    /// header files it includes.
+    pub fn set_file_deps(&mut self, file: String, deps: Vec<String>) {
+        self.file_deps.insert(file, deps);
+    }
+
     pub fn write(&self, output_path: &PathBuf) -> Result<(PathBuf, usize), Error> {
         let blueprint_path = output_path.join(self.get_filename());
         let mut fd = File::create(&blueprint_path).expect("could not create blueprint file");
         // write the data
-        let data = self.steps.iter().fold(String::new(), |mut acc, i| {
-            acc.push_str(i.write(&self.scheme).as_ref());
-            acc.push('\n');
-            acc
-        });
+        let data = match self.scheme {
+            Scheme::Tsv => self.steps.iter().fold(String::new(), |mut acc, i| {
+                acc.push_str(i.write(&self.scheme).as_ref());
+                acc.push('\n');
+                acc
+            }),
+            Scheme::Filelist => self.write_filelist(output_path),
+            Scheme::Ninja => self.write_ninja(),
+        };
         fd.write_all(data.as_bytes())
             .expect("failed to write data to blueprint");
         Ok((blueprint_path, self.steps.len()))
     }
+
+    /// Composes the text for a `.f` blueprint.
+    ///
+    /// Only hdl files are written, as auxiliary files are not understood by
+    /// the tools that read file lists.
+    fn write_filelist(&self, output_path: &PathBuf) -> String {
+        let path = |p: &str| match self.filelist.is_relative() {
+            true => relative_path(output_path, p),
+            false => p.to_string(),
+        };
+        let nodes: Vec<&IpFileNode> = self
+            .steps
+            .iter()
+            .filter_map(|i| match i {
+                Instruction::Hdl(node) => Some(*node),
+                Instruction::Auxiliary(_, _, _) => None,
+            })
+            .collect();
+
+        let mut data = String::new();
+        for def in self.filelist.get_defines() {
+            data.push_str(&format!("+define+{}\n", def));
+        }
+        for dir in &self.include_dirs {
+            data.push_str(&format!("+incdir+{}\n", path(dir)));
+        }
+        match self.filelist.is_grouping_libraries() {
+            true => {
+                // reference every library before compiling the first group
+                let mut libraries: Vec<String> = Vec::new();
+                for node in &nodes {
+                    let lib = node.get_library().to_string();
+                    if libraries.contains(&lib) == false {
+                        libraries.push(lib);
+                    }
+                }
+                for lib in &libraries {
+                    data.push_str(&format!("-L {}\n", lib));
+                }
+                let mut current = None;
+                for node in &nodes {
+                    let lib = node.get_library().to_string();
+                    if current.as_ref() != Some(&lib) {
+                        data.push_str(&format!("-work {}\n", lib));
+                        current = Some(lib);
+                    }
+                    data.push_str(&format!("{}\n", path(node.get_file())));
+                }
+            }
+            false => {
+                for node in &nodes {
+                    data.push_str(&format!("{}\n", path(node.get_file())));
+                }
+            }
+        }
+        data
+    }
+
+    /// Composes the text for a `.ninja` blueprint.
+    ///
+    /// Every hdl file is analyzed in its own build step that produces a stamp
+    /// file, and each step depends on the stamps of the files it requires so
+    /// only the files affected by a change are analyzed again.
+    fn write_ninja(&self) -> String {
+        let nodes: Vec<&IpFileNode> = self
+            .steps
+            .iter()
+            .filter_map(|i| match i {
+                Instruction::Hdl(node) => Some(*node),
+                Instruction::Auxiliary(_, _, _) => None,
+            })
+            .collect();
+
+        // name a unique stamp for every file
+        let mut stamps: HashMap<&String, String> = HashMap::new();
+        let mut taken: Vec<String> = Vec::new();
+        for node in &nodes {
+            let name = PathBuf::from(node.get_file())
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let base = format!("stamps/{}/{}", node.get_library(), name);
+            let mut stamp = format!("{}.stamp", base);
+            let mut n = 1;
+            while taken.contains(&stamp) == true {
+                stamp = format!("{}.{}.stamp", base, n);
+                n += 1;
+            }
+            taken.push(stamp.clone());
+            stamps.insert(node.get_file(), stamp);
+        }
+
+        let mut data = String::from("ninja_required_version = 1.3\n");
+        // write a rule for each kind of hdl file in use
+        let mut rules: Vec<&'static str> = Vec::new();
+        for node in &nodes {
+            let rule = NinjaConfig::get_rule_name(node.get_file());
+            if rules.contains(&rule) == true {
+                continue;
+            }
+            rules.push(rule);
+            let command = self
+                .ninja
+                .get_command(node.get_file())
+                .map(|c| c.as_str())
+                .unwrap_or("exit 1");
+            data.push_str(&format!(
+                "\nrule {}\n  command = {}\n  description = analyze $in\n",
+                rule,
+                match cfg!(windows) {
+                    true => format!("cmd /c {} && type nul > $out", command),
+                    false => format!("{} && touch $out", command),
+                }
+            ));
+        }
+
+        for node in &nodes {
+            let mut implicit = Vec::new();
+            if let Some(deps) = self.file_deps.get(node.get_file()) {
+                for dep in deps {
+                    // depend on the dependency's stamp, or the file itself if it is not analyzed
+                    implicit.push(match stamps.get(dep) {
+                        Some(stamp) => ninja_escape(stamp),
+                        None => ninja_escape(dep),
+                    });
+                }
+            }
+            data.push_str(&format!(
+                "\nbuild {}: {} {}{}\n  library = {}\n",
+                ninja_escape(stamps.get(node.get_file()).unwrap()),
+                NinjaConfig::get_rule_name(node.get_file()),
+                ninja_escape(node.get_file()),
+                match implicit.is_empty() {
+                    true => String::new(),
+                    false => format!(" | {}", implicit.join(" ")),
+                },
+                node.get_library()
+            ));
+        }
+
+        data.push_str("\nbuild analyze: phony");
+        for node in &nodes {
+            data.push_str(&format!(
+                " {}",
+                ninja_escape(stamps.get(node.get_file()).unwrap())
+            ));
+        }
+        data.push_str("\n\ndefault analyze\n");
+        data
+    }
+}
+
+/// Escapes the characters that have special meaning in a ninja path.
+fn ninja_escape(p: &str) -> String {
+    p.replace('$', "$$").replace(' ', "$ ").replace(':', "$:")
+}
+
+/// Expresses the path `p` relative to the directory `base`.
+fn relative_path(base: &PathBuf, p: &str) -> String {
+    let path = PathBuf::from(p);
+    let base: Vec<Component> = base.components().collect();
+    let parts: Vec<Component> = path.components().collect();
+    let common = base
+        .iter()
+        .zip(parts.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    // paths that share no root cannot be made relative
+    if common == 0 {
+        return p.to_string();
+    }
+    let mut result = PathBuf::new();
+    for _ in common..base.len() {
+        result.push("..");
+    }
+    for c in &parts[common..] {
+        result.push(c);
+    }
+    filesystem::into_std_str(result)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn relative_paths() {
+        let base = PathBuf::from("/home/user/ip/target/sim");
+        assert_eq!(
+            relative_path(&base, "/home/user/ip/rtl/top.sv"),
+            "../../rtl/top.sv"
+        );
+        assert_eq!(
+            relative_path(&base, "/home/user/.orbit/cache/lib/a.vhd"),
+            "../../../.orbit/cache/lib/a.vhd"
+        );
+        assert_eq!(relative_path(&base, "rtl/top.sv"), "rtl/top.sv");
+    }
+
+    #[test]
+    fn ninja_commands() {
+        let config: NinjaConfig = toml::from_str("vhdl = \"ghdl -a --work=$library $in\"").unwrap();
+        assert_eq!(
+            config.get_command("rtl/top.vhd"),
+            Ok(&String::from("ghdl -a --work=$library $in"))
+        );
+        assert_eq!(config.get_command("rtl/alu.sv"), Err("systemverilog"));
+        assert_eq!(config.get_command("rtl/defs.vh"), Err("verilog"));
+        assert_eq!(
+            ninja_escape("C:/my files/$top.vhd"),
+            "C$:/my$ files/$$top.vhd"
+        );
+    }
+
+    #[test]
+    fn scheme_from_toml() {
+        #[derive(Deserialize)]
+        struct Plans {
+            plans: Vec<Scheme>,
+        }
+        let plans: Plans = toml::from_str("plans = [\"tsv\", \"f\", \"ninja\"]").unwrap();
+        assert_eq!(
+            plans.plans,
+            vec![Scheme::Tsv, Scheme::Filelist, Scheme::Ninja]
+        );
+    }
 }
@@ -29,7 +29,7 @@ use std::{
 
 use super::channel::Channel;
 use super::iparchive::ARCHIVE_EXT;
-use super::ippointer::IpPointer;
+use super::ippointer::{IpPointer, PointerStatus};
 use super::{
     pkgid::PkgPart,
     version::{AnyVersion, Version},
@@ -123,6 +123,10 @@ pub struct IpLevel {
     installs: Vec<Ip>,
     downloads: Vec<Ip>,
     available: Vec<Ip>,
+    /// The reasons for the versions yanked from channels.
+    yanked: HashMap<Version, String>,
+    /// The message for the ip being deprecated in a channel.
+    deprecated: Option<String>,
 }
 
 impl IpLevel {
@@ -131,6 +135,8 @@ impl IpLevel {
             installs: Vec::new(),
             available: Vec::new(),
             downloads: Vec::new(),
+            yanked: HashMap::new(),
+            deprecated: None,
         }
     }
 
@@ -173,19 +179,76 @@ impl IpLevel {
         self.downloads.is_empty() == false
     }
 
+    /// Records the `status` of the `version` published to a channel.
+    pub fn mark(&mut self, version: &Version, status: &PointerStatus) -> () {
+        if let Some(reason) = status.get_yanked() {
+            self.yanked.insert(version.clone(), reason.clone());
+        }
+        if let Some(message) = status.get_deprecated() {
+            self.deprecated = Some(message.clone());
+        }
+    }
+
+    /// Returns the reason the `version` was yanked, if it is yanked.
+    pub fn get_yanked(&self, version: &Version) -> Option<&String> {
+        self.yanked.get(version)
+    }
+
+    /// Returns the message explaining the ip's deprecation, if it is deprecated.
+    pub fn get_deprecated(&self) -> Option<&String> {
+        self.deprecated.as_ref()
+    }
+
+    /// Checks if the `ip` can be selected during resolution.
+    ///
+    /// Yanked versions are skipped unless they are the `locked` version.
+    fn is_selectable(&self, ip: &Ip, locked: Option<&Version>) -> bool {
+        let version = ip.get_man().get_ip().get_version();
+        self.yanked.contains_key(version) == false || locked == Some(version)
+    }
+
     /// Returns the manifest with the most compatible version fitting `version`.
     pub fn get_install(&self, version: &AnyVersion) -> Option<&Ip> {
-        Self::get_target_version(version, self.get_installations())
+        Self::get_target_version(version, self.installs.iter())
     }
 
     /// Returns the manifest with the most compatible version fitting `version`.
     pub fn get_download(&self, version: &AnyVersion) -> Option<&Ip> {
-        Self::get_target_version(version, self.get_downloads())
+        Self::get_target_version(version, self.downloads.iter())
     }
 
     /// Returns the manifest with the most compatible version fitting `version`.
     pub fn get_available(&self, version: &AnyVersion) -> Option<&Ip> {
-        Self::get_target_version(version, self.get_availability())
+        Self::get_target_version(version, self.available.iter())
+    }
+
+    /// Returns the installation with the most compatible version fitting `version`
+    /// that is not yanked, unless it is the `locked` version.
+    pub fn resolve_install(&self, version: &AnyVersion, locked: Option<&Version>) -> Option<&Ip> {
+        Self::get_target_version(
+            version,
+            self.installs
+                .iter()
+                .filter(|ip| self.is_selectable(ip, locked)),
+        )
+    }
+
+    /// Errors if the only installations fitting `version` are yanked and none of
+    /// them is the `locked` version.
+    pub fn check_yanked(
+        &self,
+        version: &AnyVersion,
+        locked: Option<&Version>,
+    ) -> Result<(), Error> {
+        match self.resolve_install(version, locked) {
+            Some(_) => Ok(()),
+            None => match self.get_install(version) {
+                Some(yanked) => Err(Error::IpOnlyYanked(
+                    yanked.get_man().get_ip().into_ip_spec(),
+                )),
+                None => Ok(()),
+            },
+        }
     }
 
     /// References the ip matching the most compatible version `version`.
@@ -194,20 +257,32 @@ impl IpLevel {
     /// first sought for in the cache installations, and if not found then searched
     /// for in the availability space.
     /// Note: `usable` to `false` will not check queued state
+    ///
+    /// Versions yanked from a channel are skipped.
     pub fn get(
         &self,
         check_downloads: bool,
         check_available: bool,
         version: &AnyVersion,
     ) -> Option<&Ip> {
-        let ins = self.get_install(version);
+        let ins = self.resolve_install(version, None);
 
         let dld = match check_downloads {
-            true => self.get_download(version),
+            true => Self::get_target_version(
+                version,
+                self.downloads
+                    .iter()
+                    .filter(|ip| self.is_selectable(ip, None)),
+            ),
             false => None,
         };
         let ava = match check_available {
-            true => self.get_available(version),
+            true => Self::get_target_version(
+                version,
+                self.available
+                    .iter()
+                    .filter(|ip| self.is_selectable(ip, None)),
+            ),
             false => None,
         };
         // keep the highest found version
@@ -260,11 +335,13 @@ impl IpLevel {
     /// Returns `None` if no compatible version was found.
     ///
     /// Panics if a development version is entered as `target`.
-    fn get_target_version<'a>(target: &AnyVersion, space: &'a Vec<Ip>) -> Option<&'a Ip> {
+    fn get_target_version<'a>(
+        target: &AnyVersion,
+        space: impl Iterator<Item = &'a Ip>,
+    ) -> Option<&'a Ip> {
         // find the specified version for the given ip
         let mut latest_version: Option<&Ip> = None;
         space
-            .iter()
             .filter(|ip| match &target {
                 AnyVersion::Specific(v) => {
                     crate::core::version::is_compatible(v, ip.get_man().get_ip().get_version())
@@ -353,16 +430,31 @@ impl<'a> Catalog<'a> {
         // update the availables
         for (&name, &chan) in channels {
             map.insert(name, chan.get_root());
-            self = self.detect(
-                map.get(name).unwrap(),
-                &IpLevel::add_available,
-                IpState::Available,
-            )?;
+            for (ip, status) in IpPointer::detect_all(chan.get_root())? {
+                let version = ip.get_man().get_ip().get_version().clone();
+                self.insert(ip, &IpLevel::add_available)
+                    .mark(&version, &status);
+            }
         }
         self.available = Some(map);
         Ok(self)
     }
 
+    /// Reads which versions are yanked and which ip are deprecated from the
+    /// `channels` without adding their ip to the availability space.
+    ///
+    /// Only ip already in the catalog are marked.
+    pub fn statuses(mut self, channels: &HashMap<&'a String, &'a Channel>) -> Result<Self, Fault> {
+        for chan in channels.values() {
+            for (ip, status) in IpPointer::detect_all(chan.get_root())? {
+                if let Some(lvl) = self.inner.get_mut(ip.get_uuid()) {
+                    lvl.mark(ip.get_man().get_ip().get_version(), &status);
+                }
+            }
+        }
+        Ok(self)
+    }
+
     pub fn set_cache_path(mut self, path: &'a PathBuf) -> Result<Self, Fault> {
         self.cache = Some(&path);
         Ok(self)
@@ -477,33 +569,41 @@ impl<'a> Catalog<'a> {
     ) -> Result<Self, Fault> {
         match lvl {
             IpState::Installation => Ip::detect_all(path, false),
-            IpState::Available => IpPointer::detect_all(path),
+            IpState::Available => Ok(IpPointer::detect_all(path)?
+                .into_iter()
+                .map(|(ip, _)| ip)
+                .collect()),
             IpState::Downloaded => IpArchive::detect_all(path),
             IpState::Unknown => Ok(Vec::new()),
         }?
         .into_iter()
-        .for_each(|ip| match self.inner.get_mut(&ip.get_uuid()) {
-            Some(lvl) => add(lvl, ip),
-            None => {
-                let pkgpart = ip.get_man().get_ip().get_name();
-                // add this to the list of uuids for this name
-                match self.mappings.get_mut(pkgpart) {
-                    Some(ids) => ids.push(ip.get_uuid().clone()),
-                    None => {
-                        self.mappings
-                            .insert(pkgpart.clone(), vec![ip.get_uuid().clone()]);
-                    }
-                }
-                let pkgid = ip.get_uuid().clone();
-                let mut lvl = IpLevel::new();
-                add(&mut lvl, ip);
-                self.inner.insert(pkgid, lvl);
-                ()
-            }
+        .for_each(|ip| {
+            self.insert(ip, add);
         });
         Ok(self)
     }
 
+    /// Places the `ip` into its level of the catalog using `add`.
+    ///
+    /// Returns the level the `ip` was added to.
+    fn insert(&mut self, ip: Ip, add: &dyn Fn(&mut IpLevel, Ip) -> ()) -> &mut IpLevel {
+        let pkgid = ip.get_uuid().clone();
+        if self.inner.contains_key(&pkgid) == false {
+            let pkgpart = ip.get_man().get_ip().get_name();
+            // add this to the list of uuids for this name
+            match self.mappings.get_mut(pkgpart) {
+                Some(ids) => ids.push(pkgid.clone()),
+                None => {
+                    self.mappings.insert(pkgpart.clone(), vec![pkgid.clone()]);
+                }
+            }
+            self.inner.insert(pkgid.clone(), IpLevel::new());
+        }
+        let lvl = self.inner.get_mut(&pkgid).unwrap();
+        add(lvl, ip);
+        lvl
+    }
+
     pub fn get_cache_path(&self) -> &PathBuf {
         self.cache.as_ref().unwrap()
     }
@@ -571,6 +671,84 @@ mod test {
         let ce = CacheEntry::from(&Uuid::nil());
         assert_eq!("0000000000000000000000000000", ce.offset());
     }
+
+    /// Creates an installation of ip "gates" at `version` under `root`.
+    fn install(root: &std::path::Path, version: &str) -> Ip {
+        let dir = root.join(version);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("Orbit.toml"),
+            format!(
+                "[ip]\nname = \"gates\"\nversion = \"{}\"\nuuid = \"0000000000000000000000000\"\n",
+                version
+            ),
+        )
+        .unwrap();
+        Ip::load(dir, false, false).unwrap()
+    }
+
+    /// Creates a level with installations 1.0.0 and 1.1.0, where 1.1.0 is yanked.
+    fn yanked_level(root: &std::path::Path) -> IpLevel {
+        let mut level = IpLevel::new();
+        level.add_install(install(root, "1.0.0"));
+        level.add_install(install(root, "1.1.0"));
+        let mut status = PointerStatus::default();
+        status.set_yanked(Some(String::from("broken reset")));
+        level.mark(&Version::from_str("1.1.0").unwrap(), &status);
+        level
+    }
+
+    fn version_of(ip: Option<&Ip>) -> String {
+        ip.unwrap().get_man().get_ip().get_version().to_string()
+    }
+
+    #[test]
+    fn resolve_skips_yanked() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut level = yanked_level(dir.path());
+        // a newer compatible version replaces the yanked one
+        level.add_install(install(dir.path(), "1.2.0"));
+        let version = AnyVersion::from_str("1").unwrap();
+        assert_eq!(version_of(level.resolve_install(&version, None)), "1.2.0");
+        assert_eq!(version_of(level.get(true, true, &version)), "1.2.0");
+        // an older compatible version is selected when no newer one exists
+        let level = yanked_level(dir.path());
+        assert_eq!(version_of(level.resolve_install(&version, None)), "1.0.0");
+        assert_eq!(level.check_yanked(&version, None), Ok(()));
+    }
+
+    #[test]
+    fn resolve_keeps_locked_yanked() {
+        let dir = tempfile::tempdir().unwrap();
+        let level = yanked_level(dir.path());
+        let locked = Version::from_str("1.1.0").unwrap();
+        let version = AnyVersion::from_str("1").unwrap();
+        assert_eq!(
+            version_of(level.resolve_install(&version, Some(&locked))),
+            "1.1.0"
+        );
+        assert_eq!(level.check_yanked(&version, Some(&locked)), Ok(()));
+    }
+
+    #[test]
+    fn resolve_only_yanked() {
+        let dir = tempfile::tempdir().unwrap();
+        let level = yanked_level(dir.path());
+        let version = AnyVersion::from_str("1.1").unwrap();
+        assert_eq!(level.resolve_install(&version, None).is_none(), true);
+        assert_eq!(
+            level.check_yanked(&version, None),
+            Err(Error::IpOnlyYanked(
+                install(dir.path(), "1.1.0")
+                    .get_man()
+                    .get_ip()
+                    .into_ip_spec()
+            ))
+        );
+        // a version that is not installed at all is not reported as yanked
+        let version = AnyVersion::from_str("2").unwrap();
+        assert_eq!(level.check_yanked(&version, None), Ok(()));
+    }
 }
 
 type Checksum = String;
@@ -0,0 +1,515 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Compares the public interfaces between two versions of an ip to determine
+//! the smallest version bump that is allowed under semantic versioning.
+
+use crate::core::ip::{InterfaceItem, UnitEntry};
+use crate::core::version::Version;
+use crate::util::anyerror::AnyError;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The part of a version that is incremented.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Bump {
+    Micro,
+    Minor,
+    Major,
+}
+
+impl Bump {
+    /// Determines the bump that was made going from version `prev` to `next`.
+    pub fn between(prev: &Version, next: &Version) -> Self {
+        if next.get_major() > prev.get_major() {
+            Self::Major
+        } else if next.get_major() == prev.get_major() && next.get_minor() > prev.get_minor() {
+            Self::Minor
+        } else {
+            Self::Micro
+        }
+    }
+}
+
+impl Display for Bump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Micro => "micro",
+                Self::Minor => "minor",
+                Self::Major => "major",
+            }
+        )
+    }
+}
+
+impl FromStr for Bump {
+    type Err = AnyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "major" => Ok(Self::Major),
+            "minor" => Ok(Self::Minor),
+            "micro" => Ok(Self::Micro),
+            _ => Err(AnyError(format!(
+                "unknown level \"{}\": expecting \"major\", \"minor\", or \"micro\"",
+                s
+            ))),
+        }
+    }
+}
+
+/// A difference found in the public interface of an ip.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Change {
+    UnitAdded(String),
+    UnitRemoved(String),
+    GenericAdded(String, InterfaceItem),
+    GenericRemoved(String, String),
+    GenericTypeChanged(String, String, String, String),
+    PortAdded(String, InterfaceItem),
+    PortRemoved(String, String),
+    PortModeChanged(String, String, String, String),
+    PortTypeChanged(String, String, String, String),
+}
+
+impl Change {
+    /// Returns the name of the unit that was changed.
+    pub fn get_unit(&self) -> &str {
+        match self {
+            Self::UnitAdded(u) | Self::UnitRemoved(u) => u,
+            Self::GenericAdded(u, _) | Self::PortAdded(u, _) => u,
+            Self::GenericRemoved(u, _) | Self::PortRemoved(u, _) => u,
+            Self::GenericTypeChanged(u, _, _, _)
+            | Self::PortModeChanged(u, _, _, _)
+            | Self::PortTypeChanged(u, _, _, _) => u,
+        }
+    }
+
+    /// Returns the kind of change, such as "port-added".
+    pub fn get_kind(&self) -> &str {
+        match self {
+            Self::UnitAdded(_) => "unit-added",
+            Self::UnitRemoved(_) => "unit-removed",
+            Self::GenericAdded(_, _) => "generic-added",
+            Self::GenericRemoved(_, _) => "generic-removed",
+            Self::GenericTypeChanged(_, _, _, _) => "generic-type-changed",
+            Self::PortAdded(_, _) => "port-added",
+            Self::PortRemoved(_, _) => "port-removed",
+            Self::PortModeChanged(_, _, _, _) => "port-mode-changed",
+            Self::PortTypeChanged(_, _, _, _) => "port-type-changed",
+        }
+    }
+
+    /// Returns the name of the generic or port that was changed.
+    ///
+    /// Returns `None` if the change is to an entire unit.
+    pub fn get_item(&self) -> Option<&str> {
+        match self {
+            Self::UnitAdded(_) | Self::UnitRemoved(_) => None,
+            Self::GenericAdded(_, i) | Self::PortAdded(_, i) => Some(i.get_name()),
+            Self::GenericRemoved(_, i) | Self::PortRemoved(_, i) => Some(i),
+            Self::GenericTypeChanged(_, i, _, _)
+            | Self::PortModeChanged(_, i, _, _)
+            | Self::PortTypeChanged(_, i, _, _) => Some(i),
+        }
+    }
+
+    /// Returns the previous mode or type of the item, if it was changed.
+    pub fn get_from(&self) -> Option<&str> {
+        match self {
+            Self::GenericTypeChanged(_, _, from, _)
+            | Self::PortModeChanged(_, _, from, _)
+            | Self::PortTypeChanged(_, _, from, _) => Some(from),
+            _ => None,
+        }
+    }
+
+    /// Returns the new mode or type of the item, if it was changed, or the type
+    /// of the item, if it was added.
+    pub fn get_to(&self) -> Option<&str> {
+        match self {
+            Self::GenericTypeChanged(_, _, _, to)
+            | Self::PortModeChanged(_, _, _, to)
+            | Self::PortTypeChanged(_, _, _, to) => Some(to),
+            Self::GenericAdded(_, i) | Self::PortAdded(_, i) => i.get_type().map(|t| t.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns the smallest version bump required to release this change.
+    ///
+    /// Additions are backward compatible unless existing instantiations would
+    /// no longer compile, which is the case for a new generic or input port
+    /// without a default value.
+    pub fn get_bump(&self) -> Bump {
+        match self {
+            Self::UnitAdded(_) => Bump::Minor,
+            Self::GenericAdded(_, item) => match item.has_default() {
+                true => Bump::Minor,
+                false => Bump::Major,
+            },
+            Self::PortAdded(_, item) => {
+                let is_input = item.get_mode().map_or(true, |m| m == "in" || m == "input");
+                match is_input == false || item.has_default() == true {
+                    true => Bump::Minor,
+                    false => Bump::Major,
+                }
+            }
+            _ => Bump::Major,
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnitAdded(u) => write!(f, "added unit \"{}\"", u),
+            Self::UnitRemoved(u) => write!(f, "removed unit \"{}\"", u),
+            Self::GenericAdded(u, g) => write!(
+                f,
+                "added generic \"{}\" to \"{}\"{}",
+                g.get_name(),
+                u,
+                match g.has_default() {
+                    true => "",
+                    false => " without a default",
+                }
+            ),
+            Self::GenericRemoved(u, g) => write!(f, "removed generic \"{}\" from \"{}\"", g, u),
+            Self::GenericTypeChanged(u, g, from, to) => write!(
+                f,
+                "changed type of generic \"{}\" in \"{}\" from \"{}\" to \"{}\"",
+                g, u, from, to
+            ),
+            Self::PortAdded(u, p) => write!(
+                f,
+                "added port \"{}\" to \"{}\"{}",
+                p.get_name(),
+                u,
+                match self.get_bump() {
+                    Bump::Major => " without a default",
+                    _ => "",
+                }
+            ),
+            Self::PortRemoved(u, p) => write!(f, "removed port \"{}\" from \"{}\"", p, u),
+            Self::PortModeChanged(u, p, from, to) => write!(
+                f,
+                "changed mode of port \"{}\" in \"{}\" from {} to {}",
+                p, u, from, to
+            ),
+            Self::PortTypeChanged(u, p, from, to) => write!(
+                f,
+                "changed type of port \"{}\" in \"{}\" from \"{}\" to \"{}\"",
+                p, u, from, to
+            ),
+        }
+    }
+}
+
+impl Serialize for Change {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Change", 5)?;
+        state.serialize_field("unit", self.get_unit())?;
+        state.serialize_field("kind", self.get_kind())?;
+        state.serialize_field("item", &self.get_item())?;
+        state.serialize_field("from", &self.get_from())?;
+        state.serialize_field("to", &self.get_to())?;
+        state.end()
+    }
+}
+
+/// Checks if two identifiers are equal for the language of the unit.
+fn is_same(a: &str, b: &str, case_sensitive: bool) -> bool {
+    match case_sensitive {
+        true => a == b,
+        false => a.eq_ignore_ascii_case(b),
+    }
+}
+
+/// Transforms a type into a form that ignores whitespace and, if the language
+/// is not case-sensitive, casing.
+fn normalize(ty: Option<&String>, case_sensitive: bool) -> String {
+    let ty: String = ty
+        .map(|s| s.as_str())
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect();
+    match case_sensitive {
+        true => ty,
+        false => ty.to_lowercase(),
+    }
+}
+
+/// Compares the declarations of an interface list from `prev` to `next`.
+fn compare_items<'a>(
+    prev: &'a Vec<InterfaceItem>,
+    next: &'a Vec<InterfaceItem>,
+    case_sensitive: bool,
+) -> (
+    Vec<&'a InterfaceItem>,
+    Vec<&'a InterfaceItem>,
+    Vec<(&'a InterfaceItem, &'a InterfaceItem)>,
+) {
+    let removed = prev
+        .iter()
+        .filter(|p| {
+            next.iter()
+                .any(|n| is_same(p.get_name(), n.get_name(), case_sensitive))
+                == false
+        })
+        .collect();
+    let added = next
+        .iter()
+        .filter(|n| {
+            prev.iter()
+                .any(|p| is_same(p.get_name(), n.get_name(), case_sensitive))
+                == false
+        })
+        .collect();
+    let kept = prev
+        .iter()
+        .filter_map(|p| {
+            next.iter()
+                .find(|n| is_same(p.get_name(), n.get_name(), case_sensitive))
+                .map(|n| (p, n))
+        })
+        .collect();
+    (removed, added, kept)
+}
+
+/// Collects the changes between the public units of the previous version `prev`
+/// and the public units of the next version `next`.
+///
+/// The interfaces of units are only compared when both versions recorded them.
+pub fn compare(prev: &Vec<UnitEntry>, next: &Vec<UnitEntry>) -> Vec<Change> {
+    let public = |units: &'_ Vec<UnitEntry>| -> Vec<UnitEntry> {
+        units
+            .iter()
+            .filter(|u| u.get_visibility() == "public")
+            .cloned()
+            .collect()
+    };
+    compare_units(&public(prev), &public(next))
+}
+
+/// Collects the changes between all of the units of the previous version `prev`
+/// and all of the units of the next version `next`.
+pub fn compare_units(prev: &Vec<UnitEntry>, next: &Vec<UnitEntry>) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for p in prev {
+        let case_sensitive = p.get_language() != "vhdl";
+        let n = match next
+            .iter()
+            .find(|n| is_same(p.get_name(), n.get_name(), case_sensitive))
+        {
+            Some(n) => n,
+            None => {
+                changes.push(Change::UnitRemoved(p.get_name().to_string()));
+                continue;
+            }
+        };
+        let (p_iface, n_iface) = match (p.get_interface(), n.get_interface()) {
+            (Some(a), Some(b)) => (a, b),
+            _ => continue,
+        };
+        let unit = n.get_name().to_string();
+        // compare the generics
+        let (removed, added, kept) = compare_items(
+            p_iface.get_generics(),
+            n_iface.get_generics(),
+            case_sensitive,
+        );
+        removed.into_iter().for_each(|g| {
+            changes.push(Change::GenericRemoved(
+                unit.clone(),
+                g.get_name().to_string(),
+            ))
+        });
+        added
+            .into_iter()
+            .for_each(|g| changes.push(Change::GenericAdded(unit.clone(), g.clone())));
+        kept.into_iter().for_each(|(a, b)| {
+            let (ta, tb) = (
+                normalize(a.get_type(), case_sensitive),
+                normalize(b.get_type(), case_sensitive),
+            );
+            if ta != tb {
+                changes.push(Change::GenericTypeChanged(
+                    unit.clone(),
+                    b.get_name().to_string(),
+                    a.get_type().cloned().unwrap_or_default(),
+                    b.get_type().cloned().unwrap_or_default(),
+                ));
+            }
+        });
+        // compare the ports
+        let (removed, added, kept) =
+            compare_items(p_iface.get_ports(), n_iface.get_ports(), case_sensitive);
+        removed.into_iter().for_each(|g| {
+            changes.push(Change::PortRemoved(unit.clone(), g.get_name().to_string()))
+        });
+        added
+            .into_iter()
+            .for_each(|g| changes.push(Change::PortAdded(unit.clone(), g.clone())));
+        kept.into_iter().for_each(|(a, b)| {
+            if a.get_mode() != b.get_mode() {
+                changes.push(Change::PortModeChanged(
+                    unit.clone(),
+                    b.get_name().to_string(),
+                    a.get_mode().cloned().unwrap_or_default(),
+                    b.get_mode().cloned().unwrap_or_default(),
+                ));
+            }
+            let (ta, tb) = (
+                normalize(a.get_type(), case_sensitive),
+                normalize(b.get_type(), case_sensitive),
+            );
+            if ta != tb {
+                changes.push(Change::PortTypeChanged(
+                    unit.clone(),
+                    b.get_name().to_string(),
+                    a.get_type().cloned().unwrap_or_default(),
+                    b.get_type().cloned().unwrap_or_default(),
+                ));
+            }
+        });
+    }
+    // find the new units
+    for n in next {
+        let case_sensitive = n.get_language() != "vhdl";
+        if prev
+            .iter()
+            .any(|p| is_same(p.get_name(), n.get_name(), case_sensitive))
+            == false
+        {
+            changes.push(Change::UnitAdded(n.get_name().to_string()));
+        }
+    }
+    changes
+}
+
+/// Returns the smallest version bump that is required to release all of the
+/// `changes`.
+pub fn required_bump(changes: &Vec<Change>) -> Bump {
+    changes
+        .iter()
+        .map(|c| c.get_bump())
+        .max()
+        .unwrap_or(Bump::Micro)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::ip::UnitInterface;
+
+    fn unit(name: &str, visibility: &str, ports: Vec<InterfaceItem>) -> UnitEntry {
+        UnitEntry::new(
+            name,
+            "vhdl",
+            "entity",
+            visibility,
+            Some(UnitInterface::new(
+                vec![InterfaceItem::new(
+                    "WIDTH",
+                    None,
+                    Some("positive"),
+                    Some("8"),
+                )],
+                ports,
+            )),
+        )
+    }
+
+    #[test]
+    fn compare_interfaces() {
+        let prev = vec![
+            unit(
+                "fifo",
+                "public",
+                vec![
+                    InterfaceItem::new("clk", Some("in"), Some("std_logic"), None),
+                    InterfaceItem::new("data", Some("in"), Some("std_logic_vector"), None),
+                ],
+            ),
+            unit("adder", "public", vec![]),
+            unit("helper", "protected", vec![]),
+        ];
+        // only additions
+        let next = vec![
+            unit(
+                "FIFO",
+                "public",
+                vec![
+                    InterfaceItem::new("clk", Some("in"), Some("STD_LOGIC"), None),
+                    InterfaceItem::new("data", Some("in"), Some("std_logic_vector"), None),
+                    InterfaceItem::new("full", Some("out"), Some("std_logic"), None),
+                ],
+            ),
+            unit("adder", "public", vec![]),
+            unit("mux", "public", vec![]),
+        ];
+        let changes = compare(&prev, &next);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(required_bump(&changes), Bump::Minor);
+
+        // breaking changes
+        let next = vec![unit(
+            "fifo",
+            "public",
+            vec![
+                InterfaceItem::new("clk", Some("out"), Some("std_logic"), None),
+                InterfaceItem::new("din", Some("in"), Some("std_logic_vector"), None),
+            ],
+        )];
+        let changes = compare(&prev, &next);
+        assert_eq!(
+            changes,
+            vec![
+                Change::PortRemoved(String::from("fifo"), String::from("data")),
+                Change::PortAdded(
+                    String::from("fifo"),
+                    InterfaceItem::new("din", Some("in"), Some("std_logic_vector"), None)
+                ),
+                Change::PortModeChanged(
+                    String::from("fifo"),
+                    String::from("clk"),
+                    String::from("in"),
+                    String::from("out")
+                ),
+                Change::UnitRemoved(String::from("adder")),
+            ]
+        );
+        assert_eq!(required_bump(&changes), Bump::Major);
+        assert_eq!(required_bump(&compare(&prev, &prev)), Bump::Micro);
+    }
+
+    #[test]
+    fn bump_between_versions() {
+        let v = |s: &str| Version::from_str(s).unwrap();
+        assert_eq!(Bump::between(&v("1.2.3"), &v("2.0.0")), Bump::Major);
+        assert_eq!(Bump::between(&v("1.2.3"), &v("1.3.0")), Bump::Minor);
+        assert_eq!(Bump::between(&v("1.2.3"), &v("1.2.4")), Bump::Micro);
+    }
+}
@@ -0,0 +1,1003 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Reads the source files referenced by existing vendor projects.
+//!
+//! The supported file lists are plain `.f` file lists, Vivado project files
+//! (`.xpr`) and Tcl scripts, and Quartus settings files (`.qsf`).
+
+use crate::core::manifest::IpName;
+use crate::core::uuid::Uuid;
+use crate::util::anyerror::AnyError;
+use crate::util::filesystem;
+use crate::util::filesystem::Standardize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Libraries that tools assign to files by default.
+const DEFAULT_LIBRARIES: [&str; 2] = ["work", "xil_defaultlib"];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ListFormat {
+    Filelist,
+    Xpr,
+    Tcl,
+    Qsf,
+}
+
+impl ListFormat {
+    /// Determines the format of the file list from its file extension.
+    fn detect(path: &PathBuf) -> Result<Self, AnyError> {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+        match ext.as_ref() {
+            "f" => Ok(Self::Filelist),
+            "xpr" => Ok(Self::Xpr),
+            "tcl" => Ok(Self::Tcl),
+            "qsf" | "qip" => Ok(Self::Qsf),
+            _ => Err(AnyError(format!(
+                "unsupported file list {:?}; expecting a file ending with .f, .xpr, .tcl, .qsf, or .qip",
+                PathBuf::standardize(path)
+            ))),
+        }
+    }
+}
+
+/// The source files collected from an existing project's file list.
+#[derive(Debug, PartialEq)]
+pub struct FileList {
+    files: Vec<PathBuf>,
+    include_dirs: Vec<PathBuf>,
+    libraries: Vec<String>,
+    missing: Vec<(PathBuf, PathBuf)>,
+    warnings: Vec<String>,
+}
+
+impl FileList {
+    fn new() -> Self {
+        Self {
+            files: Vec::new(),
+            include_dirs: Vec::new(),
+            libraries: Vec::new(),
+            missing: Vec::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Reads the file list at `path`, following any nested file lists.
+    ///
+    /// The format is determined by the file's extension.
+    pub fn read(path: &PathBuf) -> Result<Self, AnyError> {
+        let format = ListFormat::detect(path)?;
+        let mut list = Self::new();
+        list.read_list(&filesystem::full_normal(path), format, &mut Vec::new())?;
+        Ok(list)
+    }
+
+    /// Returns the warnings encountered while reading the file list.
+    pub fn get_warnings(&self) -> &Vec<String> {
+        &self.warnings
+    }
+
+    /// Returns the files that were referenced but not found, paired with the
+    /// file list that referenced them.
+    pub fn get_missing(&self) -> &Vec<(PathBuf, PathBuf)> {
+        &self.missing
+    }
+
+    fn read_list(
+        &mut self,
+        path: &PathBuf,
+        format: ListFormat,
+        visited: &mut Vec<PathBuf>,
+    ) -> Result<(), AnyError> {
+        if visited.contains(path) == true {
+            self.warnings.push(format!(
+                "skipping file list {:?} because it includes itself",
+                path
+            ));
+            return Ok(());
+        }
+        let text = match std::fs::read_to_string(path) {
+            Ok(t) => t,
+            Err(e) => {
+                return Err(AnyError(format!(
+                    "failed to read file list {:?}: {}",
+                    path, e
+                )))
+            }
+        };
+        visited.push(path.clone());
+        match format {
+            ListFormat::Filelist => self.read_filelist(&text, path, visited)?,
+            ListFormat::Xpr => self.read_xpr(&text, path),
+            ListFormat::Tcl | ListFormat::Qsf => self.read_tcl(&text, path, visited)?,
+        }
+        visited.pop();
+        Ok(())
+    }
+
+    /// Records a referenced file or directory that is relative to `dir`.
+    fn add_path(&mut self, dir: &PathBuf, p: &str, source: &PathBuf) {
+        let path = PathBuf::standardize(dir.join(p));
+        if path.is_dir() == true {
+            self.add_include_dir(dir, p, source);
+        } else if path.exists() == false {
+            if self.missing.iter().any(|(m, _)| m == &path) == false {
+                self.missing.push((path, source.clone()));
+            }
+        } else if self.files.contains(&path) == false {
+            self.files.push(path);
+        }
+    }
+
+    /// Records a referenced directory whose files are all used.
+    fn add_include_dir(&mut self, dir: &PathBuf, p: &str, source: &PathBuf) {
+        let path = PathBuf::standardize(dir.join(p));
+        if path.is_dir() == false {
+            if self.missing.iter().any(|(m, _)| m == &path) == false {
+                self.missing.push((path, source.clone()));
+            }
+        } else if self.include_dirs.contains(&path) == false {
+            self.include_dirs.push(path);
+        }
+    }
+
+    fn add_library(&mut self, lib: &str) {
+        let lib = lib.trim();
+        if lib.is_empty() == true
+            || DEFAULT_LIBRARIES
+                .iter()
+                .any(|d| d.eq_ignore_ascii_case(lib))
+                == true
+        {
+            return;
+        }
+        if self.libraries.iter().any(|l| l == lib) == false {
+            self.libraries.push(lib.to_string());
+        }
+    }
+
+    /// Reads a plain `.f` file list.
+    ///
+    /// Nested file lists (`-f`, `-F`) and files are resolved relative to the
+    /// directory of the file list that references them.
+    fn read_filelist(
+        &mut self,
+        text: &str,
+        source: &PathBuf,
+        visited: &mut Vec<PathBuf>,
+    ) -> Result<(), AnyError> {
+        let dir = source.parent().unwrap().to_path_buf();
+        let mut words = split_filelist(text).into_iter();
+        while let Some(word) = words.next() {
+            let word = match expand_env(&word) {
+                Ok(w) => w,
+                Err(var) => {
+                    self.warnings.push(format!(
+                        "skipping \"{}\" in {:?} because variable \"{}\" is not set",
+                        word, source, var
+                    ));
+                    continue;
+                }
+            };
+            if word == "-f" || word == "-F" {
+                if let Some(nested) = words.next().and_then(|w| expand_env(&w).ok()) {
+                    let path = PathBuf::standardize(dir.join(&nested));
+                    match path.exists() {
+                        true => self.read_list(&path, ListFormat::Filelist, visited)?,
+                        false => self.missing.push((path, source.clone())),
+                    }
+                }
+            } else if word == "-v" {
+                if let Some(file) = words.next().and_then(|w| expand_env(&w).ok()) {
+                    self.add_path(&dir, &file, source);
+                }
+            } else if word == "-y" {
+                if let Some(lib_dir) = words.next() {
+                    self.warnings.push(format!(
+                        "skipping library directory \"{}\" in {:?}",
+                        lib_dir, source
+                    ));
+                }
+            } else if let Some(dirs) = word.strip_prefix("+incdir+") {
+                for d in dirs.split('+').filter(|d| d.is_empty() == false) {
+                    self.add_include_dir(&dir, d, source);
+                }
+            } else if word.starts_with('+') == true || word.starts_with('-') == true {
+                // ignore other tool options
+                continue;
+            } else {
+                self.add_path(&dir, &word, source);
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a Vivado project file.
+    ///
+    /// Paths are resolved using the project's directory for `$PPRDIR`.
+    fn read_xpr(&mut self, text: &str, source: &PathBuf) {
+        let dir = source.parent().unwrap().to_path_buf();
+        let ppr_dir = filesystem::into_std_str(dir.clone());
+        let mut rest = text;
+        while let Some(i) = rest.find("<File ") {
+            rest = &rest[i..];
+            // the file's library is found before the end of its element
+            let tag_end = rest.find('>').unwrap_or(rest.len());
+            let end = match rest[..tag_end].ends_with('/') {
+                true => tag_end,
+                false => rest.find("</File>").unwrap_or(rest.len()),
+            };
+            let element = &rest[..end];
+            if let Some(path) = xml_attr(element, "Path") {
+                self.add_path(&dir, &path.replace("$PPRDIR", &ppr_dir), source);
+            }
+            if let Some(i) = element.find("Name=\"Library\"") {
+                if let Some(lib) = xml_attr(&element[i..], "Val") {
+                    self.add_library(&lib);
+                }
+            }
+            rest = &rest[end..];
+        }
+        // verilog include directories are stored as fileset options
+        let mut rest = text;
+        while let Some(i) = rest.find("<Option Name=\"VerilogDir\"") {
+            rest = &rest[i..];
+            let end = rest.find("/>").unwrap_or(rest.len());
+            if let Some(val) = xml_attr(&rest[..end], "Val") {
+                for d in val.split('|').filter(|d| d.is_empty() == false) {
+                    self.add_include_dir(&dir, &d.replace("$PPRDIR", &ppr_dir), source);
+                }
+            }
+            rest = &rest[end..];
+        }
+    }
+
+    /// Reads a Vivado Tcl script or a Quartus settings file.
+    ///
+    /// Only the commands that add source files are interpreted, along with
+    /// `set` to track variables used within paths.
+    fn read_tcl(
+        &mut self,
+        text: &str,
+        source: &PathBuf,
+        visited: &mut Vec<PathBuf>,
+    ) -> Result<(), AnyError> {
+        let dir = source.parent().unwrap().to_path_buf();
+        let mut tcl = Tcl::new(source);
+        for cmd in split_tcl(text) {
+            let name = match cmd.first() {
+                Some(n) => n.as_str(),
+                None => continue,
+            };
+            // evaluate the command's arguments
+            let mut args: Vec<Vec<String>> = Vec::new();
+            let mut unresolved = None;
+            for word in &cmd[1..] {
+                match tcl.eval_word(word) {
+                    Ok(v) => args.push(v),
+                    Err(e) => {
+                        unresolved = Some(e);
+                        break;
+                    }
+                }
+            }
+            let is_source_cmd = match name {
+                "set"
+                | "read_vhdl"
+                | "read_verilog"
+                | "add_files"
+                | "import_files"
+                | "set_global_assignment" => true,
+                _ => false,
+            };
+            if is_source_cmd == false {
+                continue;
+            }
+            if let Some(e) = unresolved {
+                if name != "set" {
+                    self.warnings.push(format!(
+                        "skipping command \"{}\" in {:?} because \"{}\" could not be resolved",
+                        name, source, e
+                    ));
+                }
+                continue;
+            }
+            match name {
+                "set" => {
+                    if let (Some(var), Some(val)) = (args.get(0), args.get(1)) {
+                        tcl.set(&var.join(" "), &val.join(" "));
+                    }
+                }
+                "set_global_assignment" => {
+                    let opts = Options::parse(&args, &["-name", "-library", "-section_id"]);
+                    let value = match opts.values.first() {
+                        Some(v) => v,
+                        None => continue,
+                    };
+                    match opts.get("-name").unwrap_or("").to_uppercase().as_ref() {
+                        "VHDL_FILE"
+                        | "VERILOG_FILE"
+                        | "SYSTEMVERILOG_FILE"
+                        | "VERILOG_INCLUDE_FILE"
+                        | "SDC_FILE" => {
+                            self.add_path(&dir, value, source);
+                            if let Some(lib) = opts.get("-library") {
+                                self.add_library(lib);
+                            }
+                        }
+                        "SEARCH_PATH" | "USER_LIBRARIES" => {
+                            for d in value.split(';') {
+                                self.add_include_dir(&dir, d, source);
+                            }
+                        }
+                        "QIP_FILE" => {
+                            let path = PathBuf::standardize(dir.join(value));
+                            match path.exists() {
+                                true => self.read_list(&path, ListFormat::Qsf, visited)?,
+                                false => self.missing.push((path, source.clone())),
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                _ => {
+                    // read_vhdl, read_verilog, add_files, import_files
+                    let opts = Options::parse(&args, &["-library", "-fileset", "-of_objects"]);
+                    for value in &opts.values {
+                        self.add_path(&dir, value, source);
+                    }
+                    if let Some(lib) = opts.get("-library") {
+                        self.add_library(lib);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Composes the text for an `Orbit.toml` file that includes the collected
+    /// files found within `root`.
+    ///
+    /// Any files that cannot be represented are stored in the file list's warnings.
+    pub fn into_manifest(
+        &mut self,
+        name: &IpName,
+        library: &Option<String>,
+        root: &PathBuf,
+    ) -> String {
+        let root = PathBuf::standardize(root);
+        let mut include = Vec::new();
+        let mut outside = Vec::new();
+        for f in &self.files {
+            match f.strip_prefix(&root) {
+                Ok(p) => include.push(filesystem::into_std_str(p.to_path_buf())),
+                Err(_) => outside.push(f.clone()),
+            }
+        }
+        for d in &self.include_dirs {
+            match d.strip_prefix(&root) {
+                Ok(p) => match p.as_os_str().is_empty() {
+                    // the entire ip is already included
+                    true => continue,
+                    false => {
+                        include.push(format!("{}/", filesystem::into_std_str(p.to_path_buf())))
+                    }
+                },
+                Err(_) => outside.push(d.clone()),
+            }
+        }
+        for p in outside {
+            self.warnings.push(format!(
+                "skipping {:?} because it is outside of the ip's directory",
+                p
+            ));
+        }
+
+        let library = match library {
+            Some(l) => Some(l.clone()),
+            None => {
+                if self.libraries.len() > 1 {
+                    self.warnings.push(format!(
+                        "files were found in multiple libraries ({}); using \"{}\"",
+                        self.libraries.join(", "),
+                        self.libraries[0]
+                    ));
+                }
+                self.libraries.first().cloned()
+            }
+        };
+
+        let mut result = format!(
+            "[ip]\nname = \"{}\"\nversion = \"0.1.0\"\nuuid = \"{}\"\n",
+            name,
+            Uuid::new()
+        );
+        if let Some(lib) = library {
+            result.push_str(&format!("library = \"{}\"\n", lib));
+        }
+        if include.is_empty() == false {
+            result.push_str("include = [\n");
+            for f in &include {
+                result.push_str(&format!("    \"{}\",\n", f.replace('"', "\\\"")));
+            }
+            result.push_str("]\n");
+        }
+        result.push_str("\n[dependencies]\n");
+        result
+    }
+}
+
+/// The options and remaining values given to a Tcl command.
+struct Options {
+    named: Vec<(String, String)>,
+    values: Vec<String>,
+}
+
+impl Options {
+    /// Separates the command's arguments into options and values, where the
+    /// options in `with_values` take the following argument as their value.
+    ///
+    /// Any other argument beginning with '-' is a flag and is ignored.
+    fn parse(args: &Vec<Vec<String>>, with_values: &[&str]) -> Self {
+        let mut named = Vec::new();
+        let mut values = Vec::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.len() == 1 && arg[0].starts_with('-') == true {
+                true => {
+                    if with_values.contains(&arg[0].as_str()) == true {
+                        if let Some(v) = iter.next() {
+                            named.push((arg[0].clone(), v.join(" ")));
+                        }
+                    }
+                }
+                false => values.extend(arg.iter().cloned()),
+            }
+        }
+        Self { named, values }
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        self.named
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// The variables known while reading a Tcl script.
+struct Tcl {
+    script: PathBuf,
+    vars: HashMap<String, String>,
+}
+
+impl Tcl {
+    fn new(script: &PathBuf) -> Self {
+        let mut vars = HashMap::new();
+        // quartus ip files reference their own directory through this variable
+        vars.insert(
+            String::from("quartus(qip_path)"),
+            filesystem::into_std_str(script.parent().unwrap().to_path_buf()),
+        );
+        Self {
+            script: script.clone(),
+            vars,
+        }
+    }
+
+    fn set(&mut self, name: &str, value: &str) {
+        self.vars
+            .insert(name.trim_start_matches("::").to_string(), value.to_string());
+    }
+
+    /// Evaluates a word into its list of values.
+    ///
+    /// Braced words and `[list ...]` commands are split into multiple values.
+    /// Returns the text that could not be resolved on failure.
+    fn eval_word(&self, word: &str) -> Result<Vec<String>, String> {
+        if word.starts_with('{') == true && word.ends_with('}') == true {
+            let inner = &word[1..word.len() - 1];
+            return Ok(inner.split_whitespace().map(|s| s.to_string()).collect());
+        }
+        let word = match word.starts_with('"') == true && word.ends_with('"') && word.len() > 1 {
+            true => &word[1..word.len() - 1],
+            false => word,
+        };
+        if word.starts_with("[list ") == true && word.ends_with(']') == true {
+            let mut values = Vec::new();
+            for item in split_tcl(&word[6..word.len() - 1]).concat() {
+                values.extend(self.eval_word(&item)?);
+            }
+            return Ok(values);
+        }
+        Ok(vec![self.subst(word)?])
+    }
+
+    /// Substitutes variables and supported commands within the text.
+    fn subst(&self, text: &str) -> Result<String, String> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut result = String::new();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i] {
+                '\\' if i + 1 < chars.len() => {
+                    result.push(chars[i + 1]);
+                    i += 2;
+                }
+                '$' => {
+                    i += 1;
+                    let mut name = String::new();
+                    if i < chars.len() && chars[i] == '{' {
+                        i += 1;
+                        while i < chars.len() && chars[i] != '}' {
+                            name.push(chars[i]);
+                            i += 1;
+                        }
+                        i += 1;
+                    } else {
+                        while i < chars.len()
+                            && (chars[i].is_ascii_alphanumeric()
+                                || chars[i] == '_'
+                                || chars[i] == ':')
+                        {
+                            name.push(chars[i]);
+                            i += 1;
+                        }
+                        // array element
+                        if i < chars.len() && chars[i] == '(' {
+                            while i < chars.len() && chars[i] != ')' {
+                                name.push(chars[i]);
+                                i += 1;
+                            }
+                            name.push(')');
+                            i += 1;
+                        }
+                    }
+                    match self.vars.get(name.trim_start_matches("::")) {
+                        Some(v) => result.push_str(v),
+                        None => return Err(format!("${}", name)),
+                    }
+                }
+                '[' => {
+                    let start = i;
+                    let mut depth = 0;
+                    while i < chars.len() {
+                        match chars[i] {
+                            '[' => depth += 1,
+                            ']' => {
+                                depth -= 1;
+                                if depth == 0 {
+                                    break;
+                                }
+                            }
+                            _ => (),
+                        }
+                        i += 1;
+                    }
+                    let inner: String = chars[start + 1..i.min(chars.len())].iter().collect();
+                    result.push_str(&self.eval_command(&inner)?);
+                    i += 1;
+                }
+                c => {
+                    result.push(c);
+                    i += 1;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Evaluates the file path commands commonly found in project scripts.
+    fn eval_command(&self, text: &str) -> Result<String, String> {
+        let words = split_tcl(text).concat();
+        let mut args = Vec::new();
+        for w in &words {
+            args.push(self.eval_word(w)?.join(" "));
+        }
+        let args: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        match args.as_slice() {
+            ["info", "script"] => Ok(filesystem::into_std_str(self.script.clone())),
+            ["file", "normalize", p] => Ok(p.to_string()),
+            ["file", "dirname", p] => Ok(match p.rsplit_once('/') {
+                Some((parent, _)) => parent.to_string(),
+                None => String::from("."),
+            }),
+            ["file", "join", parts @ ..] => Ok(parts.join("/")),
+            _ => Err(format!("[{}]", text)),
+        }
+    }
+}
+
+/// Splits Tcl text into a list of commands, where each command is a list of
+/// its unevaluated words.
+fn split_tcl(text: &str) -> Vec<Vec<String>> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut commands = Vec::new();
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    let mut braces = 0;
+    let mut brackets = 0;
+    let mut quoted = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let nested = braces > 0 || brackets > 0 || quoted == true;
+        match c {
+            '\\' if i + 1 < chars.len() => {
+                if chars[i + 1] == '\n' && nested == false {
+                    // line continuation
+                    if word.is_empty() == false {
+                        words.push(std::mem::take(&mut word));
+                    }
+                } else {
+                    word.push(c);
+                    word.push(chars[i + 1]);
+                }
+                i += 1;
+            }
+            '#' if nested == false && words.is_empty() && word.is_empty() => {
+                // skip the comment
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            '\n' | ';' if nested == false => {
+                if word.is_empty() == false {
+                    words.push(std::mem::take(&mut word));
+                }
+                if words.is_empty() == false {
+                    commands.push(std::mem::take(&mut words));
+                }
+            }
+            ' ' | '\t' | '\r' if nested == false => {
+                if word.is_empty() == false {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            _ => {
+                match c {
+                    '{' if quoted == false => braces += 1,
+                    '}' if quoted == false && braces > 0 => braces -= 1,
+                    '[' if braces == 0 => brackets += 1,
+                    ']' if braces == 0 && brackets > 0 => brackets -= 1,
+                    '"' if braces == 0 && brackets == 0 => quoted = !quoted,
+                    _ => (),
+                }
+                word.push(c);
+            }
+        }
+        i += 1;
+    }
+    if word.is_empty() == false {
+        words.push(word);
+    }
+    if words.is_empty() == false {
+        commands.push(words);
+    }
+    commands
+}
+
+/// Splits the text of a `.f` file list into its words, removing comments.
+fn split_filelist(text: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut in_block = false;
+    for line in text.lines() {
+        let mut line = line;
+        if in_block == true {
+            match line.find("*/") {
+                Some(i) => {
+                    line = &line[i + 2..];
+                    in_block = false;
+                }
+                None => continue,
+            }
+        }
+        let mut content = String::new();
+        let mut rest = line;
+        loop {
+            let comment = rest.find("//");
+            let block = rest.find("/*");
+            match (comment, block) {
+                (Some(c), Some(b)) if b < c => {
+                    content.push_str(&rest[..b]);
+                    match rest[b..].find("*/") {
+                        Some(e) => {
+                            content.push(' ');
+                            rest = &rest[b + e + 2..];
+                        }
+                        None => {
+                            in_block = true;
+                            break;
+                        }
+                    }
+                }
+                (Some(c), _) => {
+                    content.push_str(&rest[..c]);
+                    break;
+                }
+                (None, Some(b)) => {
+                    content.push_str(&rest[..b]);
+                    match rest[b..].find("*/") {
+                        Some(e) => {
+                            content.push(' ');
+                            rest = &rest[b + e + 2..];
+                        }
+                        None => {
+                            in_block = true;
+                            break;
+                        }
+                    }
+                }
+                (None, None) => {
+                    content.push_str(rest);
+                    break;
+                }
+            }
+        }
+        // a word beginning with '#' comments out the remainder of the line
+        words.extend(
+            content
+                .split_whitespace()
+                .take_while(|w| w.starts_with('#') == false)
+                .map(|w| w.trim_matches('"').to_string()),
+        );
+    }
+    words
+}
+
+/// Expands environment variables written as `$VAR`, `${VAR}`, or `$(VAR)`.
+///
+/// Returns the name of the first variable that is not set on failure.
+fn expand_env(word: &str) -> Result<String, String> {
+    let mut result = String::new();
+    let mut rest = word;
+    while let Some(i) = rest.find('$') {
+        result.push_str(&rest[..i]);
+        rest = &rest[i + 1..];
+        let (name, len) = match rest.chars().next() {
+            Some('{') => match rest.find('}') {
+                Some(e) => (&rest[1..e], e + 1),
+                None => return Err(rest.to_string()),
+            },
+            Some('(') => match rest.find(')') {
+                Some(e) => (&rest[1..e], e + 1),
+                None => return Err(rest.to_string()),
+            },
+            _ => {
+                let e = rest
+                    .find(|c: char| c.is_ascii_alphanumeric() == false && c != '_')
+                    .unwrap_or(rest.len());
+                (&rest[..e], e)
+            }
+        };
+        match std::env::var(name) {
+            Ok(v) => result.push_str(&v),
+            Err(_) => return Err(name.to_string()),
+        }
+        rest = &rest[len..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Returns the unescaped value of the XML attribute `name` within `element`.
+fn xml_attr(element: &str, name: &str) -> Option<String> {
+    let key = format!(" {}=\"", name);
+    let start = element.find(&key)? + key.len();
+    let len = element[start..].find('"')?;
+    Some(
+        element[start..start + len]
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&"),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+    use std::str::FromStr;
+    use tempfile::tempdir;
+
+    #[test]
+    fn split_tcl_words() {
+        let text = r#"# create the project
+set origin_dir [file dirname [info script]]
+read_vhdl -library lib {rtl/a.vhd rtl/b.vhd}; read_verilog "rtl/c.v"
+add_files -norecurse \
+    $origin_dir/rtl/d.sv
+"#;
+        assert_eq!(
+            split_tcl(text),
+            vec![
+                vec!["set", "origin_dir", "[file dirname [info script]]"],
+                vec!["read_vhdl", "-library", "lib", "{rtl/a.vhd rtl/b.vhd}"],
+                vec!["read_verilog", "\"rtl/c.v\""],
+                vec!["add_files", "-norecurse", "$origin_dir/rtl/d.sv"],
+            ]
+        );
+    }
+
+    #[test]
+    fn eval_tcl_words() {
+        let mut tcl = Tcl::new(&PathBuf::from("/proj/scripts/build.tcl"));
+        let origin_dir = tcl.eval_word("[file dirname [info script]]").unwrap();
+        tcl.set("origin_dir", &origin_dir[0]);
+        assert_eq!(
+            tcl.eval_word("\"$origin_dir/../rtl/top.vhd\""),
+            Ok(vec![String::from("/proj/scripts/../rtl/top.vhd")])
+        );
+        assert_eq!(
+            tcl.eval_word("[list a.v ${origin_dir}/b.v]"),
+            Ok(vec![String::from("a.v"), String::from("/proj/scripts/b.v")])
+        );
+        assert_eq!(
+            tcl.eval_word("[file join $::quartus(qip_path) \"fifo.v\"]"),
+            Ok(vec![String::from("/proj/scripts/fifo.v")])
+        );
+        assert_eq!(
+            tcl.eval_word("[get_property DIRECTORY [current_project]]/a.v"),
+            Err(String::from("[current_project]"))
+        );
+    }
+
+    #[test]
+    fn read_nested_filelist() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        fs::create_dir_all(root.join("rtl/include")).unwrap();
+        fs::write(root.join("rtl/top.sv"), "").unwrap();
+        fs::write(root.join("rtl/alu.sv"), "").unwrap();
+        fs::write(root.join("rtl/include/defs.svh"), "").unwrap();
+        fs::write(
+            root.join("files.f"),
+            "// top-level list\n+incdir+rtl/include\n-f rtl/rtl.f\n/* unused */ +define+SIM\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("rtl/rtl.f"),
+            "top.sv\nalu.sv # trailing\nmissing.sv\n-f ../files.f\n",
+        )
+        .unwrap();
+
+        let mut list = FileList::read(&root.join("files.f")).unwrap();
+        assert_eq!(
+            list.get_missing(),
+            &vec![(
+                PathBuf::standardize(root.join("rtl/missing.sv")),
+                PathBuf::standardize(root.join("rtl/rtl.f"))
+            )]
+        );
+        assert_eq!(list.get_warnings().len(), 1);
+
+        let manifest = list.into_manifest(
+            &IpName::from_str("cpu").unwrap(),
+            &Some(String::from("cpu")),
+            &root,
+        );
+        assert_eq!(
+            manifest.split_once("library").unwrap().1,
+            r#" = "cpu"
+include = [
+    "rtl/top.sv",
+    "rtl/alu.sv",
+    "rtl/include/",
+]
+
+[dependencies]
+"#
+        );
+    }
+
+    #[test]
+    fn read_quartus_settings() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        fs::create_dir_all(root.join("ip/fifo")).unwrap();
+        fs::write(root.join("top.vhd"), "").unwrap();
+        fs::write(root.join("ip/fifo/fifo.v"), "").unwrap();
+        fs::write(
+            root.join("ip/fifo/fifo.qip"),
+            "set_global_assignment -name VERILOG_FILE [file join $::quartus(qip_path) \"fifo.v\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("proj.qsf"),
+            r#"set_global_assignment -name TOP_LEVEL_ENTITY top
+set_global_assignment -name VHDL_FILE top.vhd -library soc
+set_global_assignment -name QIP_FILE ip/fifo/fifo.qip
+set_global_assignment -name SYSTEMVERILOG_FILE old.sv
+set_location_assignment PIN_A1 -to clk
+"#,
+        )
+        .unwrap();
+
+        let mut list = FileList::read(&root.join("proj.qsf")).unwrap();
+        assert_eq!(list.get_missing().len(), 1);
+        let manifest = list.into_manifest(&IpName::from_str("soc").unwrap(), &None, &root);
+        assert_eq!(
+            manifest.split_once("library").unwrap().1,
+            r#" = "soc"
+include = [
+    "top.vhd",
+    "ip/fifo/fifo.v",
+]
+
+[dependencies]
+"#
+        );
+    }
+
+    #[test]
+    fn read_vivado_project() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        fs::create_dir_all(root.join("proj")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("src/top.vhd"), "").unwrap();
+        fs::write(root.join("src/pins.xdc"), "").unwrap();
+        fs::write(
+            root.join("proj/proj.xpr"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Project Version="7">
+  <FileSets Version="1" Minor="31">
+    <FileSet Name="sources_1" Type="DesignSrcs" RelSrcDir="$PSRCDIR/sources_1">
+      <File Path="$PPRDIR/../src/top.vhd">
+        <FileInfo>
+          <Attr Name="Library" Val="soc_lib"/>
+          <Attr Name="UsedIn" Val="synthesis"/>
+        </FileInfo>
+      </File>
+      <File Path="$PPRDIR/../src/gone.vhd"/>
+    </FileSet>
+    <FileSet Name="constrs_1" Type="Constrs" RelSrcDir="$PSRCDIR/constrs_1">
+      <File Path="$PPRDIR/../src/pins.xdc"/>
+    </FileSet>
+  </FileSets>
+</Project>
+"#,
+        )
+        .unwrap();
+
+        let mut list = FileList::read(&root.join("proj/proj.xpr")).unwrap();
+        assert_eq!(
+            list.get_missing()[0].0,
+            PathBuf::standardize(root.join("src/gone.vhd"))
+        );
+        let manifest = list.into_manifest(&IpName::from_str("soc").unwrap(), &None, &root);
+        assert_eq!(
+            manifest.split_once("library").unwrap().1,
+            r#" = "soc_lib"
+include = [
+    "src/top.vhd",
+    "src/pins.xdc",
+]
+
+[dependencies]
+"#
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::util::anyerror::Fault;
+use crate::util::sha256;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// The file within a target's output directory that stores the fingerprint of
+/// the target's last successful execution.
+pub const FINGERPRINT_FILE: &str = ".fingerprint";
+
+/// A summary of every input that can affect the result of executing a target.
+#[derive(Debug, PartialEq)]
+pub struct Fingerprint {
+    data: String,
+}
+
+impl Fingerprint {
+    pub fn new() -> Self {
+        Self {
+            data: String::new(),
+        }
+    }
+
+    /// Adds a named value to the fingerprint.
+    pub fn text(mut self, key: &str, value: &str) -> Self {
+        self.data.push_str(&format!("{}={}\n", key, value));
+        self
+    }
+
+    /// Adds the variables to the fingerprint, sorted by their keys.
+    pub fn vars(mut self, vars: &HashMap<&String, &String>) -> Self {
+        let mut keys: Vec<&&String> = vars.keys().collect();
+        keys.sort();
+        for k in keys {
+            self = self.text(&format!("env.{}", k), vars.get(*k).unwrap());
+        }
+        self
+    }
+
+    /// Adds the contents of the file at `path` to the fingerprint.
+    pub fn file(self, path: &str) -> Self {
+        let checksum = match std::fs::read(path) {
+            Ok(bytes) => sha256::compute_sha256(&bytes).to_string(),
+            Err(_) => String::from("missing"),
+        };
+        self.text(&format!("file.{}", path), &checksum)
+    }
+
+    /// Reads the fingerprint stored in the output directory `dir`, if one exists.
+    pub fn read(dir: &PathBuf) -> Option<String> {
+        std::fs::read_to_string(dir.join(FINGERPRINT_FILE))
+            .ok()
+            .map(|s| s.trim().to_string())
+    }
+
+    /// Stores the fingerprint in the output directory `dir`.
+    pub fn write(&self, dir: &PathBuf) -> Result<(), Fault> {
+        std::fs::write(dir.join(FINGERPRINT_FILE), format!("{}\n", self))?;
+        Ok(())
+    }
+
+    /// Removes any fingerprint stored in the output directory `dir`.
+    pub fn remove(dir: &PathBuf) -> Result<(), Fault> {
+        let path = dir.join(FINGERPRINT_FILE);
+        if path.exists() == true {
+            std::fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Fingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", sha256::compute_sha256(self.data.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::filesystem;
+    use tempfile::tempdir;
+
+    #[test]
+    fn detects_changes() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let file = filesystem::into_std_str(root.join("top.vhd"));
+        std::fs::write(&file, "entity top is end entity;").unwrap();
+
+        let compose = || {
+            Fingerprint::new()
+                .text("command", "ghdl")
+                .text("args", "-a")
+                .file(&file)
+        };
+        assert_eq!(compose().to_string(), compose().to_string());
+
+        // changing a file changes the fingerprint
+        let before = compose();
+        before.write(&root).unwrap();
+        assert_eq!(Fingerprint::read(&root), Some(before.to_string()));
+        std::fs::write(&file, "entity top2 is end entity;").unwrap();
+        assert_ne!(Fingerprint::read(&root), Some(compose().to_string()));
+
+        // changing the arguments changes the fingerprint
+        assert_ne!(
+            compose().text("extra", "--wave").to_string(),
+            compose().to_string()
+        );
+
+        Fingerprint::remove(&root).unwrap();
+        assert_eq!(Fingerprint::read(&root), None);
+    }
+}
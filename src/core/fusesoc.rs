@@ -0,0 +1,780 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Reads and writes FuseSoC CAPI2 `.core` files.
+
+use crate::core::catalog::{Catalog, PkgName};
+use crate::core::fileset;
+use crate::core::manifest::IpName;
+use crate::core::pkgid::PkgPart;
+use crate::core::uuid::Uuid;
+use crate::core::version::{AnyVersion, PartialVersion, Version};
+use crate::util::anyerror::AnyError;
+use std::str::FromStr;
+
+const CAPI2_HEADER: &str = "CAPI=2:";
+
+/// The name of the single fileset written when exporting a `.core` file.
+const EXPORT_FILESET: &str = "rtl";
+
+#[derive(Debug, PartialEq)]
+struct CoreSource {
+    path: String,
+    logical_name: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct CoreFileset {
+    name: String,
+    files: Vec<CoreSource>,
+    depend: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct CoreTarget {
+    name: String,
+    filesets: Vec<String>,
+    toplevel: Option<String>,
+}
+
+/// The contents of a CAPI2 `.core` file.
+#[derive(Debug, PartialEq)]
+pub struct CoreFile {
+    vlnv: String,
+    description: Option<String>,
+    filesets: Vec<CoreFileset>,
+    targets: Vec<CoreTarget>,
+    warnings: Vec<String>,
+}
+
+impl FromStr for CoreFile {
+    type Err = AnyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim_start().starts_with(CAPI2_HEADER) == false {
+            return Err(AnyError(format!(
+                "missing \"{}\" header (only CAPI2 core files are supported)",
+                CAPI2_HEADER
+            )));
+        }
+        let root = Yaml::parse(s)?;
+        let mut warnings = Vec::new();
+
+        let vlnv = match root.get("name").and_then(|n| n.as_str()) {
+            Some(n) => n.to_string(),
+            None => return Err(AnyError(format!("missing required key \"name\""))),
+        };
+
+        let mut filesets = Vec::new();
+        if let Some(entries) = root.get("filesets").and_then(|f| f.as_map()) {
+            for (name, fset) in entries {
+                let default_lib = fset
+                    .get("logical_name")
+                    .and_then(|l| l.as_str())
+                    .map(|l| l.to_string());
+                let mut files = Vec::new();
+                for f in fset
+                    .get("files")
+                    .and_then(|f| f.as_list())
+                    .unwrap_or(&Vec::new())
+                {
+                    // a file is either a path or a path mapped to its attributes
+                    let (path, attrs) = match f {
+                        Yaml::Scalar(p) => (p.clone(), None),
+                        Yaml::Map(m) if m.len() == 1 => (m[0].0.clone(), Some(&m[0].1)),
+                        _ => continue,
+                    };
+                    if is_conditional(&path) == true {
+                        warnings.push(format!(
+                            "skipping conditional file \"{}\" in fileset \"{}\"",
+                            path, name
+                        ));
+                        continue;
+                    }
+                    files.push(CoreSource {
+                        path: path,
+                        logical_name: attrs
+                            .and_then(|a| a.get("logical_name"))
+                            .and_then(|l| l.as_str())
+                            .map(|l| l.to_string())
+                            .or(default_lib.clone()),
+                    });
+                }
+                let mut depend = Vec::new();
+                for d in fset
+                    .get("depend")
+                    .and_then(|d| d.as_list())
+                    .unwrap_or(&Vec::new())
+                {
+                    if let Some(d) = d.as_str() {
+                        match is_conditional(d) {
+                            true => warnings.push(format!(
+                                "skipping conditional dependency \"{}\" in fileset \"{}\"",
+                                d, name
+                            )),
+                            false => depend.push(d.to_string()),
+                        }
+                    }
+                }
+                filesets.push(CoreFileset {
+                    name: name.clone(),
+                    files: files,
+                    depend: depend,
+                });
+            }
+        }
+
+        let mut targets = Vec::new();
+        if let Some(entries) = root.get("targets").and_then(|t| t.as_map()) {
+            for (name, target) in entries {
+                targets.push(CoreTarget {
+                    name: name.clone(),
+                    filesets: target
+                        .get("filesets")
+                        .and_then(|f| f.as_list())
+                        .map(|l| {
+                            l.iter()
+                                .filter_map(|f| f.as_str())
+                                .map(|f| f.to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    toplevel: target
+                        .get("toplevel")
+                        .and_then(|t| match t {
+                            Yaml::List(l) => l.first().and_then(|t| t.as_str()),
+                            _ => t.as_str(),
+                        })
+                        .map(|t| t.to_string()),
+                });
+            }
+        }
+
+        Ok(Self {
+            vlnv: vlnv,
+            description: root
+                .get("description")
+                .and_then(|d| d.as_str())
+                .map(|d| d.to_string()),
+            filesets: filesets,
+            targets: targets,
+            warnings: warnings,
+        })
+    }
+}
+
+impl CoreFile {
+    /// Accesses the messages for parts of the core file that were not converted.
+    pub fn get_warnings(&self) -> &Vec<String> {
+        &self.warnings
+    }
+
+    /// Returns the name part of the core's VLNV.
+    pub fn get_name(&self) -> &str {
+        split_vlnv(&self.vlnv).0
+    }
+
+    /// Returns the version part of the core's VLNV.
+    pub fn get_version(&self) -> Option<&str> {
+        split_vlnv(&self.vlnv).1
+    }
+
+    /// Prefixes the relative file paths with the directory `dir`, which is the
+    /// location of the core file relative to the ip's root.
+    pub fn set_root(&mut self, dir: &str) {
+        if dir.is_empty() == true {
+            return;
+        }
+        self.filesets
+            .iter_mut()
+            .flat_map(|f| f.files.iter_mut())
+            .filter(|f| std::path::Path::new(&f.path).is_relative())
+            .for_each(|f| f.path = format!("{}/{}", dir, f.path));
+    }
+
+    /// Returns the default target, which is the target named "default" or
+    /// otherwise the first target.
+    fn get_default_target(&self) -> Option<&CoreTarget> {
+        self.targets
+            .iter()
+            .find(|t| t.name == "default")
+            .or(self.targets.first())
+    }
+
+    /// Returns the filesets used by any target, or all filesets if there are
+    /// no targets.
+    fn get_used_filesets(&self) -> Vec<&CoreFileset> {
+        match self.targets.is_empty() {
+            true => self.filesets.iter().collect(),
+            false => self
+                .filesets
+                .iter()
+                .filter(|f| self.targets.iter().any(|t| t.filesets.contains(&f.name)))
+                .collect(),
+        }
+    }
+
+    /// Composes the text for an `Orbit.toml` file that describes the same ip as
+    /// the core file.
+    ///
+    /// Dependencies found in the `catalog` are written as entries; the rest are
+    /// written as comments. Any parts that cannot be represented are stored in
+    /// the core file's warnings.
+    pub fn into_manifest(
+        &mut self,
+        name: &IpName,
+        library: &Option<String>,
+        catalog: &Catalog,
+    ) -> String {
+        let version = match self.get_version() {
+            Some(v) => match complete_version(v) {
+                Some(v) => v,
+                None => {
+                    self.warnings
+                        .push(format!("invalid version \"{}\"; using \"0.1.0\"", v));
+                    Version::new().minor(1)
+                }
+            },
+            None => Version::new().minor(1),
+        };
+
+        let filesets = self.get_used_filesets();
+
+        // use the first logical name found as the library when none is given
+        let library = library.clone().or(filesets
+            .iter()
+            .flat_map(|f| f.files.iter())
+            .find_map(|f| f.logical_name.clone()));
+
+        let mut include = Vec::new();
+        let mut deps: Vec<(PkgPart, Option<PartialVersion>)> = Vec::new();
+        let mut warnings = Vec::new();
+        for fset in &filesets {
+            for f in &fset.files {
+                if include.contains(&f.path) == false {
+                    include.push(f.path.clone());
+                }
+            }
+            for d in &fset.depend {
+                match parse_dependency(d) {
+                    Some((dep_name, dep_ver)) => {
+                        if deps.iter().any(|(n, _)| n == &dep_name) == false {
+                            deps.push((dep_name, dep_ver));
+                        }
+                    }
+                    None => warnings.push(format!("invalid dependency \"{}\"", d)),
+                }
+            }
+        }
+        // files only found in filesets that no target uses
+        let mut exclude = Vec::new();
+        for fset in &self.filesets {
+            if filesets.contains(&fset) == true {
+                continue;
+            }
+            for f in &fset.files {
+                if include.contains(&f.path) == false && exclude.contains(&f.path) == false {
+                    exclude.push(f.path.clone());
+                }
+            }
+        }
+        self.warnings.append(&mut warnings);
+
+        let mut result = format!(
+            "[ip]\nname = {}\nversion = \"{}\"\nuuid = \"{}\"\n",
+            quote(&name.to_string()),
+            version,
+            Uuid::new()
+        );
+        if let Some(lib) = library {
+            result.push_str(&format!("library = {}\n", quote(&lib)));
+        }
+        if let Some(desc) = &self.description {
+            result.push_str(&format!("description = {}\n", quote(desc.trim())));
+        }
+        if include.is_empty() == false {
+            result.push_str("include = [\n");
+            for f in &include {
+                result.push_str(&format!("    {},\n", quote(f)));
+            }
+            result.push_str("]\n");
+        }
+        if exclude.is_empty() == false {
+            result.push_str("exclude = [\n");
+            for f in &exclude {
+                result.push_str(&format!("    {},\n", quote(f)));
+            }
+            result.push_str("]\n");
+        }
+
+        result.push_str(&format!(
+            "\n[ip.metadata.fusesoc]\nvlnv = {}\n",
+            quote(&self.vlnv)
+        ));
+        if let Some(top) = self.get_default_target().and_then(|t| t.toplevel.as_ref()) {
+            result.push_str(&format!("toplevel = {}\n", quote(top)));
+        }
+
+        // dependencies not found in the catalog are commented out
+        result.push_str("\n[dependencies]\n");
+        for (dep_name, dep_ver) in &deps {
+            match (resolve_dependency(catalog, dep_name, dep_ver), dep_ver) {
+                (Some(v), _) => result.push_str(&format!("{} = \"{}\"\n", dep_name, v)),
+                (None, Some(v)) => {
+                    result.push_str(&format!("# {} = \"{}\"\n", dep_name, v));
+                    self.warnings.push(format!(
+                        "dependency \"{}\" was not found in the catalog and was written as a comment in [dependencies]",
+                        dep_name
+                    ));
+                }
+                (None, None) => self.warnings.push(format!(
+                    "dependency \"{}\" was not found in the catalog and requires a version to be added to [dependencies]",
+                    dep_name
+                )),
+            }
+        }
+        result
+    }
+}
+
+/// Writes the text for a CAPI2 `.core` file.
+///
+/// The `files` are written in order as a single fileset, where each file is
+/// paired with its hdl library.
+pub fn write_core(
+    vlnv: &str,
+    description: Option<&String>,
+    files: &Vec<(String, String)>,
+    top: &str,
+) -> String {
+    let mut result = format!("{}\nname: {}\n", CAPI2_HEADER, quote(vlnv));
+    if let Some(desc) = description {
+        result.push_str(&format!("description: {}\n", quote(desc.trim())));
+    }
+    result.push_str(&format!("\nfilesets:\n  {}:\n    files:\n", EXPORT_FILESET));
+    for (lib, path) in files {
+        result.push_str(&format!(
+            "      - {}: {{file_type: {}, logical_name: {}}}\n",
+            quote(path),
+            file_type(path),
+            lib
+        ));
+    }
+    result.push_str(&format!(
+        "\ntargets:\n  default:\n    filesets: [{}]\n    toplevel: {}\n",
+        EXPORT_FILESET, top
+    ));
+    result
+}
+
+/// Determines the CAPI2 file type for an hdl source file.
+fn file_type(path: &str) -> &'static str {
+    if fileset::is_vhdl(path) == true {
+        "vhdlSource"
+    } else if fileset::is_verilog(path) == true {
+        "verilogSource"
+    } else if fileset::is_systemverilog(path) == true {
+        "systemVerilogSource"
+    } else {
+        "user"
+    }
+}
+
+/// Wraps the text in double quotes, escaping any characters as needed.
+///
+/// The result is valid as both a TOML basic string and a YAML double-quoted scalar.
+fn quote(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' => result.push_str("\\n"),
+            '\t' => result.push_str("\\t"),
+            '\r' => result.push_str("\\r"),
+            c if c.is_control() == true => result.push_str(&format!("\\u{:04X}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Checks if the entry uses the `flag ? (entry)` syntax of CAPI2.
+fn is_conditional(s: &str) -> bool {
+    s.contains('?') && s.trim_end().ends_with(')')
+}
+
+/// Splits a VLNV into its name and optional version.
+///
+/// The VLNV is in the form `vendor:library:name:version`, where the vendor,
+/// library, and version may be omitted.
+fn split_vlnv(vlnv: &str) -> (&str, Option<&str>) {
+    let parts: Vec<&str> = vlnv.split(':').collect();
+    match parts.len() {
+        0 | 1 => (vlnv, None),
+        2 => (parts[0], Some(parts[1])),
+        3 => (parts[2], None),
+        _ => (
+            parts[2],
+            match parts[3].is_empty() {
+                true => None,
+                false => Some(parts[3]),
+            },
+        ),
+    }
+}
+
+/// Extends a partial version into a full version, removing any revision label.
+fn complete_version(s: &str) -> Option<Version> {
+    let s = s.split_once('-').map(|(v, _)| v).unwrap_or(s);
+    let mut levels: Vec<&str> = s.split('.').collect();
+    while levels.len() < 3 {
+        levels.push("0");
+    }
+    Version::from_str(&levels.join(".")).ok()
+}
+
+/// Finds the version requirement to write for the dependency `name` by
+/// looking it up in the `catalog`.
+///
+/// The requirement from the core file is kept when it is given, otherwise the
+/// latest version in the catalog is used.
+fn resolve_dependency(
+    catalog: &Catalog,
+    name: &PkgPart,
+    version: &Option<PartialVersion>,
+) -> Option<String> {
+    let level = catalog.translate_name(&PkgName::new(name, None)).ok()??;
+    let target = match version {
+        Some(v) => AnyVersion::Specific(v.clone()),
+        None => AnyVersion::Latest,
+    };
+    let ip = level.get(true, true, &target)?;
+    Some(match version {
+        Some(v) => v.to_string(),
+        None => ip.get_man().get_ip().get_version().to_string(),
+    })
+}
+
+/// Parses a CAPI2 dependency such as `>=vendor:library:name:1.0` into an ip
+/// name and its version.
+fn parse_dependency(s: &str) -> Option<(PkgPart, Option<PartialVersion>)> {
+    let s = s.trim_start_matches(|c| "<>=^~!".contains(c));
+    let (name, version) = split_vlnv(s);
+    let name = PkgPart::from_str(name).ok()?;
+    let version = match version {
+        Some(v) => PartialVersion::from_str(v.split_once('-').map(|(v, _)| v).unwrap_or(v)).ok(),
+        None => None,
+    };
+    Some((name, version))
+}
+
+/// A node of a YAML document.
+#[derive(Debug, PartialEq)]
+enum Yaml {
+    Scalar(String),
+    List(Vec<Yaml>),
+    Map(Vec<(String, Yaml)>),
+}
+
+impl Yaml {
+    fn parse(s: &str) -> Result<Self, AnyError> {
+        match serde_yaml::from_str::<serde_yaml::Value>(s) {
+            Ok(v) => Ok(Self::from_value(v)),
+            Err(e) => Err(AnyError(e.to_string())),
+        }
+    }
+
+    /// Converts a parsed YAML value into a node, where every scalar is kept as
+    /// text and a null value is an empty scalar.
+    fn from_value(v: serde_yaml::Value) -> Self {
+        use serde_yaml::Value;
+        match v {
+            Value::Null => Self::Scalar(String::new()),
+            Value::Bool(b) => Self::Scalar(b.to_string()),
+            Value::Number(n) => Self::Scalar(n.to_string()),
+            Value::String(s) => Self::Scalar(s),
+            Value::Sequence(l) => Self::List(l.into_iter().map(|i| Self::from_value(i)).collect()),
+            Value::Mapping(m) => Self::Map(
+                m.into_iter()
+                    .filter_map(|(k, v)| match Self::from_value(k) {
+                        Self::Scalar(k) => Some((k, Self::from_value(v))),
+                        _ => None,
+                    })
+                    .collect(),
+            ),
+            Value::Tagged(t) => Self::from_value(t.value),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&Yaml> {
+        match self {
+            Self::Map(m) => m.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Scalar(s) => Some(s.as_str()),
+            _ => None,
+        }
+    }
+
+    fn as_list(&self) -> Option<&Vec<Yaml>> {
+        match self {
+            Self::List(l) => Some(l),
+            _ => None,
+        }
+    }
+
+    fn as_map(&self) -> Option<&Vec<(String, Yaml)>> {
+        match self {
+            Self::Map(m) => Some(m),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::filesystem;
+    use std::path::PathBuf;
+    use tempfile::tempdir;
+
+    const CORE: &str = r#"CAPI=2:
+# a uart core
+name: "acme:comm:uart:1.2"
+description: Simple uart transceiver
+
+filesets:
+  rtl:
+    files:
+      - rtl/uart_pkg.vhd
+      - rtl/uart.vhd : {logical_name: comm}
+      - "icarus? (rtl/sim_only.v)"
+    file_type: vhdlSource
+    depend:
+      - ">=acme:base:fifo:2.1"
+      - acme:base:gates
+  tb:
+    files:
+    - tb/uart_tb.vhd
+    file_type: vhdlSource
+  unused:
+    files: [legacy/old.vhd]
+
+targets:
+  default:
+    filesets: [rtl]
+    toplevel: uart
+  sim:
+    default_tool: ghdl
+    filesets:
+      - rtl
+      - tb
+    toplevel: uart_tb
+"#;
+
+    #[test]
+    fn parse_yaml() {
+        let y = Yaml::parse(
+            r#"a: 1
+b:
+  - x
+  - y: 'it''s' # the core's comment
+    z: [1, "2, 3"]
+c: {k: v, e: }
+d: |
+  line one
+  line two
+# don't stop reading here
+e: [
+  first,
+  second,
+]
+"#,
+        )
+        .unwrap();
+        assert_eq!(y.get("a"), Some(&Yaml::Scalar(String::from("1"))));
+        let b = y.get("b").unwrap().as_list().unwrap();
+        assert_eq!(b[0], Yaml::Scalar(String::from("x")));
+        assert_eq!(b[1].get("y"), Some(&Yaml::Scalar(String::from("it's"))));
+        assert_eq!(
+            b[1].get("z"),
+            Some(&Yaml::List(vec![
+                Yaml::Scalar(String::from("1")),
+                Yaml::Scalar(String::from("2, 3"))
+            ]))
+        );
+        assert_eq!(
+            y.get("c").unwrap().get("e"),
+            Some(&Yaml::Scalar(String::new()))
+        );
+        assert_eq!(
+            y.get("d"),
+            Some(&Yaml::Scalar(String::from("line one\nline two\n")))
+        );
+        // flow sequences may span multiple lines
+        assert_eq!(
+            y.get("e"),
+            Some(&Yaml::List(vec![
+                Yaml::Scalar(String::from("first")),
+                Yaml::Scalar(String::from("second"))
+            ]))
+        );
+    }
+
+    #[test]
+    fn read_core() {
+        let core = CoreFile::from_str(CORE).unwrap();
+        assert_eq!(core.get_name(), "uart");
+        assert_eq!(core.get_version(), Some("1.2"));
+        assert_eq!(core.filesets.len(), 3);
+        assert_eq!(core.filesets[0].files.len(), 2);
+        assert_eq!(
+            core.filesets[0].files[1].logical_name,
+            Some(String::from("comm"))
+        );
+        assert_eq!(core.get_warnings().len(), 1);
+        assert_eq!(
+            core.get_default_target().unwrap().toplevel,
+            Some(String::from("uart"))
+        );
+        // the unused fileset is not referenced by a target
+        assert_eq!(core.get_used_filesets().len(), 2);
+    }
+
+    #[test]
+    fn core_into_manifest() {
+        let mut core = CoreFile::from_str(CORE).unwrap();
+        let man = core.into_manifest(&PkgPart::from_str("uart").unwrap(), &None, &Catalog::new());
+        assert!(man.contains("version = \"1.2.0\"\n"));
+        assert!(man.contains("library = \"comm\"\n"));
+        assert!(man.contains(
+            "include = [\n    \"rtl/uart_pkg.vhd\",\n    \"rtl/uart.vhd\",\n    \"tb/uart_tb.vhd\",\n]\n"
+        ));
+        assert!(man.contains("exclude = [\n    \"legacy/old.vhd\",\n]\n"));
+        assert!(man.contains("toplevel = \"uart\"\n"));
+        assert!(man.contains("[dependencies]\n# fifo = \"2.1\"\n"));
+        // the conditional file and the unresolved dependencies are reported
+        assert_eq!(core.get_warnings().len(), 3);
+        // the manifest must be valid toml
+        assert!(man.parse::<toml::Table>().is_ok());
+    }
+
+    #[test]
+    fn dependencies() {
+        assert_eq!(
+            parse_dependency(">=acme:base:fifo:2.1"),
+            Some((
+                PkgPart::from_str("fifo").unwrap(),
+                Some(PartialVersion::from_str("2.1").unwrap())
+            ))
+        );
+        assert_eq!(
+            parse_dependency("::gates:1.0.0-r2"),
+            Some((
+                PkgPart::from_str("gates").unwrap(),
+                Some(PartialVersion::from_str("1.0.0").unwrap())
+            ))
+        );
+        assert_eq!(
+            parse_dependency("acme:base:gates"),
+            Some((PkgPart::from_str("gates").unwrap(), None))
+        );
+    }
+
+    #[test]
+    fn core_into_manifest_resolves_dependencies() {
+        // install the dependencies into a temporary cache
+        let cache = tempdir().unwrap();
+        let cache_path = cache.path().to_path_buf();
+        for name in ["ip-a", "ip-b"] {
+            filesystem::copy(
+                &PathBuf::from("./tests/s3").join(name),
+                &cache_path.join(name),
+                false,
+                None,
+            )
+            .unwrap();
+        }
+        let catalog = Catalog::new().installations(&cache_path).unwrap();
+
+        let mut core = CoreFile::from_str(
+            r#"CAPI=2:
+name: ::top:1.0.0
+filesets:
+  rtl:
+    depend: ["::ip-a:0.1", ip-b, "::fifo:2.1"]
+"#,
+        )
+        .unwrap();
+        let man = core.into_manifest(&PkgPart::from_str("top").unwrap(), &None, &catalog);
+        // the requirement is kept, or the latest version is used when it is missing
+        assert!(
+            man.contains("[dependencies]\nip-a = \"0.1\"\nip-b = \"0.1.0\"\n# fifo = \"2.1\"\n")
+        );
+        assert_eq!(core.get_warnings().len(), 1);
+    }
+
+    #[test]
+    fn multi_line_description() {
+        let mut core = CoreFile::from_str(
+            "CAPI=2:\nname: ::uart:1.0.0\ndescription: |\n  A simple uart.\n  Supports\tparity.\n",
+        )
+        .unwrap();
+        let man = core.into_manifest(&PkgPart::from_str("uart").unwrap(), &None, &Catalog::new());
+        // the manifest must be valid toml and keep the line breaks
+        let table = man.parse::<toml::Table>().unwrap();
+        assert_eq!(
+            table["ip"]["description"].as_str(),
+            Some("A simple uart.\nSupports\tparity.")
+        );
+
+        let desc = String::from("first line\nsecond \"line\"\u{1}");
+        let core =
+            CoreFile::from_str(&write_core("::uart:1.0.0", Some(&desc), &vec![], "uart")).unwrap();
+        assert_eq!(core.description, Some(desc));
+    }
+
+    #[test]
+    fn export_round_trip() {
+        let text = write_core(
+            "orbit:comm:uart:1.2.0",
+            None,
+            &vec![
+                (String::from("base"), String::from("/cache/fifo.vhd")),
+                (String::from("comm"), String::from("rtl/uart.vhd")),
+            ],
+            "uart",
+        );
+        let core = CoreFile::from_str(&text).unwrap();
+        assert_eq!(core.get_name(), "uart");
+        assert_eq!(core.filesets[0].files[0].path, "/cache/fifo.vhd");
+        assert_eq!(
+            core.filesets[0].files[1].logical_name,
+            Some(String::from("comm"))
+        );
+        assert_eq!(
+            core.get_default_target().unwrap().toplevel,
+            Some(String::from("uart"))
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::util::anyerror::{AnyError, Fault};
+use colored::Colorize;
+use std::str::FromStr;
+
+/// A generic (VHDL) or parameter (Verilog) override set from the command-line.
+///
+/// Multiple comma-separated values form a sweep, where the target is executed
+/// once for each value.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Generic {
+    name: String,
+    values: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum GenericError {
+    MissingSeparator(char),
+    EmptyName,
+    EmptyValue,
+}
+
+impl std::error::Error for GenericError {}
+
+impl std::fmt::Display for GenericError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self {
+            Self::MissingSeparator(c) => write!(f, "missing separator '{}'", c),
+            Self::EmptyName => write!(f, "empty name"),
+            Self::EmptyValue => write!(f, "empty value"),
+        }
+    }
+}
+
+impl FromStr for Generic {
+    type Err = GenericError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, values) = match s.split_once('=') {
+            Some(r) => r,
+            None => return Err(Self::Err::MissingSeparator('=')),
+        };
+        let name = name.trim();
+        if name.is_empty() == true {
+            return Err(Self::Err::EmptyName);
+        }
+        let values: Vec<String> = values.split(',').map(|v| v.trim().to_string()).collect();
+        if values.iter().any(|v| v.is_empty()) == true {
+            return Err(Self::Err::EmptyValue);
+        }
+        Ok(Self {
+            name: name.to_string(),
+            values: values,
+        })
+    }
+}
+
+impl Generic {
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn get_values(&self) -> &Vec<String> {
+        &self.values
+    }
+
+    /// Checks if the generic has more than one value to execute the target with.
+    pub fn is_sweep(&self) -> bool {
+        self.values.len() > 1
+    }
+}
+
+/// A single assignment of a value to every generic.
+pub type Combination = Vec<(String, String)>;
+
+/// Expands the `generics` into every combination of their values.
+///
+/// The last generic's values change the fastest.
+pub fn combinations(generics: &Vec<Generic>) -> Vec<Combination> {
+    let mut result: Vec<Combination> = vec![Vec::new()];
+    for g in generics {
+        result = result
+            .into_iter()
+            .flat_map(|combo| {
+                g.get_values().iter().map(move |v| {
+                    let mut next = combo.clone();
+                    next.push((g.get_name().clone(), v.clone()));
+                    next
+                })
+            })
+            .collect();
+    }
+    result
+}
+
+/// Formats the `combo` as a list of `NAME=VALUE` entries separated by `sep`.
+pub fn to_string(combo: &Combination, sep: &str) -> String {
+    combo
+        .iter()
+        .map(|(n, v)| format!("{}={}", n, v))
+        .collect::<Vec<String>>()
+        .join(sep)
+}
+
+/// Creates the name of the output directory that keeps the execution of the
+/// `combo` separate from the other combinations of a sweep.
+///
+/// Only the `generics` that are swept appear in the name.
+pub fn to_out_dir(target: &str, generics: &Vec<Generic>, combo: &Combination) -> String {
+    let mut name = target.to_string();
+    for (n, v) in combo {
+        if generics
+            .iter()
+            .any(|g| g.get_name() == n && g.is_sweep() == true)
+            == false
+        {
+            continue;
+        }
+        let value: String = v
+            .chars()
+            .map(
+                |c| match c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+                    true => c,
+                    false => '_',
+                },
+            )
+            .collect();
+        name.push_str(&format!("-{}={}", n, value));
+    }
+    name
+}
+
+/// Calls `run` once for every combination of the `generics`.
+///
+/// When the generics form a sweep, every combination is executed regardless of
+/// failures and a summary of the results is printed at the end.
+pub fn sweep<F>(generics: &Vec<Generic>, target: &str, mut run: F) -> Result<(), Fault>
+where
+    F: FnMut(&Combination) -> Result<(), Fault>,
+{
+    let combos = combinations(generics);
+    if combos.len() == 1 {
+        return run(&combos[0]);
+    }
+
+    let mut results = Vec::with_capacity(combos.len());
+    for (i, combo) in combos.iter().enumerate() {
+        println!(
+            "info: sweeping {} ({}/{})",
+            to_string(combo, " ").blue(),
+            i + 1,
+            combos.len()
+        );
+        let result = run(combo);
+        if let Err(e) = &result {
+            eprintln!("{}: {}", "error".red().bold(), e);
+        }
+        results.push(result.is_ok());
+    }
+
+    println!("info: sweep summary:");
+    for (combo, passed) in combos.iter().zip(&results) {
+        println!(
+            "    {}  {}  {}",
+            match passed {
+                true => "passed".green(),
+                false => "failed".red(),
+            },
+            to_string(combo, " "),
+            to_out_dir(target, generics, combo)
+        );
+    }
+    match results.iter().filter(|r| **r == false).count() {
+        0 => Ok(()),
+        n => Err(AnyError(format!(
+            "{} of {} sweep runs failed",
+            n,
+            combos.len()
+        )))?,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_str() {
+        let g = Generic::from_str("WIDTH=8").unwrap();
+        assert_eq!(g.get_name(), "WIDTH");
+        assert_eq!(g.get_values(), &vec![String::from("8")]);
+        assert_eq!(g.is_sweep(), false);
+
+        let g = Generic::from_str("WIDTH=8, 16,32").unwrap();
+        assert_eq!(g.get_values(), &vec!["8", "16", "32"]);
+        assert_eq!(g.is_sweep(), true);
+
+        assert_eq!(Generic::from_str("WIDTH").is_err(), true);
+        assert_eq!(Generic::from_str("=8").is_err(), true);
+        assert_eq!(Generic::from_str("WIDTH=8,").is_err(), true);
+    }
+
+    #[test]
+    fn sweep_combinations() {
+        let generics = vec![
+            Generic::from_str("WIDTH=8,16").unwrap(),
+            Generic::from_str("SIGNED=true").unwrap(),
+            Generic::from_str("DEPTH=2,4").unwrap(),
+        ];
+        let combos = combinations(&generics);
+        assert_eq!(
+            combos
+                .iter()
+                .map(|c| to_string(c, " "))
+                .collect::<Vec<String>>(),
+            vec![
+                "WIDTH=8 SIGNED=true DEPTH=2",
+                "WIDTH=8 SIGNED=true DEPTH=4",
+                "WIDTH=16 SIGNED=true DEPTH=2",
+                "WIDTH=16 SIGNED=true DEPTH=4",
+            ]
+        );
+        assert_eq!(
+            to_out_dir("sim", &generics, &combos[1]),
+            "sim-WIDTH=8-DEPTH=4"
+        );
+        assert_eq!(combinations(&Vec::new()), vec![Vec::new()]);
+    }
+}
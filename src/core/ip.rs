@@ -25,9 +25,12 @@ use std::path::PathBuf;
 
 use super::catalog::Catalog;
 use super::catalog::PkgName;
+use super::fileset;
 use super::iparchive::IpArchive;
 use super::ippointer::IpPointer;
 use super::lang;
+use super::lang::verilog::libmap::LibraryMap;
+use super::lang::Lang;
 use super::lang::LangIdentifier;
 use super::lang::LangUnit;
 use super::lockfile::LockFile;
@@ -45,6 +48,7 @@ use crate::error::Error;
 use crate::error::Hint;
 use crate::util::sha256::Sha256Hash;
 use colored::Colorize;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::str::FromStr;
@@ -101,6 +105,158 @@ use serde_derive::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize)]
 pub struct Metadata {
     protected: Vec<String>,
+    #[serde(default)]
+    units: Vec<UnitEntry>,
+}
+
+impl Metadata {
+    pub fn get_units(&self) -> &Vec<UnitEntry> {
+        &self.units
+    }
+}
+
+/// A summary of a primary design unit that is recorded in an ip's metadata.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct UnitEntry {
+    name: String,
+    language: String,
+    kind: String,
+    visibility: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    interface: Option<UnitInterface>,
+}
+
+impl From<&LangUnit> for UnitEntry {
+    fn from(value: &LangUnit) -> Self {
+        // the interface is read from the same data as `orbit get --json`
+        let interface = match value.get_lang() {
+            Lang::Vhdl => value
+                .get_vhdl_symbol()
+                .and_then(|s| s.as_entity())
+                .and_then(|e| serde_json::to_value(e).ok()),
+            Lang::Verilog => value
+                .get_verilog_symbol()
+                .and_then(|s| s.as_module())
+                .and_then(|m| serde_json::to_value(m).ok()),
+            Lang::SystemVerilog => value
+                .get_systemverilog_symbol()
+                .and_then(|s| s.as_module())
+                .and_then(|m| serde_json::to_value(m).ok()),
+        }
+        .and_then(|v| serde_json::from_value(v).ok());
+        Self {
+            name: value.get_name().to_string(),
+            language: value.get_lang().to_string(),
+            kind: value.to_string(),
+            visibility: value.get_visibility().to_string(),
+            interface: interface,
+        }
+    }
+}
+
+impl UnitEntry {
+    pub fn new(
+        name: &str,
+        language: &str,
+        kind: &str,
+        visibility: &str,
+        interface: Option<UnitInterface>,
+    ) -> Self {
+        Self {
+            name: name.to_string(),
+            language: language.to_string(),
+            kind: kind.to_string(),
+            visibility: visibility.to_string(),
+            interface: interface,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn get_kind(&self) -> &str {
+        &self.kind
+    }
+
+    pub fn get_visibility(&self) -> &str {
+        &self.visibility
+    }
+
+    /// Returns the generics and ports of the unit, if it is an entity or module.
+    pub fn get_interface(&self) -> Option<&UnitInterface> {
+        self.interface.as_ref()
+    }
+}
+
+/// The generics and ports declared by an entity or module.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Default)]
+pub struct UnitInterface {
+    #[serde(default)]
+    generics: Vec<InterfaceItem>,
+    #[serde(default)]
+    ports: Vec<InterfaceItem>,
+}
+
+impl UnitInterface {
+    pub fn new(generics: Vec<InterfaceItem>, ports: Vec<InterfaceItem>) -> Self {
+        Self {
+            generics: generics,
+            ports: ports,
+        }
+    }
+
+    pub fn get_generics(&self) -> &Vec<InterfaceItem> {
+        &self.generics
+    }
+
+    pub fn get_ports(&self) -> &Vec<InterfaceItem> {
+        &self.ports
+    }
+}
+
+/// A single generic or port declaration on a unit's interface.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct InterfaceItem {
+    #[serde(rename = "identifier")]
+    name: String,
+    #[serde(default)]
+    mode: Option<String>,
+    #[serde(rename = "type", default)]
+    ty: Option<String>,
+    #[serde(default)]
+    default: Option<String>,
+}
+
+impl InterfaceItem {
+    pub fn new(name: &str, mode: Option<&str>, ty: Option<&str>, default: Option<&str>) -> Self {
+        Self {
+            name: name.to_string(),
+            mode: mode.map(|s| s.to_string()),
+            ty: ty.map(|s| s.to_string()),
+            default: default.map(|s| s.to_string()),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_mode(&self) -> Option<&String> {
+        self.mode.as_ref()
+    }
+
+    pub fn get_type(&self) -> Option<&String> {
+        self.ty.as_ref()
+    }
+
+    pub fn has_default(&self) -> bool {
+        self.default.is_some()
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -525,6 +681,12 @@ impl Ip {
     }
 
     pub fn write_cache_metadata(&self) -> Result<(), Fault> {
+        self.write_metadata(self.get_root())
+    }
+
+    /// Writes the ip's metadata, which includes its list of non-private units,
+    /// into the directory `dir`.
+    pub fn write_metadata(&self, dir: &PathBuf) -> Result<(), Fault> {
         // generate the unit map
         let umap = self.collect_units(false, true)?;
         let protected: Vec<String> = umap
@@ -538,16 +700,49 @@ impl Ip {
             })
             .collect();
 
+        let mut units: Vec<UnitEntry> = umap.values().map(|u| UnitEntry::from(u)).collect();
+        units.sort_by(|a, b| a.name.cmp(&b.name));
+
         let meta = Metadata {
             protected: protected,
+            units: units,
         };
 
         let serialized = serde_json::to_string(&meta)?;
-        let path = self.get_root().join(manifest::ORBIT_METADATA_FILE);
+        let path = dir.join(manifest::ORBIT_METADATA_FILE);
         std::fs::write(&path, serialized)?;
         Ok(())
     }
 
+    /// Lists the non-private primary design units of the ip.
+    ///
+    /// Installed ips use the units recorded in their metadata when available,
+    /// and downloaded ips are temporarily extracted to read their source files.
+    /// Returns `None` for ips that only exist as a pointer in a channel.
+    pub fn list_units(&self) -> Result<Option<Vec<UnitEntry>>, Fault> {
+        let units = match self.get_mapping() {
+            Mapping::Physical | Mapping::Relative(_) => {
+                match Self::read_cache_metadata(self.get_root()) {
+                    // metadata written by older versions does not hold any interfaces
+                    Some(meta) if meta.units.iter().any(|u| u.interface.is_some()) == true => {
+                        return Ok(Some(meta.units))
+                    }
+                    _ => self.collect_units(false, true)?,
+                }
+            }
+            Mapping::Virtual(bytes) => {
+                let dir = tempfile::tempdir()?;
+                IpArchive::extract(&bytes, dir.path())?;
+                let ip = Ip::load(dir.path().to_path_buf(), false, false)?;
+                ip.collect_units(true, true)?
+            }
+            Mapping::Imaginary => return Ok(None),
+        };
+        let mut units: Vec<UnitEntry> = units.values().map(|u| UnitEntry::from(u)).collect();
+        units.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Some(units))
+    }
+
     /// Caches the result of collecting all the primary design units for the given package.
     ///
     /// Writes the data to the toml data structure. Note, this function does not save the manifest data to file.
@@ -568,6 +763,38 @@ impl Ip {
     //     tbl["units"].as_array_mut().unwrap().set_trailing("\n");
     // }
 
+    /// Collects the primary design units from the `files`.
+    ///
+    /// Verilog files assigned to other libraries by a library map are collected
+    /// separately for each library, so units only need unique names within their
+    /// own library. When a name is defined in more than one library, the unit from
+    /// the ip's library is kept, followed by the first library in name order.
+    fn collect_library_units(
+        &self,
+        files: &Vec<String>,
+    ) -> Result<HashMap<LangIdentifier, LangUnit>, CodeFault> {
+        let lib_map = match LibraryMap::load(self.get_root()) {
+            Some(m) => m,
+            None => return lang::collect_units(files),
+        };
+        // divide the files by their library, where `None` is the ip's library
+        let mut groups: BTreeMap<Option<&str>, Vec<String>> = BTreeMap::new();
+        for f in files {
+            let lib = match fileset::is_vhdl(f) {
+                true => None,
+                false => lib_map.lookup(f),
+            };
+            groups.entry(lib).or_default().push(f.clone());
+        }
+        let mut map = HashMap::new();
+        for (_, group) in groups {
+            for (key, unit) in lang::collect_units(&group)? {
+                map.entry(key).or_insert(unit);
+            }
+        }
+        Ok(map)
+    }
+
     /// Gathers the list of primary design units for the current ip.
     ///
     /// If the manifest has an toml entry for `units` and `force` is set to `false`,
@@ -586,7 +813,7 @@ impl Ip {
                 // collect all files
                 let files = self.gather_current_files();
 
-                let mut map = lang::collect_units(&files)?;
+                let mut map = self.collect_library_units(&files)?;
 
                 // work to remove files that are totally private
                 if public_list.exists() == true {
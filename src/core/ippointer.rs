@@ -17,11 +17,14 @@
 
 use super::{
     ip::Ip,
-    manifest::{self, Manifest, IP_MANIFEST_FILE},
+    manifest::{self, Manifest, IP_MANIFEST_FILE, ORBIT_STATUS_FILE},
+    uuid::Uuid,
 };
 use crate::error::LastError;
 use crate::util::anyerror::Fault;
+use crate::warn;
 use crate::{core::manifest::FromFile, error::Error};
+use serde_derive::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 /// The ip pointer stores the manifest for an ip, to be used to grab the ip from another
@@ -29,6 +32,7 @@ use std::path::PathBuf;
 #[derive(Debug, PartialEq)]
 pub struct IpPointer {
     manifest: Manifest,
+    status: PointerStatus,
 }
 
 impl IpPointer {
@@ -45,13 +49,22 @@ impl IpPointer {
             )))?;
         }
         let man = Manifest::from_file(&man_path)?;
-        Ok(Self { manifest: man })
+        let status = PointerStatus::read(&path);
+        Ok(Self {
+            manifest: man,
+            status: status,
+        })
     }
 
-    /// Finds all Manifest files available in the provided path `path`.
+    pub fn get_status(&self) -> &PointerStatus {
+        &self.status
+    }
+
+    /// Finds all Manifest files available in the provided path `path`, along
+    /// with the status of each published version.
     ///
     /// Errors if on filesystem problems.
-    pub fn detect_all(path: &PathBuf) -> Result<Vec<Ip>, Fault> {
+    pub fn detect_all(path: &PathBuf) -> Result<Vec<(Ip, PointerStatus)>, Fault> {
         let mut result = Vec::new();
         // walk the directory
         for mut entry in manifest::find_file(&path, IP_MANIFEST_FILE, false)? {
@@ -59,9 +72,168 @@ impl IpPointer {
             entry.pop();
             result.push({
                 let ptr = IpPointer::read(entry)?;
-                Ip::from(ptr)
+                let status = ptr.get_status().clone();
+                (Ip::from(ptr), status)
             });
         }
         Ok(result)
     }
 }
+
+/// The standing of a published version of an ip within a channel.
+///
+/// A version is yanked to discourage its use, and an ip is deprecated to
+/// discourage the use of all of its versions.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct PointerStatus {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    yanked: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deprecated: Option<String>,
+}
+
+impl PointerStatus {
+    /// Reads the status stored in the pointer directory `dir`.
+    ///
+    /// A missing status file means the version is neither yanked nor deprecated.
+    /// A status file that cannot be read is reported and treated the same way.
+    pub fn read(dir: &PathBuf) -> Self {
+        let path = dir.join(ORBIT_STATUS_FILE);
+        if path.exists() == false {
+            return Self::default();
+        }
+        let result: Result<Self, Fault> = std::fs::read_to_string(&path)
+            .map_err(|e| e.into())
+            .and_then(|text| Ok(serde_json::from_str(&text)?));
+        match result {
+            Ok(status) => status,
+            Err(e) => {
+                warn!("ignoring status file {:?}: {}", path, e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Writes the status into the pointer directory `dir`.
+    ///
+    /// The status file is removed when the version is neither yanked nor deprecated.
+    pub fn write(&self, dir: &PathBuf) -> Result<(), Fault> {
+        let path = dir.join(ORBIT_STATUS_FILE);
+        if self == &Self::default() {
+            if path.exists() == true {
+                std::fs::remove_file(&path)?;
+            }
+        } else {
+            std::fs::write(&path, serde_json::to_string(&self)?)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the reason the version was yanked, if it is yanked.
+    pub fn get_yanked(&self) -> Option<&String> {
+        self.yanked.as_ref()
+    }
+
+    /// Returns the message explaining the ip's deprecation, if it is deprecated.
+    pub fn get_deprecated(&self) -> Option<&String> {
+        self.deprecated.as_ref()
+    }
+
+    pub fn set_yanked(&mut self, reason: Option<String>) {
+        self.yanked = reason;
+    }
+
+    pub fn set_deprecated(&mut self, message: Option<String>) {
+        self.deprecated = message;
+    }
+
+    /// Creates the status for a new version of the ip with `uuid` published to the
+    /// channel at `root`.
+    ///
+    /// A deprecation applies to the entire ip, so it is carried forward from the
+    /// versions already published to the channel.
+    pub fn inherit(root: &PathBuf, uuid: &Uuid) -> Result<Self, Fault> {
+        let deprecated = IpPointer::detect_all(root)?
+            .into_iter()
+            .filter(|(ip, _)| ip.get_uuid() == uuid)
+            .find_map(|(_, status)| status.deprecated);
+        Ok(Self {
+            yanked: None,
+            deprecated: deprecated,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn write_and_read_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        // no status file is treated as a normal version
+        assert_eq!(PointerStatus::read(&root), PointerStatus::default());
+
+        let mut status = PointerStatus::default();
+        status.set_yanked(Some(String::from("reset is stuck high")));
+        status.write(&root).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(root.join(ORBIT_STATUS_FILE)).unwrap(),
+            "{\"yanked\":\"reset is stuck high\"}"
+        );
+        assert_eq!(PointerStatus::read(&root), status);
+
+        // clearing the status removes the file
+        status.set_yanked(None);
+        status.write(&root).unwrap();
+        assert_eq!(root.join(ORBIT_STATUS_FILE).exists(), false);
+
+        // a malformed status file is treated as a normal version
+        std::fs::write(root.join(ORBIT_STATUS_FILE), "{\"yanked\": ").unwrap();
+        assert_eq!(PointerStatus::read(&root), PointerStatus::default());
+    }
+
+    #[test]
+    fn inherit_deprecation() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let uuid = Uuid::nil();
+        let publish = |version: &str, status: &PointerStatus| {
+            let path = root.join(version);
+            std::fs::create_dir_all(&path).unwrap();
+            std::fs::write(
+                path.join(IP_MANIFEST_FILE),
+                format!(
+                    "[ip]\nname = \"uart\"\nversion = \"{}\"\nuuid = \"{}\"\n",
+                    version,
+                    uuid.encode()
+                ),
+            )
+            .unwrap();
+            status.write(&path).unwrap();
+        };
+        // only a yank is kept to its own version
+        let mut status = PointerStatus::default();
+        status.set_yanked(Some(String::from("reset is stuck high")));
+        publish("1.0.0", &status);
+        assert_eq!(
+            PointerStatus::inherit(&root, &uuid).unwrap(),
+            PointerStatus::default()
+        );
+        // a deprecation is carried forward to the next version
+        status.set_deprecated(Some(String::from("use uart2 instead")));
+        publish("1.0.0", &status);
+        let next = PointerStatus::inherit(&root, &uuid).unwrap();
+        assert_eq!(next.get_yanked(), None);
+        assert_eq!(
+            next.get_deprecated(),
+            Some(&String::from("use uart2 instead"))
+        );
+        // a different ip is not affected
+        assert_eq!(
+            PointerStatus::inherit(&root, &Uuid::new()).unwrap(),
+            PointerStatus::default()
+        );
+    }
+}
@@ -0,0 +1,484 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Writes IEEE 1685-2014 (IP-XACT) component descriptions.
+
+use crate::core::fileset;
+use crate::core::lang::sv::token::keyword::Keyword as SvKeyword;
+use crate::core::lang::translate;
+use crate::core::lang::verilog::symbols::module::Module;
+use crate::core::lang::vhdl::symbols::entity::Entity;
+use crate::core::lang::vhdl::token::keyword::Keyword as VhdlKeyword;
+use crate::core::lang::Lang;
+use std::fmt::Display;
+
+const NAMESPACE: &str = "http://www.accellera.org/XMLSchema/IPXACT/1685-2014";
+
+/// The name given to the single view, instantiation, and file set of the component.
+const VIEW_NAME: &str = "rtl";
+
+#[derive(Debug, PartialEq)]
+struct ModelParameter {
+    name: String,
+    data_type: Option<String>,
+    value: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct WirePort {
+    name: String,
+    direction: String,
+    vector: Option<(String, String)>,
+    type_name: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct SourceFile {
+    path: String,
+    library: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Component {
+    vendor: String,
+    library: String,
+    name: String,
+    version: String,
+    description: Option<String>,
+    lang: Lang,
+    hdl_library: String,
+    module_name: String,
+    parameters: Vec<ModelParameter>,
+    ports: Vec<WirePort>,
+    files: Vec<SourceFile>,
+}
+
+impl Component {
+    /// Creates a new component identified by its VLNV.
+    pub fn new(vendor: &str, library: &str, name: &str, version: &str) -> Self {
+        Self {
+            vendor: vendor.to_string(),
+            library: library.to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+            description: None,
+            lang: Lang::Vhdl,
+            hdl_library: String::new(),
+            module_name: String::new(),
+            parameters: Vec::new(),
+            ports: Vec::new(),
+            files: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, text: Option<&String>) -> Self {
+        self.description = text.cloned();
+        self
+    }
+
+    /// Sets the component's model from the interface of a VHDL entity.
+    pub fn entity(mut self, entity: &Entity, hdl_library: &str) -> Self {
+        self.lang = Lang::Vhdl;
+        self.hdl_library = hdl_library.to_string();
+        self.module_name = entity.get_name().to_string();
+        self.parameters = entity
+            .get_generics()
+            .0
+            .iter()
+            .map(|g| {
+                let mark = translate::split_vhdl_type(g.get_type_tokens());
+                ModelParameter {
+                    name: g.get_identifier().to_string(),
+                    data_type: mark.map(|(m, _)| m),
+                    value: g.get_default_tokens().map(|v| {
+                        translate::vhdl_expr_to_verilog(v)
+                            .unwrap_or_else(|| translate::vhdl_tokens_to_string(v))
+                    }),
+                }
+            })
+            .collect();
+        self.ports = entity
+            .get_ports()
+            .0
+            .iter()
+            .map(|p| {
+                let (type_name, range) = match translate::split_vhdl_type(p.get_type_tokens()) {
+                    Some((m, r)) => (Some(m), r),
+                    None => (None, None),
+                };
+                WirePort {
+                    name: p.get_identifier().to_string(),
+                    direction: String::from(match p.get_mode() {
+                        VhdlKeyword::In => "in",
+                        VhdlKeyword::Out | VhdlKeyword::Buffer => "out",
+                        _ => "inout",
+                    }),
+                    vector: range.map(|r| (r.get_left().clone(), r.get_right().clone())),
+                    type_name: type_name,
+                }
+            })
+            .collect();
+        self
+    }
+
+    /// Sets the component's model from the interface of a Verilog or SystemVerilog module.
+    pub fn module(mut self, module: &Module, lang: Lang, hdl_library: &str) -> Self {
+        self.lang = lang;
+        self.hdl_library = hdl_library.to_string();
+        self.module_name = module.get_name().to_string();
+        self.parameters = module
+            .get_parameters()
+            .iter()
+            .filter(|p| p.is_localparam() == false)
+            .map(|p| ModelParameter {
+                name: p.get_name().to_string(),
+                data_type: p.get_data_type().map(|t| t.to_string()),
+                value: p
+                    .get_default()
+                    .map(|v| translate::verilog_tokens_to_string(v)),
+            })
+            .collect();
+        self.ports = module
+            .get_ports()
+            .iter()
+            .map(|p| WirePort {
+                name: p.get_name().to_string(),
+                direction: String::from(match p.get_direction() {
+                    SvKeyword::Input => "in",
+                    SvKeyword::Output => "out",
+                    _ => "inout",
+                }),
+                vector: p
+                    .get_range()
+                    .and_then(|r| translate::split_verilog_range(r))
+                    .map(|(l, r)| {
+                        (
+                            translate::verilog_tokens_to_string(l),
+                            translate::verilog_tokens_to_string(r),
+                        )
+                    }),
+                type_name: p.get_data_type().map(|t| t.to_string()),
+            })
+            .collect();
+        self
+    }
+
+    /// Returns the names of the parameters without a default value, which are
+    /// not written as module parameters.
+    pub fn get_unset_parameters(&self) -> Vec<&String> {
+        self.parameters
+            .iter()
+            .filter(|p| p.value.is_none())
+            .map(|p| &p.name)
+            .collect()
+    }
+
+    /// Adds an hdl source file to the component's file set.
+    pub fn add_file(&mut self, path: &str, library: &str) {
+        self.files.push(SourceFile {
+            path: path.to_string(),
+            library: library.to_string(),
+        });
+    }
+}
+
+impl Display for Component {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut x = XmlWriter::new();
+        x.line(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        x.open_with(
+            "component",
+            &format!(
+                r#"xmlns:ipxact="{0}" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:schemaLocation="{0} {0}/index.xsd""#,
+                NAMESPACE
+            ),
+        );
+        x.element("vendor", &self.vendor);
+        x.element("library", &self.library);
+        x.element("name", &self.name);
+        x.element("version", &self.version);
+
+        x.open("model");
+        x.open("views");
+        x.open("view");
+        x.element("name", VIEW_NAME);
+        x.element("componentInstantiationRef", VIEW_NAME);
+        x.close("view");
+        x.close("views");
+
+        x.open("instantiations");
+        x.open("componentInstantiation");
+        x.element("name", VIEW_NAME);
+        x.element("language", &self.lang.to_string());
+        x.element("libraryName", &self.hdl_library);
+        x.element("moduleName", &self.module_name);
+        // a module parameter requires a value, so those without a default are left out
+        let parameters: Vec<&ModelParameter> = self
+            .parameters
+            .iter()
+            .filter(|p| p.value.is_some())
+            .collect();
+        if parameters.is_empty() == false {
+            x.open("moduleParameters");
+            for p in parameters {
+                let mut attrs = format!(r#"parameterId="{}" resolve="user""#, escape(&p.name));
+                if let Some(t) = &p.data_type {
+                    attrs.push_str(&format!(r#" dataType="{}""#, escape(t)));
+                }
+                x.open_with("moduleParameter", &attrs);
+                x.element("name", &p.name);
+                x.element("value", p.value.as_ref().unwrap());
+                x.close("moduleParameter");
+            }
+            x.close("moduleParameters");
+        }
+        x.open("fileSetRef");
+        x.element("localName", VIEW_NAME);
+        x.close("fileSetRef");
+        x.close("componentInstantiation");
+        x.close("instantiations");
+
+        if self.ports.is_empty() == false {
+            x.open("ports");
+            for p in &self.ports {
+                x.open("port");
+                x.element("name", &p.name);
+                x.open("wire");
+                x.element("direction", &p.direction);
+                if let Some((left, right)) = &p.vector {
+                    x.open("vectors");
+                    x.open("vector");
+                    x.element("left", left);
+                    x.element("right", right);
+                    x.close("vector");
+                    x.close("vectors");
+                }
+                if let Some(t) = &p.type_name {
+                    x.open("wireTypeDefs");
+                    x.open("wireTypeDef");
+                    x.element("typeName", t);
+                    x.element("viewRef", VIEW_NAME);
+                    x.close("wireTypeDef");
+                    x.close("wireTypeDefs");
+                }
+                x.close("wire");
+                x.close("port");
+            }
+            x.close("ports");
+        }
+        x.close("model");
+
+        x.open("fileSets");
+        x.open("fileSet");
+        x.element("name", VIEW_NAME);
+        for file in &self.files {
+            x.open("file");
+            x.element("name", &file.path);
+            x.element("fileType", file_type(&file.path));
+            x.element("logicalName", &file.library);
+            x.close("file");
+        }
+        x.close("fileSet");
+        x.close("fileSets");
+
+        if let Some(d) = &self.description {
+            x.element("description", d);
+        }
+        x.close("component");
+        write!(f, "{}", x.into_string())
+    }
+}
+
+/// Determines the IP-XACT file type for an hdl source file.
+fn file_type(path: &str) -> &'static str {
+    if fileset::is_vhdl(path) == true {
+        "vhdlSource"
+    } else if fileset::is_verilog(path) == true {
+        "verilogSource"
+    } else if fileset::is_systemverilog(path) == true {
+        "systemVerilogSource"
+    } else {
+        "unknown"
+    }
+}
+
+/// Replaces the characters that cannot appear in XML text or attributes.
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Writes indented XML elements under the `ipxact` namespace.
+struct XmlWriter {
+    text: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    fn new() -> Self {
+        Self {
+            text: String::new(),
+            depth: 0,
+        }
+    }
+
+    fn line(&mut self, s: &str) {
+        self.text.push_str(&"  ".repeat(self.depth));
+        self.text.push_str(s);
+        self.text.push('\n');
+    }
+
+    fn open(&mut self, tag: &str) {
+        self.line(&format!("<ipxact:{}>", tag));
+        self.depth += 1;
+    }
+
+    fn open_with(&mut self, tag: &str, attrs: &str) {
+        self.line(&format!("<ipxact:{} {}>", tag, attrs));
+        self.depth += 1;
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.line(&format!("</ipxact:{}>", tag));
+    }
+
+    fn element(&mut self, tag: &str, value: &str) {
+        self.line(&format!("<ipxact:{0}>{1}</ipxact:{0}>", tag, escape(value)));
+    }
+
+    fn into_string(self) -> String {
+        self.text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::lang::sv::symbols::SystemVerilogParser;
+    use crate::core::lang::vhdl::symbols::VHDLParser;
+
+    #[test]
+    fn escape_text() {
+        assert_eq!(escape("a < b && c"), "a &lt; b &amp;&amp; c");
+        assert_eq!(escape("\"fast\""), "&quot;fast&quot;");
+    }
+
+    #[test]
+    fn from_entity() {
+        let entity = VHDLParser::read(
+            r#"entity fifo is
+    generic ( WIDTH : positive := 8 );
+    port (
+        clk : in std_logic;
+        data : out std_logic_vector(WIDTH-1 downto 0)
+    );
+end entity;"#,
+        )
+        .unwrap()
+        .into_symbols()
+        .into_iter()
+        .find_map(|s| s.into_entity())
+        .unwrap();
+        let mut comp = Component::new("orbit", "mem", "fifo", "1.0.0").entity(&entity, "mem");
+        comp.add_file("rtl/fifo.vhd", "mem");
+        assert_eq!(
+            comp.parameters,
+            vec![ModelParameter {
+                name: String::from("WIDTH"),
+                data_type: Some(String::from("positive")),
+                value: Some(String::from("8")),
+            }]
+        );
+        assert_eq!(
+            comp.ports[1],
+            WirePort {
+                name: String::from("data"),
+                direction: String::from("out"),
+                vector: Some((String::from("WIDTH - 1"), String::from("0"))),
+                type_name: Some(String::from("std_logic_vector")),
+            }
+        );
+        let xml = comp.to_string();
+        assert!(xml.contains("<ipxact:language>vhdl</ipxact:language>"));
+        assert!(xml.contains("<ipxact:fileType>vhdlSource</ipxact:fileType>"));
+        assert!(xml.ends_with("</ipxact:component>\n"));
+    }
+
+    #[test]
+    fn from_module() {
+        let module = SystemVerilogParser::read(
+            r#"module adder #(parameter int WIDTH = 8) (
+    input logic [WIDTH-1:0] a,
+    output logic [WIDTH:0] sum
+);
+endmodule"#,
+        )
+        .unwrap()
+        .into_symbols()
+        .into_iter()
+        .find_map(|s| s.into_module())
+        .unwrap();
+        let comp = Component::new("orbit", "math", "adder", "0.1.0").module(
+            &module,
+            Lang::SystemVerilog,
+            "math",
+        );
+        assert_eq!(comp.parameters[0].data_type, Some(String::from("int")));
+        assert_eq!(
+            comp.ports[0].vector,
+            Some((String::from("WIDTH-1"), String::from("0")))
+        );
+        assert_eq!(comp.ports[1].direction, String::from("out"));
+    }
+
+    #[test]
+    fn skip_parameter_without_default() {
+        let entity = VHDLParser::read(
+            r#"entity fifo is
+    generic ( DEPTH : positive; WIDTH : positive := 8 );
+    port ( clk : in std_logic );
+end entity;"#,
+        )
+        .unwrap()
+        .into_symbols()
+        .into_iter()
+        .find_map(|s| s.into_entity())
+        .unwrap();
+        let comp = Component::new("orbit", "mem", "fifo", "1.0.0").entity(&entity, "mem");
+        assert_eq!(comp.get_unset_parameters(), vec!["DEPTH"]);
+        let xml = comp.to_string();
+        assert_eq!(xml.contains("<ipxact:name>DEPTH</ipxact:name>"), false);
+        assert_eq!(xml.contains("<ipxact:value>8</ipxact:value>"), true);
+        assert_eq!(xml.contains("<ipxact:value></ipxact:value>"), false);
+
+        // no module parameters are written when none have a default
+        let entity = VHDLParser::read(
+            "entity fifo is generic ( DEPTH : positive ); port ( clk : in std_logic ); end entity;",
+        )
+        .unwrap()
+        .into_symbols()
+        .into_iter()
+        .find_map(|s| s.into_entity())
+        .unwrap();
+        let comp = Component::new("orbit", "mem", "fifo", "1.0.0").entity(&entity, "mem");
+        assert_eq!(comp.to_string().contains("moduleParameters"), false);
+    }
+}
@@ -0,0 +1,256 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::lexer::Position;
+use std::collections::{HashMap, HashSet};
+
+/// A column on a line that is aligned with the anchors of the other lines in
+/// the same group.
+///
+/// The whitespace following the first `mark` characters at the column is
+/// collapsed into a single space.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Anchor {
+    group: usize,
+    col: usize,
+    mark: usize,
+    offset: u8,
+}
+
+impl Anchor {
+    pub fn new(group: usize, col: usize, mark: usize, offset: u8) -> Self {
+        Self {
+            group,
+            col,
+            mark,
+            offset,
+        }
+    }
+}
+
+/// How a line of source code is written back.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Layout {
+    /// The line is kept exactly as it is written.
+    Verbatim,
+    /// The line's leading whitespace is replaced with the indentation depth.
+    Indent(usize, Option<Anchor>),
+}
+
+/// Rewrites source code line by line according to the layout determined for
+/// each line.
+///
+/// Lines without a layout are blank or contain only whitespace.
+#[derive(Debug, PartialEq)]
+pub struct SourceFormatter {
+    layouts: HashMap<usize, Layout>,
+    edits: HashMap<usize, Vec<(usize, String)>>,
+    trailing: HashSet<usize>,
+}
+
+impl SourceFormatter {
+    pub fn new() -> Self {
+        Self {
+            layouts: HashMap::new(),
+            edits: HashMap::new(),
+            trailing: HashSet::new(),
+        }
+    }
+
+    /// Sets the indentation `depth` of `line`.
+    pub fn indent(&mut self, line: usize, depth: usize) {
+        self.layouts.insert(line, Layout::Indent(depth, None));
+    }
+
+    /// Keeps `line` exactly as it is written.
+    pub fn keep(&mut self, line: usize) {
+        self.layouts.insert(line, Layout::Verbatim);
+    }
+
+    /// Keeps the trailing whitespace of `line`, such as when it ends with a comment.
+    pub fn keep_trailing(&mut self, line: usize) {
+        self.trailing.insert(line);
+    }
+
+    /// Aligns the column of the token at `pos` with the other anchors of `group`.
+    ///
+    /// A `mark` length of 0 keeps the spacing after the token as it is written.
+    /// The line must already have an indentation set.
+    pub fn align(&mut self, pos: &Position, group: usize, mark: usize, offset: u8) {
+        if let Some(Layout::Indent(_, anchor)) = self.layouts.get_mut(&pos.line()) {
+            *anchor = Some(Anchor::new(group, pos.col(), mark, offset));
+        }
+    }
+
+    /// Replaces the characters of the token at `pos` with `text` of the same length.
+    pub fn replace(&mut self, pos: &Position, text: String) {
+        self.edits
+            .entry(pos.line())
+            .or_insert(Vec::new())
+            .push((pos.col(), text));
+    }
+
+    /// Applies the edits to the line of `text` numbered `line`.
+    fn edit(&self, line: usize, text: &str) -> Vec<char> {
+        let mut chars: Vec<char> = text.chars().collect();
+        if let Some(edits) = self.edits.get(&line) {
+            for (col, word) in edits {
+                for (i, c) in word.chars().enumerate() {
+                    if let Some(x) = chars.get_mut(col - 1 + i) {
+                        *x = c;
+                    }
+                }
+            }
+        }
+        chars
+    }
+
+    /// Splits the line's content at the anchor into its trimmed left and right sides.
+    fn split(&self, line: usize, chars: &Vec<char>, anchor: &Anchor) -> Option<(String, String)> {
+        if anchor.col == 0 || anchor.col > chars.len() {
+            return None;
+        }
+        let left: String = chars[..anchor.col - 1].iter().collect();
+        let right: String = match chars.get(anchor.col - 1 + anchor.mark..) {
+            Some(rest) if anchor.mark > 0 => {
+                let mark: String = chars[anchor.col - 1..anchor.col - 1 + anchor.mark]
+                    .iter()
+                    .collect();
+                let rest: String = rest.iter().collect();
+                // the end of the line is trimmed later unless it is kept
+                match rest.trim().is_empty() {
+                    true => mark,
+                    false => format!("{} {}", mark, rest.trim_start()),
+                }
+            }
+            _ => chars[anchor.col - 1..].iter().collect::<String>(),
+        };
+        match left.trim().is_empty() {
+            true => None,
+            false => Some((
+                left.trim().to_string(),
+                self.trim_end(line, &right).to_string(),
+            )),
+        }
+    }
+
+    /// Removes the trailing whitespace of the `line` of `text` unless it is kept.
+    fn trim_end<'a>(&self, line: usize, text: &'a str) -> &'a str {
+        match self.trailing.contains(&line) {
+            true => text,
+            false => text.trim_end(),
+        }
+    }
+
+    /// Writes the formatted `source` code using `tab_size` spaces per indentation.
+    pub fn apply(&self, source: &str, tab_size: u8) -> String {
+        let lines: Vec<&str> = source.split('\n').collect();
+
+        // determine the widest left side of every group of anchors
+        let mut widths: HashMap<usize, usize> = HashMap::new();
+        for (i, text) in lines.iter().enumerate() {
+            if let Some(Layout::Indent(_, Some(anchor))) = self.layouts.get(&(i + 1)) {
+                if let Some((left, _)) = self.split(i + 1, &self.edit(i + 1, text), anchor) {
+                    let width = widths.entry(anchor.group).or_insert(0);
+                    *width = (*width).max(left.chars().count());
+                }
+            }
+        }
+
+        let mut result = Vec::with_capacity(lines.len());
+        for (i, text) in lines.iter().enumerate() {
+            // preserve the line's carriage return
+            let (text, cr) = match text.strip_suffix('\r') {
+                Some(t) => (t, "\r"),
+                None => (*text, ""),
+            };
+            let line = match self.layouts.get(&(i + 1)) {
+                Some(Layout::Verbatim) => text.to_string(),
+                Some(Layout::Indent(depth, anchor)) => {
+                    let chars = self.edit(i + 1, text);
+                    let indent = " ".repeat(depth * tab_size as usize);
+                    match anchor
+                        .as_ref()
+                        .and_then(|a| Some((a, self.split(i + 1, &chars, a)?)))
+                    {
+                        Some((a, (left, right))) => format!(
+                            "{}{}{}{}",
+                            indent,
+                            left,
+                            " ".repeat(widths[&a.group] - left.chars().count() + a.offset as usize),
+                            right
+                        ),
+                        None => {
+                            let content: String = chars.into_iter().collect();
+                            match content.trim().is_empty() {
+                                true => String::new(),
+                                false => format!(
+                                    "{}{}",
+                                    indent,
+                                    self.trim_end(i + 1, content.trim_start())
+                                ),
+                            }
+                        }
+                    }
+                }
+                None => String::new(),
+            };
+            result.push(format!("{}{}", line, cr));
+        }
+        result.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn apply_layouts() {
+        let code = "a: x  \n\n  bb:y\n /* keep\n  this */\n\tc;\n -- d  ";
+        let mut fmt = SourceFormatter::new();
+        fmt.indent(1, 1);
+        fmt.align(&Position::place(1, 2), 0, 1, 1);
+        fmt.indent(3, 1);
+        fmt.align(&Position::place(3, 5), 0, 1, 1);
+        fmt.replace(&Position::place(3, 6), String::from("Y"));
+        fmt.indent(4, 0);
+        fmt.keep(5);
+        fmt.indent(6, 2);
+        fmt.indent(7, 0);
+        fmt.keep_trailing(7);
+        assert_eq!(
+            fmt.apply(code, 2),
+            "  a  : x\n\n  bb : Y\n/* keep\n  this */\n    c;\n-- d  "
+        );
+    }
+
+    #[test]
+    fn align_keeps_trailing_comment() {
+        let code = "a :  in bit;  -- trailing   \nbb : out bit;  ";
+        let mut fmt = SourceFormatter::new();
+        fmt.indent(1, 1);
+        fmt.align(&Position::place(1, 3), 0, 1, 1);
+        fmt.keep_trailing(1);
+        fmt.indent(2, 1);
+        fmt.align(&Position::place(2, 4), 0, 1, 1);
+        assert_eq!(
+            fmt.apply(code, 2),
+            "  a  : in bit;  -- trailing   \n  bb : out bit;"
+        );
+    }
+}
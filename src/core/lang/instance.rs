@@ -0,0 +1,441 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::LangIdentifier;
+use std::fmt::Display;
+
+/// The largest number of iterations a loop scope elaborates into separate blocks.
+const MAX_LOOP_INDICES: u64 = 256;
+
+/// A generic (VHDL) or parameter (Verilog) association written at an instantiation.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Association {
+    formal: Option<String>,
+    actual: String,
+}
+
+impl Association {
+    pub fn new(formal: Option<String>, actual: String) -> Self {
+        Self { formal, actual }
+    }
+
+    pub fn get_formal(&self) -> Option<&String> {
+        self.formal.as_ref()
+    }
+
+    pub fn get_actual(&self) -> &String {
+        &self.actual
+    }
+}
+
+impl Display for Association {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.formal {
+            Some(name) => write!(f, "{}={}", name, self.actual),
+            None => write!(f, "{}", self.actual),
+        }
+    }
+}
+
+/// The direction of data through a port.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Direction {
+    In,
+    Out,
+    InOut,
+    Other,
+}
+
+/// A port on the interface of a design unit.
+#[derive(Debug, PartialEq, Clone)]
+pub struct PortSummary {
+    name: String,
+    dir: Direction,
+    has_default: bool,
+}
+
+impl PortSummary {
+    pub fn new(name: String, dir: Direction, has_default: bool) -> Self {
+        Self {
+            name,
+            dir,
+            has_default,
+        }
+    }
+}
+
+/// The generics and ports of a design unit that its instances connect to.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Interface {
+    generics: Vec<String>,
+    ports: Vec<PortSummary>,
+    case_sensitive: bool,
+}
+
+impl Interface {
+    pub fn new(generics: Vec<String>, ports: Vec<PortSummary>, case_sensitive: bool) -> Self {
+        Self {
+            generics,
+            ports,
+            case_sensitive,
+        }
+    }
+
+    fn is_match(&self, a: &str, b: &str) -> bool {
+        match self.case_sensitive {
+            true => a == b,
+            false => a.eq_ignore_ascii_case(b),
+        }
+    }
+
+    fn has_generic(&self, name: &str) -> bool {
+        self.generics.iter().any(|g| self.is_match(g, name))
+    }
+
+    fn find_port(&self, name: &str) -> Option<usize> {
+        self.ports.iter().position(|p| self.is_match(&p.name, name))
+    }
+}
+
+/// A problem with how an instance connects to the interface of its unit.
+#[derive(Debug, PartialEq)]
+pub enum ConnectionIssue {
+    UnknownGeneric(String),
+    UnknownPort(String),
+    MissingInput(String),
+    DrivesInput(String, String),
+}
+
+impl Display for ConnectionIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownGeneric(g) => write!(f, "sets unknown generic {:?}", g),
+            Self::UnknownPort(p) => write!(f, "connects unknown port {:?}", p),
+            Self::MissingInput(p) => write!(
+                f,
+                "leaves input port {:?} unconnected without a default value",
+                p
+            ),
+            Self::DrivesInput(p, a) => write!(
+                f,
+                "drives input port {:?} of its enclosing unit from port {:?}",
+                a, p
+            ),
+        }
+    }
+}
+
+/// A labeled generate block that encloses an instantiation.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Scope {
+    label: String,
+    kind: ScopeKind,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ScopeKind {
+    /// A conditional (`if`/`case`) generate block.
+    Branch,
+    /// A loop generate block, with its indices if its bounds are literals.
+    Loop(Option<Vec<i64>>),
+}
+
+impl Scope {
+    pub fn new(label: String, kind: ScopeKind) -> Self {
+        Self { label, kind }
+    }
+
+    /// Creates a loop scope iterating from `left` to `right` (inclusive) when
+    /// both bounds are integer literals.
+    ///
+    /// Loops with more than `MAX_LOOP_INDICES` iterations are treated as if their
+    /// bounds are unknown.
+    pub fn with_bounds(label: String, left: Option<i64>, right: Option<i64>) -> Self {
+        let indices = match (left, right) {
+            (Some(l), Some(r)) if l.abs_diff(r) >= MAX_LOOP_INDICES => None,
+            (Some(l), Some(r)) if l <= r => Some((l..=r).collect()),
+            (Some(l), Some(r)) => Some((r..=l).rev().collect()),
+            _ => None,
+        };
+        Self::new(label, ScopeKind::Loop(indices))
+    }
+
+    pub fn get_label(&self) -> &String {
+        &self.label
+    }
+
+    pub fn get_kind(&self) -> &ScopeKind {
+        &self.kind
+    }
+
+    /// Returns the names of every block this scope elaborates into.
+    ///
+    /// Loops with unknown bounds are represented by a single `[*]` block.
+    pub fn expand(&self) -> Vec<String> {
+        match &self.kind {
+            ScopeKind::Branch => vec![self.label.clone()],
+            ScopeKind::Loop(Some(indices)) => indices
+                .iter()
+                .map(|i| format!("{}[{}]", self.label, i))
+                .collect(),
+            ScopeKind::Loop(None) => vec![format!("{}[*]", self.label)],
+        }
+    }
+}
+
+/// An instantiation of a design unit within an architecture or module.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Instance<I> {
+    name: I,
+    unit: I,
+    lib: Option<I>,
+    generics: Vec<Association>,
+    ports: Vec<Association>,
+    scopes: Vec<Scope>,
+}
+
+impl<I> Instance<I> {
+    pub fn new(unit: I, name: I) -> Self {
+        Self {
+            name: name,
+            unit: unit,
+            lib: None,
+            generics: Vec::new(),
+            ports: Vec::new(),
+            scopes: Vec::new(),
+        }
+    }
+
+    pub fn library(mut self, lib: Option<I>) -> Self {
+        self.lib = lib;
+        self
+    }
+
+    pub fn generics(mut self, generics: Vec<Association>) -> Self {
+        self.generics = generics;
+        self
+    }
+
+    pub fn ports(mut self, ports: Vec<Association>) -> Self {
+        self.ports = ports;
+        self
+    }
+
+    pub fn scopes(mut self, scopes: Vec<Scope>) -> Self {
+        self.scopes = scopes;
+        self
+    }
+
+    /// Places the instance within the outer `scopes`.
+    pub fn enclose(&mut self, scopes: &[Scope]) {
+        self.scopes.splice(0..0, scopes.iter().cloned());
+    }
+
+    /// Accesses the instance label.
+    pub fn get_name(&self) -> &I {
+        &self.name
+    }
+
+    /// Accesses the name of the instantiated design unit.
+    pub fn get_unit(&self) -> &I {
+        &self.unit
+    }
+
+    /// Accesses the library explicitly given for the design unit, if any.
+    pub fn get_library(&self) -> Option<&I> {
+        self.lib.as_ref()
+    }
+
+    pub fn get_generics(&self) -> &Vec<Association> {
+        &self.generics
+    }
+
+    pub fn get_ports(&self) -> &Vec<Association> {
+        &self.ports
+    }
+
+    /// Accesses the generate blocks enclosing the instance, from outermost to innermost.
+    pub fn get_scopes(&self) -> &Vec<Scope> {
+        &self.scopes
+    }
+}
+
+impl<I> Instance<I> {
+    /// Checks the generic and port associations against the `unit`'s interface.
+    ///
+    /// The interface of the `parent` unit holding the instance is used to find
+    /// outputs that drive the parent's input ports.
+    pub fn check(&self, unit: &Interface, parent: Option<&Interface>) -> Vec<ConnectionIssue> {
+        // the formal's name without any element or slice selection
+        let base = |f: &String| f.split('(').next().unwrap().trim().to_string();
+        let mut issues = Vec::new();
+
+        for formal in self.generics.iter().filter_map(|g| g.get_formal()) {
+            if formal.contains('(') == false && unit.has_generic(formal) == false {
+                issues.push(ConnectionIssue::UnknownGeneric(formal.clone()));
+            }
+        }
+
+        let mut connected = vec![false; unit.ports.len()];
+        let mut wildcard = false;
+        for (i, assoc) in self.ports.iter().enumerate() {
+            let index = match assoc.get_formal() {
+                Some(f) if f == "*" => {
+                    wildcard = true;
+                    continue;
+                }
+                Some(f) => match unit.find_port(&base(f)) {
+                    Some(j) => j,
+                    None => {
+                        // skip formals that may be conversion functions
+                        if f.contains('(') == false {
+                            issues.push(ConnectionIssue::UnknownPort(f.clone()));
+                        }
+                        continue;
+                    }
+                },
+                None => match i < unit.ports.len() {
+                    true => i,
+                    false => continue,
+                },
+            };
+            let actual = assoc.get_actual();
+            if actual.is_empty() == true || actual.eq_ignore_ascii_case("open") == true {
+                continue;
+            }
+            connected[index] = true;
+            // an output cannot drive an input port of the enclosing unit
+            let port = &unit.ports[index];
+            if port.dir == Direction::Out || port.dir == Direction::InOut {
+                if let Some(parent) = parent {
+                    if let Some(j) = parent.find_port(actual) {
+                        if parent.ports[j].dir == Direction::In {
+                            issues.push(ConnectionIssue::DrivesInput(
+                                port.name.clone(),
+                                parent.ports[j].name.clone(),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        if wildcard == false {
+            unit.ports
+                .iter()
+                .zip(connected)
+                .filter(|(p, c)| p.dir == Direction::In && p.has_default == false && *c == false)
+                .for_each(|(p, _)| issues.push(ConnectionIssue::MissingInput(p.name.clone())));
+        }
+        issues
+    }
+}
+
+impl<I: Clone + Into<LangIdentifier>> Instance<I> {
+    /// Converts the identifiers into language-agnostic identifiers.
+    pub fn to_lang(&self) -> Instance<LangIdentifier> {
+        Instance {
+            name: self.name.clone().into(),
+            unit: self.unit.clone().into(),
+            lib: self.lib.clone().map(|l| l.into()),
+            generics: self.generics.clone(),
+            ports: self.ports.clone(),
+            scopes: self.scopes.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn check_connections() {
+        let port =
+            |n: &str, d: Direction, default: bool| PortSummary::new(n.to_string(), d, default);
+        let unit = Interface::new(
+            vec![String::from("WIDTH")],
+            vec![
+                port("clk", Direction::In, false),
+                port("rst", Direction::In, false),
+                port("en", Direction::In, true),
+                port("q", Direction::Out, false),
+            ],
+            false,
+        );
+        let parent = Interface::new(
+            Vec::new(),
+            vec![
+                port("clk", Direction::In, false),
+                port("sel", Direction::In, false),
+            ],
+            false,
+        );
+        let assoc =
+            |f: Option<&str>, a: &str| Association::new(f.map(|s| s.to_string()), a.to_string());
+        let inst = Instance::new("dff", "u_dff")
+            .generics(vec![assoc(Some("width"), "8"), assoc(Some("DEPTH"), "2")])
+            .ports(vec![
+                assoc(Some("CLK"), "clk"),
+                assoc(Some("rst"), "open"),
+                assoc(Some("q"), "sel"),
+                assoc(Some("d"), "x"),
+            ]);
+        assert_eq!(
+            inst.check(&unit, Some(&parent)),
+            vec![
+                ConnectionIssue::UnknownGeneric(String::from("DEPTH")),
+                ConnectionIssue::DrivesInput(String::from("q"), String::from("sel")),
+                ConnectionIssue::UnknownPort(String::from("d")),
+                ConnectionIssue::MissingInput(String::from("rst")),
+            ]
+        );
+
+        // positional and wildcard connections
+        let inst = Instance::new("dff", "u_dff").ports(vec![assoc(None, "clk"), assoc(None, "r")]);
+        assert_eq!(inst.check(&unit, None), vec![]);
+        let inst = Instance::new("dff", "u_dff").ports(vec![assoc(Some("*"), "")]);
+        assert_eq!(inst.check(&unit, None), vec![]);
+    }
+
+    #[test]
+    fn expand_scopes() {
+        let s = Scope::with_bounds(String::from("gen_lane"), Some(0), Some(2));
+        assert_eq!(
+            s.expand(),
+            vec!["gen_lane[0]", "gen_lane[1]", "gen_lane[2]"]
+        );
+
+        let s = Scope::with_bounds(String::from("gen_lane"), Some(1), Some(0));
+        assert_eq!(s.expand(), vec!["gen_lane[1]", "gen_lane[0]"]);
+
+        let s = Scope::with_bounds(String::from("gen_lane"), Some(0), None);
+        assert_eq!(s.expand(), vec!["gen_lane[*]"]);
+
+        let s = Scope::with_bounds(String::from("gen_lane"), Some(0), Some(255));
+        assert_eq!(s.expand().len(), 256);
+
+        let s = Scope::with_bounds(String::from("gen_lane"), Some(0), Some(1 << 20));
+        assert_eq!(s.expand(), vec!["gen_lane[*]"]);
+
+        let s = Scope::with_bounds(String::from("gen_lane"), Some(i64::MAX), Some(i64::MIN));
+        assert_eq!(s.expand(), vec!["gen_lane[*]"]);
+
+        let s = Scope::new(String::from("gen_fast"), ScopeKind::Branch);
+        assert_eq!(s.expand(), vec!["gen_fast"]);
+    }
+}
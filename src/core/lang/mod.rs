@@ -19,6 +19,8 @@ pub mod sv;
 pub mod verilog;
 pub mod vhdl;
 
+pub mod formatter;
+pub mod instance;
 pub mod lexer;
 pub mod parser;
 
@@ -26,6 +28,10 @@ pub mod node;
 
 pub mod reference;
 
+pub mod testbench;
+
+pub mod translate;
+
 use crate::error::Error;
 use crate::error::Hint;
 use crate::util::anyerror::AnyError;
@@ -155,7 +161,7 @@ impl FromStr for Lang {
         match s {
             "vhdl" => Ok(Self::Vhdl),
             "verilog" => Ok(Self::Verilog),
-            "systemverilog" => Ok(Self::SystemVerilog),
+            "systemverilog" | "sv" => Ok(Self::SystemVerilog),
             _ => Err(AnyError(format!("unsupported language {:?}", s))),
         }
     }
@@ -21,10 +21,14 @@ use crate::core::lang::vhdl::symbols::VhdlSymbol;
 use crate::util::anyerror::AnyError;
 use colored::Colorize;
 
+use super::instance::{Direction, Instance, Interface, PortSummary};
 use super::reference::RefSet;
 use super::sv::symbols::SystemVerilogSymbol;
+use super::sv::token::keyword::Keyword as SvKeyword;
+use super::verilog::symbols::config::Config;
 use super::verilog::symbols::module::Module;
 use super::verilog::symbols::VerilogSymbol;
+use super::vhdl::token::Keyword as VhdlKeyword;
 use super::{Lang, LangIdentifier, VhdlIdentifier};
 
 #[derive(Debug, PartialEq)]
@@ -82,6 +86,83 @@ impl HdlSymbol {
         }
     }
 
+    /// Casts the symbol to a Verilog or SystemVerilog configuration.
+    pub fn as_config(&self) -> Option<&Config> {
+        match &self {
+            Self::Verilog(VerilogSymbol::Config(c)) => Some(c),
+            Self::SystemVerilog(SystemVerilogSymbol::Config(c)) => Some(c),
+            _ => None,
+        }
+    }
+
+    /// Returns the names of the generics or parameters that can be overridden
+    /// on the unit.
+    pub fn get_generic_names(&self) -> Vec<String> {
+        match &self {
+            Self::Vhdl(v) => match v.as_entity() {
+                Some(e) => e
+                    .get_generics()
+                    .0
+                    .iter()
+                    .map(|g| g.get_identifier().to_string())
+                    .collect(),
+                None => Vec::new(),
+            },
+            Self::Verilog(_) | Self::SystemVerilog(_) => match self.as_module() {
+                Some(m) => m
+                    .get_parameters()
+                    .iter()
+                    .filter(|p| p.is_localparam() == false)
+                    .map(|p| p.get_name().to_string())
+                    .collect(),
+                None => Vec::new(),
+            },
+            Self::BlackBox(_) => Vec::new(),
+        }
+    }
+
+    /// Returns the generics and ports that instances of the unit connect to.
+    pub fn get_interface(&self) -> Option<Interface> {
+        match &self {
+            Self::Vhdl(v) => {
+                let e = v.as_entity()?;
+                let ports = e
+                    .get_ports()
+                    .0
+                    .iter()
+                    .map(|p| {
+                        let dir = match p.get_mode() {
+                            VhdlKeyword::In => Direction::In,
+                            VhdlKeyword::Out | VhdlKeyword::Buffer => Direction::Out,
+                            VhdlKeyword::Inout => Direction::InOut,
+                            _ => Direction::Other,
+                        };
+                        PortSummary::new(p.get_identifier().to_string(), dir, p.has_default())
+                    })
+                    .collect();
+                Some(Interface::new(self.get_generic_names(), ports, false))
+            }
+            Self::Verilog(_) | Self::SystemVerilog(_) => {
+                let m = self.as_module()?;
+                let ports = m
+                    .get_ports()
+                    .iter()
+                    .map(|p| {
+                        let dir = match p.get_direction() {
+                            SvKeyword::Input => Direction::In,
+                            SvKeyword::Output => Direction::Out,
+                            SvKeyword::Inout => Direction::InOut,
+                            _ => Direction::Other,
+                        };
+                        PortSummary::new(p.get_name().to_string(), dir, p.has_default())
+                    })
+                    .collect();
+                Some(Interface::new(self.get_generic_names(), ports, true))
+            }
+            Self::BlackBox(_) => None,
+        }
+    }
+
     pub fn is_testbench(&self) -> bool {
         match &self {
             Self::Verilog(v) => {
@@ -114,15 +195,21 @@ impl HdlSymbol {
 pub struct HdlNode<'a> {
     sym: HdlSymbol,
     files: Vec<&'a IpFileNode<'a>>, // must use a vector to retain file order in blueprint
+    instances: Vec<Instance<LangIdentifier>>,
 }
 
 impl<'a> HdlNode<'a> {
     pub fn new(sym: HdlSymbol, file: &'a IpFileNode) -> Self {
         let mut set = Vec::with_capacity(1);
         set.push(file);
+        let instances = match sym.as_module() {
+            Some(m) => m.get_instances().iter().map(|i| i.to_lang()).collect(),
+            None => Vec::new(),
+        };
         Self {
             sym: sym,
             files: set,
+            instances: instances,
         }
     }
 
@@ -154,6 +241,15 @@ impl<'a> HdlNode<'a> {
         &mut self.sym
     }
 
+    /// Accesses the instantiations within the unit's body (or last architecture).
+    pub fn get_instances(&self) -> &Vec<Instance<LangIdentifier>> {
+        &self.instances
+    }
+
+    pub fn set_instances(&mut self, instances: Vec<Instance<LangIdentifier>>) {
+        self.instances = instances;
+    }
+
     pub fn get_associated_files(&self) -> &Vec<&'a IpFileNode<'a>> {
         &self.files
     }
@@ -166,6 +262,7 @@ impl<'a> HdlNode<'a> {
         Self {
             sym: sym,
             files: Vec::new(),
+            instances: Vec::new(),
         }
     }
 
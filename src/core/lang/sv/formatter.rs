@@ -0,0 +1,433 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::format::SystemVerilogFormat;
+use super::token::keyword::Keyword;
+use super::token::operator::Operator;
+use super::token::token::SystemVerilogToken;
+use super::token::tokenizer::SystemVerilogTokenizer;
+use crate::core::lang::formatter::SourceFormatter;
+use crate::core::lang::lexer::{Position, Token};
+
+/// An unclosed parenthesis.
+#[derive(Debug, PartialEq)]
+struct Paren {
+    base: usize,
+    group: usize,
+}
+
+/// Checks if the keyword ends a block of code.
+fn is_closer(kw: &Keyword) -> bool {
+    match kw {
+        Keyword::Join | Keyword::JoinAny | Keyword::JoinNone => true,
+        _ => kw.to_string().starts_with("end"),
+    }
+}
+
+/// Checks if the keyword begins a block of code that is later closed by an
+/// `end` keyword, given the `head` of the statement read so far.
+fn is_opener(kw: &Keyword, head: &Vec<&SystemVerilogToken>) -> bool {
+    // the statement's leading keywords
+    let only = |allowed: &[Keyword]| {
+        head.iter()
+            .all(|t| t.as_keyword().is_some_and(|k| allowed.contains(k)))
+    };
+    match kw {
+        Keyword::Case | Keyword::Casex | Keyword::Casez | Keyword::Randcase => true,
+        Keyword::Module
+        | Keyword::Macromodule
+        | Keyword::Program
+        | Keyword::Package
+        | Keyword::Config
+        | Keyword::Primitive
+        | Keyword::Table
+        | Keyword::Specify
+        | Keyword::Generate
+        | Keyword::Covergroup
+        | Keyword::Property
+        | Keyword::Sequence
+        | Keyword::Checker
+        | Keyword::Clocking
+        | Keyword::Interface => head.is_empty(),
+        Keyword::Class => only(&[Keyword::Virtual]),
+        Keyword::Function | Keyword::Task => only(&[
+            Keyword::Virtual,
+            Keyword::Static,
+            Keyword::Local,
+            Keyword::Protected,
+            Keyword::Automatic,
+        ]),
+        _ => false,
+    }
+}
+
+/// Checks if the statement's `head` declares a design element whose header lists
+/// its parameters and ports.
+fn is_header(head: &Vec<&SystemVerilogToken>) -> bool {
+    match head.first().and_then(|t| t.as_keyword()) {
+        Some(kw) => match kw {
+            Keyword::Module | Keyword::Macromodule | Keyword::Interface | Keyword::Program => true,
+            _ => false,
+        },
+        None => false,
+    }
+}
+
+/// Finds the name declared by the port or parameter declaration written with
+/// the `tokens` beginning on `line`.
+///
+/// Returns `None` if the declaration's name is not on the same line.
+fn find_declared_name<'a>(
+    line: usize,
+    tokens: impl Iterator<Item = &'a Token<SystemVerilogToken>>,
+) -> Option<&'a Position> {
+    let mut nested = 0;
+    let mut name = None;
+    for tkn in tokens {
+        let t = tkn.as_type();
+        if tkn.locate().line() != line {
+            break;
+        }
+        match t {
+            SystemVerilogToken::Operator(Operator::ParenL)
+            | SystemVerilogToken::Operator(Operator::BrackL)
+            | SystemVerilogToken::Operator(Operator::ConcatL) => nested += 1,
+            SystemVerilogToken::Operator(Operator::ParenR)
+            | SystemVerilogToken::Operator(Operator::BrackR)
+            | SystemVerilogToken::Operator(Operator::ConcatR) => match nested {
+                0 => break,
+                _ => nested -= 1,
+            },
+            SystemVerilogToken::Operator(Operator::Comma)
+            | SystemVerilogToken::Operator(Operator::BlockAssign)
+            | SystemVerilogToken::Operator(Operator::Terminator)
+                if nested == 0 =>
+            {
+                break
+            }
+            _ => {
+                if nested == 0 && t.as_identifier().is_some() {
+                    name = Some(tkn.locate());
+                }
+            }
+        }
+    }
+    name
+}
+
+/// Formats the Verilog or SystemVerilog `source` code.
+///
+/// Compiler directives, along with their continued lines, are kept exactly as
+/// they are written.
+///
+/// Returns `None` if the formatted code would not consist of the same tokens as
+/// the original code.
+pub fn format(source: &str, fmt: &SystemVerilogFormat) -> Option<String> {
+    let tokens = SystemVerilogTokenizer::from_source_code(source).into_tokens_all();
+    let lines: Vec<&str> = source.lines().collect();
+    let mut out = SourceFormatter::new();
+
+    let mut blocks: usize = 0;
+    let mut parens: Vec<Paren> = Vec::new();
+    // the tokens of the statement being read
+    let mut head: Vec<&SystemVerilogToken> = Vec::new();
+    // a block was already opened by the statement being read
+    let mut pushed = false;
+    // the next tokens may be a block's `: label`
+    let mut label = false;
+    let mut groups = 0;
+
+    let mut line = 0;
+    let mut depth = 0;
+    let mut verbatim_until = 0;
+    let mut line_group: Option<usize> = None;
+
+    let mut iter = tokens.iter().peekable();
+    while let Some(tkn) = iter.next() {
+        let t = tkn.as_type();
+        let pos = tkn.locate();
+        if t.is_eof() == true {
+            break;
+        }
+        if pos.line() <= verbatim_until {
+            continue;
+        }
+        let starts_line = pos.line() > line;
+        if starts_line == true {
+            line = pos.line();
+            // keep directives along with their continued lines
+            if t.is_directive() == true {
+                verbatim_until = line;
+                while lines
+                    .get(verbatim_until - 1)
+                    .is_some_and(|l| l.trim_end().ends_with('\\'))
+                {
+                    verbatim_until += 1;
+                }
+                for l in line..=verbatim_until {
+                    out.keep(l);
+                }
+                continue;
+            }
+            line_group = parens.last().map(|p| p.group);
+            depth = match parens.last() {
+                Some(p) => match t.check_delimiter(&Operator::ParenR) {
+                    true => p.base,
+                    false => p.base + 1,
+                },
+                None => match t.as_keyword() {
+                    Some(kw) if is_closer(kw) == true => blocks.saturating_sub(1),
+                    Some(Keyword::Begin) | Some(Keyword::Fork) => blocks,
+                    // continue the statement from the previous line
+                    _ => match head.is_empty() == false && pushed == false {
+                        true => blocks + 1,
+                        false => blocks,
+                    },
+                },
+            };
+            out.indent(line, depth);
+            // align the names of a port or parameter list
+            if fmt.is_auto_name_aligned() == true && parens.len() == 1 && is_header(&head) {
+                let paren = parens.last().unwrap();
+                let new_decl = head.last().is_some_and(|p| {
+                    p.check_delimiter(&Operator::ParenL) || p.check_delimiter(&Operator::Comma)
+                });
+                if new_decl == true {
+                    if let Some(name) =
+                        find_declared_name(line, std::iter::once(tkn).chain(iter.clone()))
+                    {
+                        out.align(name, paren.group, 0, 1 + fmt.get_name_offset());
+                    }
+                }
+            }
+        }
+
+        // keep the inner lines of multi-line comments
+        if t.as_comment().is_some() == true {
+            let extra = t.to_string().matches('\n').count();
+            if extra == 0 {
+                out.keep_trailing(line);
+            }
+            for l in line + 1..=line + extra {
+                out.keep(l);
+            }
+            line += extra;
+            continue;
+        }
+
+        // skip a block's label
+        if label == true {
+            label = false;
+            if t.check_delimiter(&Operator::Colon) == true {
+                if iter
+                    .peek()
+                    .is_some_and(|n| n.as_type().as_identifier().is_some())
+                {
+                    iter.next();
+                }
+                continue;
+            }
+        }
+
+        match t {
+            SystemVerilogToken::Operator(Operator::ParenL) => {
+                parens.push(Paren {
+                    base: depth,
+                    group: groups,
+                });
+                groups += 1;
+                head.push(t);
+            }
+            SystemVerilogToken::Operator(Operator::ParenR) => {
+                parens.pop();
+                head.push(t);
+            }
+            SystemVerilogToken::Operator(Operator::Dot) if parens.is_empty() == false => {
+                let paren = parens.last().unwrap();
+                // align the connections of a port or parameter list
+                if fmt.is_auto_mapping_aligned() == true
+                    && starts_line == true
+                    && line_group == Some(paren.group)
+                {
+                    let name = iter.next();
+                    if let Some(next) = iter.peek() {
+                        if next.as_type().check_delimiter(&Operator::ParenL) == true {
+                            out.align(next.locate(), paren.group, 0, fmt.get_mapping_offset());
+                        }
+                    }
+                    head.push(t);
+                    if let Some(name) = name {
+                        head.push(name.as_type());
+                    }
+                } else {
+                    head.push(t);
+                }
+            }
+            SystemVerilogToken::Operator(Operator::Terminator) if parens.is_empty() == true => {
+                head.clear();
+                pushed = false;
+            }
+            SystemVerilogToken::Keyword(kw) if parens.is_empty() == true => {
+                if is_closer(kw) == true {
+                    blocks = blocks.saturating_sub(1);
+                    head.clear();
+                    pushed = false;
+                    label = true;
+                } else if kw == &Keyword::Begin || kw == &Keyword::Fork {
+                    blocks += 1;
+                    head.clear();
+                    pushed = false;
+                    label = true;
+                } else if is_opener(kw, &head) == true {
+                    blocks += 1;
+                    pushed = true;
+                    head.push(t);
+                } else {
+                    head.push(t);
+                }
+            }
+            _ => head.push(t),
+        }
+    }
+
+    let result = out.apply(source, fmt.get_tab_size());
+    // verify the code itself is unchanged
+    let tokens_after = SystemVerilogTokenizer::from_source_code(&result).into_tokens_all();
+    match tokens
+        .iter()
+        .map(|t| t.as_type())
+        .eq(tokens_after.iter().map(|t| t.as_type()))
+    {
+        true => Some(result),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_module() {
+        let code = r#"`define WIDTH 8
+`define MAX(a, b) \
+    ((a) > (b) ? (a) : (b))
+
+module counter #(
+parameter N = 4
+) (
+  input logic clk,
+      output logic [N-1:0] count
+);
+/* a block
+     comment */
+always_ff @(posedge clk) begin : l_count
+if (count == 0)
+count <= 1;
+  else begin
+      count <= count + 1;
+        end
+end : l_count
+
+  function automatic int add(int a,
+  int b);
+return a + b;
+    endfunction
+
+adder u_add (
+.a(count),
+    .sum   (total),
+  .b(1)
+);
+endmodule
+"#;
+        let expected = r#"`define WIDTH 8
+`define MAX(a, b) \
+    ((a) > (b) ? (a) : (b))
+
+module counter #(
+  parameter N = 4
+) (
+  input logic clk,
+  output logic [N-1:0] count
+);
+  /* a block
+     comment */
+  always_ff @(posedge clk) begin : l_count
+    if (count == 0)
+      count <= 1;
+    else begin
+      count <= count + 1;
+    end
+  end : l_count
+
+  function automatic int add(int a,
+    int b);
+    return a + b;
+  endfunction
+
+  adder u_add (
+    .a  (count),
+    .sum(total),
+    .b  (1)
+  );
+endmodule
+"#;
+        let mut fmt: SystemVerilogFormat =
+            toml::from_str("tab-size = 2\nmapping-auto-alignment = true").unwrap();
+        fmt.merge(Some(SystemVerilogFormat::new()));
+        assert_eq!(format(code, &fmt).unwrap(), expected);
+    }
+
+    #[test]
+    fn format_header_names() {
+        let code = r#"module shift #(
+  parameter int unsigned W = 8,
+  parameter DEPTH = 2
+) (
+  input logic clk, // clock
+  input logic rst,
+  input wire [W-1:0] d,
+  output logic [W-1:0] q [DEPTH],
+  my_if.slave bus
+);
+  assign q[0] = d;
+endmodule
+"#;
+        let expected = r#"module shift #(
+  parameter int unsigned W = 8,
+  parameter              DEPTH = 2
+) (
+  input logic          clk, // clock
+  input logic          rst,
+  input wire [W-1:0]   d,
+  output logic [W-1:0] q [DEPTH],
+  my_if.slave          bus
+);
+  assign q[0] = d;
+endmodule
+"#;
+        let mut fmt: SystemVerilogFormat =
+            toml::from_str("tab-size = 2\nname-auto-alignment = true").unwrap();
+        fmt.merge(Some(SystemVerilogFormat::new()));
+        assert_eq!(format(code, &fmt).unwrap(), expected);
+
+        // the names are left as they are written without alignment
+        let fmt: SystemVerilogFormat = toml::from_str("tab-size = 2").unwrap();
+        assert_eq!(format(code, &fmt).unwrap(), code);
+    }
+}
@@ -108,6 +108,14 @@ impl SystemVerilogSymbol {
         }
     }
 
+    /// Transforms `self` into module.
+    pub fn into_module(self) -> Option<Module> {
+        match self {
+            Self::Module(m) => Some(m),
+            _ => None,
+        }
+    }
+
     pub fn get_refs(&self) -> &RefSet {
         match &self {
             Self::Module(m) => m.get_refs(),
@@ -286,6 +294,19 @@ impl Parse<SystemVerilogToken> for SystemVerilogParser {
                 if let Some(i_refs) = i_refs {
                     global_refs.extend(i_refs);
                 }
+            // take a bind directive outside of any design element
+            } else if t.as_type().check_keyword(&Keyword::Bind) {
+                let b_refs = VerilogSymbol::into_next_statement(t, &mut tokens).and_then(|stmt| {
+                    let mut b_refs = RefSet::new();
+                    if let Some(stmt) = stmt {
+                        VerilogSymbol::handle_statement(stmt, None, None, &mut b_refs, None)?;
+                    }
+                    Ok(b_refs)
+                });
+                match b_refs {
+                    Ok(b_refs) => global_refs.extend(b_refs),
+                    Err(e) => symbols.push(Err(e)),
+                }
             // take attribute and ignore if okay
             } else if t.as_type().check_delimiter(&Operator::AttrL) {
                 match SystemVerilogSymbol::parse_attr(&mut tokens, t.into_position()) {
@@ -578,4 +599,99 @@ endmodule
             ]
         );
     }
+
+    #[test]
+    fn ut_bind_and_interface_refs() {
+        let code = r#"
+module tb;
+    logic clk;
+    axi_if bus_i (clk);
+    virtual interface mon_if.passive vif;
+    dut u_dut (.clk(clk), .bus(bus_i.master));
+    bind dut : u_dut dut_checker #(.WIDTH(8)) u_chk (.clk(clk));
+endmodule
+
+module dut (input logic clk, axi_if.master bus);
+endmodule
+
+class monitor;
+    virtual cfg_if vif;
+endclass
+
+bind dut prot_checker u_prot (.clk(clk));
+"#;
+        let symbols = SystemVerilogParser::read(&code).unwrap().into_symbols();
+        let name =
+            |s: &str| CompoundIdentifier::new_minimal_verilog(Identifier::from_str(s).unwrap());
+
+        let tb = symbols[0].as_module().unwrap();
+        // bound modules are referenced but are not instantiated by the module
+        assert_eq!(
+            tb.get_edge_list_entities(),
+            vec![name("axi_if"), name("dut")]
+        );
+        for r in ["dut_checker", "mon_if", "prot_checker"] {
+            assert_eq!(tb.get_refs().contains(&name(r)), true);
+        }
+        // interface-typed ports are referenced
+        assert_eq!(symbols[1].get_refs().contains(&name("axi_if")), true);
+        // virtual interfaces within classes are referenced
+        assert_eq!(symbols[2].get_refs().contains(&name("cfg_if")), true);
+    }
+
+    #[test]
+    fn ut_instance_hierarchy() {
+        let code = r#"
+module core #(parameter N = 4) (input logic clk);
+    genvar i;
+    generate
+        for (i = 0; i < 2; i = i + 1) begin : gen_lane
+            alu #(.WIDTH(8), .DEPTH(N-1)) u_alu (.clk(clk));
+        end
+        for (genvar j = 0; j < N; j++) begin : gen_pipe
+            if (j > 0) begin : gen_reg
+                dff u_a (.clk(clk)), u_b (.clk(clk));
+            end
+        end
+    endgenerate
+    always @(posedge clk) begin
+    end
+    fifo #(16) u_fifo (.clk(clk));
+endmodule
+"#;
+        let symbols = SystemVerilogParser::read(&code).unwrap().into_symbols();
+        let insts = symbols[0].as_module().unwrap().get_instances();
+        let paths: Vec<(String, String, Vec<String>)> = insts
+            .iter()
+            .map(|i| {
+                (
+                    i.get_name().to_string(),
+                    i.get_unit().to_string(),
+                    i.get_scopes()
+                        .iter()
+                        .map(|s| s.expand().join(","))
+                        .collect(),
+                )
+            })
+            .collect();
+        let s = |v: &str| String::from(v);
+        assert_eq!(
+            paths,
+            vec![
+                (s("u_alu"), s("alu"), vec![s("gen_lane[0],gen_lane[1]")]),
+                (s("u_a"), s("dff"), vec![s("gen_pipe[*]"), s("gen_reg")]),
+                (s("u_b"), s("dff"), vec![s("gen_pipe[*]"), s("gen_reg")]),
+                (s("u_fifo"), s("fifo"), vec![]),
+            ]
+        );
+        let generics = |i: usize| {
+            insts[i]
+                .get_generics()
+                .iter()
+                .map(|g| g.to_string())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(generics(0), vec!["WIDTH=8", "DEPTH=N-1"]);
+        assert_eq!(generics(3), vec!["16"]);
+    }
 }
@@ -0,0 +1,197 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Shared rules for generating testbench skeletons around a design unit.
+
+/// The suffix appended to a unit's name to create its testbench's name.
+pub const TESTBENCH_SUFFIX: &str = "_tb";
+
+/// The name of the constant that sets the clock period in a testbench.
+pub const CLK_PERIOD: &str = "CLK_PERIOD";
+
+/// The number of clock periods the reset signal is held active.
+pub const RESET_CYCLES: usize = 4;
+
+/// Indents every non-empty line of `text` by `width` spaces.
+pub fn indent(text: &str, width: usize) -> String {
+    text.lines()
+        .map(|l| match l.is_empty() {
+            true => String::new(),
+            false => format!("{:<width$}{}", "", l, width = width),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum PortRole {
+    Clock,
+    Reset { active_low: bool },
+}
+
+impl PortRole {
+    /// Determines the role of an input port based on common naming conventions
+    /// for clocks and resets.
+    ///
+    /// Returns `None` if the port is a regular data input.
+    pub fn classify(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        // split into words to avoid matching partial names (`clkdiv`, `first`)
+        let words: Vec<&str> = name.split('_').filter(|w| w.is_empty() == false).collect();
+
+        // the clock must be the first or last word and not be qualified by the
+        // words after it (`clk_en`, `sys_clk_sel`)
+        let is_clock = |i: usize| {
+            CLOCK_NAMES.contains(&words[i])
+                && words[i + 1..]
+                    .iter()
+                    .all(|n| CLOCK_QUALIFIERS.contains(n) == false)
+        };
+        if words.is_empty() == false && (is_clock(0) || is_clock(words.len() - 1)) {
+            return Some(Self::Clock);
+        }
+
+        for (i, w) in words.iter().enumerate() {
+            // detect active-low resets by a leading 'n' or trailing 'n'/'b' (`nrst`, `rstn`)
+            let variants = [
+                (Some(*w), false),
+                (w.strip_suffix('n'), true),
+                (w.strip_suffix('b'), true),
+                (w.strip_prefix('n'), true),
+            ];
+            let found = variants.iter().find_map(|(base, low)| match base {
+                Some(b) if RESET_NAMES.contains(b) => Some(*low),
+                _ => None,
+            });
+            if let Some(low_affix) = found {
+                // detect active-low resets by a separate trailing word (`rst_n`)
+                let low_word = words
+                    .get(i + 1)
+                    .is_some_and(|n| ACTIVE_LOW_WORDS.contains(n));
+                return Some(Self::Reset {
+                    active_low: low_affix || low_word,
+                });
+            }
+        }
+        None
+    }
+
+    pub fn is_clock(&self) -> bool {
+        self == &Self::Clock
+    }
+
+    pub fn is_reset(&self) -> bool {
+        match self {
+            Self::Reset { active_low: _ } => true,
+            _ => false,
+        }
+    }
+
+    /// Returns the logic value that asserts the reset (`'0'` or `'1'`).
+    ///
+    /// Clocks are assumed to start at logic low.
+    pub fn active_level(&self) -> char {
+        match self {
+            Self::Reset { active_low } => match active_low {
+                true => '0',
+                false => '1',
+            },
+            Self::Clock => '0',
+        }
+    }
+
+    /// Returns the logic value that deasserts the reset.
+    pub fn inactive_level(&self) -> char {
+        match self.active_level() {
+            '0' => '1',
+            _ => '0',
+        }
+    }
+}
+
+const CLOCK_NAMES: [&str; 4] = ["clk", "clock", "aclk", "sclk"];
+
+const CLOCK_QUALIFIERS: [&str; 8] = ["en", "ena", "enable", "ce", "sel", "select", "div", "gate"];
+
+const RESET_NAMES: [&str; 6] = ["rst", "reset", "arst", "srst", "areset", "sreset"];
+
+const ACTIVE_LOW_WORDS: [&str; 4] = ["n", "b", "l", "ni"];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn classify_clocks() {
+        assert_eq!(PortRole::classify("clk"), Some(PortRole::Clock));
+        assert_eq!(PortRole::classify("CLK"), Some(PortRole::Clock));
+        assert_eq!(PortRole::classify("sys_clk"), Some(PortRole::Clock));
+        assert_eq!(PortRole::classify("clock_in"), Some(PortRole::Clock));
+        assert_eq!(PortRole::classify("clkdiv"), None);
+        // enables and selects of a clock are not clocks
+        assert_eq!(PortRole::classify("clk_en"), None);
+        assert_eq!(PortRole::classify("clk_sel"), None);
+        assert_eq!(PortRole::classify("sys_clk_en"), None);
+        assert_eq!(PortRole::classify("data"), None);
+    }
+
+    #[test]
+    fn classify_resets() {
+        assert_eq!(
+            PortRole::classify("rst"),
+            Some(PortRole::Reset { active_low: false })
+        );
+        assert_eq!(
+            PortRole::classify("reset"),
+            Some(PortRole::Reset { active_low: false })
+        );
+        assert_eq!(
+            PortRole::classify("rst_n"),
+            Some(PortRole::Reset { active_low: true })
+        );
+        assert_eq!(
+            PortRole::classify("rstn"),
+            Some(PortRole::Reset { active_low: true })
+        );
+        assert_eq!(
+            PortRole::classify("nrst"),
+            Some(PortRole::Reset { active_low: true })
+        );
+        assert_eq!(
+            PortRole::classify("arst_ni"),
+            Some(PortRole::Reset { active_low: true })
+        );
+        assert_eq!(PortRole::classify("first"), None);
+        assert_eq!(PortRole::classify("n"), None);
+    }
+
+    #[test]
+    fn indent_lines() {
+        assert_eq!(indent("a\n\nb;", 2), "  a\n\n  b;");
+        assert_eq!(indent("a", 0), "a");
+    }
+
+    #[test]
+    fn reset_levels() {
+        let rst = PortRole::Reset { active_low: true };
+        assert_eq!(rst.active_level(), '0');
+        assert_eq!(rst.inactive_level(), '1');
+        let rst = PortRole::Reset { active_low: false };
+        assert_eq!(rst.active_level(), '1');
+        assert_eq!(rst.inactive_level(), '0');
+    }
+}
@@ -0,0 +1,942 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+//! Translates the interface of a design unit into another hdl language.
+//!
+//! The interface is written as source code in the target language and then
+//! parsed back with that language's parser, so the translated unit can be
+//! displayed with the same code snippets as a native unit.
+
+use super::sv::symbols::SystemVerilogParser;
+use super::sv::token::keyword::Keyword as SvKeyword;
+use super::sv::token::token::SystemVerilogToken;
+use super::verilog::interface::{self, Port};
+use super::verilog::symbols::module::Module;
+use super::verilog::symbols::VerilogParser;
+use super::verilog::token::keyword::Keyword as VerilogKeyword;
+use super::vhdl::interface::InterfaceDeclaration;
+use super::vhdl::symbols::entity::Entity;
+use super::vhdl::symbols::VHDLParser;
+use super::vhdl::token::keyword::Keyword as VhdlKeyword;
+use super::vhdl::token::VhdlToken;
+use super::Lang;
+use crate::error::Error;
+
+/// The result of translating a unit's interface into another language.
+#[derive(Debug, PartialEq)]
+pub struct Translation<T> {
+    unit: T,
+    warnings: Vec<String>,
+}
+
+impl<T> Translation<T> {
+    pub fn get_unit(&self) -> &T {
+        &self.unit
+    }
+
+    /// Accesses the messages for parts of the interface that could not be
+    /// faithfully translated.
+    pub fn get_warnings(&self) -> &Vec<String> {
+        &self.warnings
+    }
+}
+
+/// Translates the interface of a VHDL entity into a Verilog or SystemVerilog module.
+///
+/// Errors if the generated source code cannot be read back as a module.
+pub fn entity_to_module(entity: &Entity, lang: &Lang) -> Result<Translation<Module>, Error> {
+    let is_sv = lang == &Lang::SystemVerilog;
+    let mut warnings = Vec::new();
+    let source = write_verilog_module(entity, is_sv, &mut warnings);
+    let symbols = match is_sv {
+        true => SystemVerilogParser::read_lazy(&source)
+            .into_symbols()
+            .into_iter()
+            .find_map(|s| s.into_module()),
+        false => VerilogParser::read_lazy(&source)
+            .into_symbols()
+            .into_iter()
+            .find_map(|s| s.into_module()),
+    };
+    match symbols {
+        Some(unit) => Ok(Translation {
+            unit: unit,
+            warnings: warnings,
+        }),
+        None => Err(Error::UnitNotTranslatable(
+            entity.get_name().to_string(),
+            lang.clone(),
+        )),
+    }
+}
+
+/// Translates the interface of a Verilog or SystemVerilog module into a VHDL entity.
+///
+/// Errors if the generated source code cannot be read back as an entity.
+pub fn module_to_entity(module: &Module) -> Result<Translation<Entity>, Error> {
+    let mut warnings = Vec::new();
+    let source = write_vhdl_entity(module, &mut warnings);
+    let entity = VHDLParser::read_lazy(&source)
+        .into_symbols()
+        .into_iter()
+        .find_map(|s| s.into_entity());
+    match entity {
+        Some(unit) => Ok(Translation {
+            unit: unit,
+            warnings: warnings,
+        }),
+        None => Err(Error::UnitNotTranslatable(
+            module.get_name().to_string(),
+            Lang::Vhdl,
+        )),
+    }
+}
+
+/// A range of bits written in a language-neutral form.
+#[derive(Debug, PartialEq)]
+pub struct Range {
+    left: String,
+    right: String,
+    descending: bool,
+}
+
+impl Range {
+    pub fn get_left(&self) -> &String {
+        &self.left
+    }
+
+    pub fn get_right(&self) -> &String {
+        &self.right
+    }
+
+    fn to_verilog(&self) -> String {
+        format!("[{}:{}]", self.left, self.right)
+    }
+
+    fn to_vhdl(&self) -> String {
+        match self.descending {
+            true => format!("({} downto {})", self.left, self.right),
+            false => format!("({} to {})", self.left, self.right),
+        }
+    }
+}
+
+/// Writes the source code of a Verilog module declaration with the entity's interface.
+fn write_verilog_module(entity: &Entity, is_sv: bool, warnings: &mut Vec<String>) -> String {
+    let mut params = Vec::new();
+    for g in entity.get_generics().0.iter() {
+        let name = verilog_name(g.get_identifier().as_str(), is_sv, warnings);
+        let decl = match vhdl_generic_to_verilog(g, is_sv) {
+            Some(kind) => format!("parameter {}{}", kind, name),
+            None => {
+                warnings.push(format!(
+                    "generic '{}' has untranslatable type \"{}\"",
+                    g.get_identifier(),
+                    vhdl_tokens_to_string(g.get_type_tokens())
+                ));
+                format!("parameter {}", name)
+            }
+        };
+        let value = match g.get_default_tokens() {
+            Some(tkns) => match vhdl_expr_to_verilog(tkns) {
+                Some(v) => format!(" = {}", v),
+                None => {
+                    warnings.push(format!(
+                        "generic '{}' has untranslatable default \"{}\"",
+                        g.get_identifier(),
+                        vhdl_tokens_to_string(tkns)
+                    ));
+                    String::new()
+                }
+            },
+            None => String::new(),
+        };
+        params.push(format!("{}{}", decl, value));
+    }
+
+    let mut ports = Vec::new();
+    for p in entity.get_ports().0.iter() {
+        let name = verilog_name(p.get_identifier().as_str(), is_sv, warnings);
+        let dir = match p.get_mode() {
+            VhdlKeyword::In => "input",
+            VhdlKeyword::Out | VhdlKeyword::Buffer => "output",
+            VhdlKeyword::Inout => "inout",
+            m => {
+                warnings.push(format!(
+                    "port '{}' has untranslatable mode \"{}\"",
+                    p.get_identifier(),
+                    m
+                ));
+                "inout"
+            }
+        };
+        let net = match is_sv {
+            true => "logic",
+            false => "wire",
+        };
+        let kind = match vhdl_port_to_verilog(p) {
+            Some(k) => k,
+            None => {
+                warnings.push(format!(
+                    "port '{}' has untranslatable type \"{}\"",
+                    p.get_identifier(),
+                    vhdl_tokens_to_string(p.get_type_tokens())
+                ));
+                String::new()
+            }
+        };
+        ports.push(format!("{} {} {}{}", dir, net, kind, name));
+    }
+
+    let mut result = format!(
+        "module {}",
+        verilog_name(entity.get_name().as_str(), is_sv, warnings)
+    );
+    if params.is_empty() == false {
+        result.push_str(&format!(" #({})", params.join(", ")));
+    }
+    result.push_str(&format!(" ({});\nendmodule\n", ports.join(", ")));
+    result
+}
+
+/// Writes the source code of a VHDL entity declaration with the module's interface.
+fn write_vhdl_entity(module: &Module, warnings: &mut Vec<String>) -> String {
+    let mut generics = Vec::new();
+    for p in module.get_parameters().iter() {
+        if p.is_localparam() == true {
+            continue;
+        }
+        let name = vhdl_name(p.get_name().as_str(), warnings);
+        let kind = match verilog_param_to_vhdl(p) {
+            Some(k) => k,
+            None => {
+                warnings.push(format!(
+                    "parameter '{}' has untranslatable type \"{}\"",
+                    p.get_name(),
+                    verilog_type_to_string(p)
+                ));
+                String::from("integer")
+            }
+        };
+        let value = match p.get_default() {
+            Some(tkns) => match verilog_expr_to_vhdl(tkns) {
+                Some(v) => format!(" := {}", v),
+                None => {
+                    warnings.push(format!(
+                        "parameter '{}' has untranslatable default \"{}\"",
+                        p.get_name(),
+                        verilog_tokens_to_string(tkns)
+                    ));
+                    String::new()
+                }
+            },
+            None => String::new(),
+        };
+        generics.push(format!("{} : {}{}", name, kind, value));
+    }
+
+    let mut ports = Vec::new();
+    for p in module.get_ports().iter() {
+        let name = vhdl_name(p.get_name().as_str(), warnings);
+        let mode = match p.get_direction() {
+            SvKeyword::Input => "in",
+            SvKeyword::Output => "out",
+            SvKeyword::Inout => "inout",
+            m => {
+                warnings.push(format!(
+                    "port '{}' has untranslatable direction \"{}\"",
+                    p.get_name(),
+                    m
+                ));
+                "inout"
+            }
+        };
+        let kind = match verilog_port_to_vhdl(p) {
+            Some(k) => k,
+            None => {
+                warnings.push(format!(
+                    "port '{}' has untranslatable type \"{}\"",
+                    p.get_name(),
+                    verilog_type_to_string(p)
+                ));
+                String::from("std_logic")
+            }
+        };
+        ports.push(format!("{} : {} {}", name, mode, kind));
+    }
+
+    let mut result = format!(
+        "entity {} is\n",
+        vhdl_name(module.get_name().as_str(), warnings)
+    );
+    if generics.is_empty() == false {
+        result.push_str(&format!("generic ({});\n", generics.join("; ")));
+    }
+    if ports.is_empty() == false {
+        result.push_str(&format!("port ({});\n", ports.join("; ")));
+    }
+    result.push_str("end entity;\n");
+    result
+}
+
+/// Returns an identifier that is legal in Verilog for the name `name`, escaping
+/// it if it is a keyword or is not a simple identifier.
+///
+/// Whitespace cannot appear in an escaped identifier, so it is replaced with `_`.
+fn verilog_name(name: &str, is_sv: bool, warnings: &mut Vec<String>) -> String {
+    let is_keyword = match is_sv {
+        true => SvKeyword::match_keyword(name).is_some(),
+        false => VerilogKeyword::match_keyword(name).is_some(),
+    };
+    if is_keyword == false && is_verilog_simple_identifier(name) == true {
+        return name.to_string();
+    }
+    match name.contains(char::is_whitespace) {
+        true => {
+            let replaced = name.replace(char::is_whitespace, "_");
+            warnings.push(format!(
+                "identifier '{}' contains whitespace and was escaped as '{}'",
+                name, replaced
+            ));
+            format!("\\{} ", replaced)
+        }
+        false => {
+            warnings.push(match is_keyword {
+                true => format!("identifier '{}' is a reserved word and was escaped", name),
+                false => format!(
+                    "identifier '{}' is not a simple identifier and was escaped",
+                    name
+                ),
+            });
+            format!("\\{} ", name)
+        }
+    }
+}
+
+/// Returns an identifier that is legal in VHDL for the name `name`, extending it
+/// if it is a keyword or is not a basic identifier.
+fn vhdl_name(name: &str, warnings: &mut Vec<String>) -> String {
+    match VhdlKeyword::match_keyword(name).is_some() || is_vhdl_basic_identifier(name) == false {
+        true => {
+            warnings.push(format!(
+                "identifier '{}' is not a basic identifier and was extended",
+                name
+            ));
+            format!("\\{}\\", name.replace('\\', "\\\\"))
+        }
+        false => name.to_string(),
+    }
+}
+
+/// Checks if `name` is a Verilog simple identifier: a letter or `_` followed by
+/// letters, digits, `_`, or `$`.
+fn is_verilog_simple_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() == true || c == '_' => {
+            chars.all(|c| c.is_ascii_alphanumeric() == true || c == '_' || c == '$')
+        }
+        _ => false,
+    }
+}
+
+/// Checks if `name` is a VHDL basic identifier: a letter followed by letters,
+/// digits, and single underscores, not ending with an underscore.
+fn is_vhdl_basic_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic()) == true
+        && name.ends_with('_') == false
+        && name.contains("__") == false
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() == true || c == '_')
+}
+
+/// Writes VHDL tokens as text separated by spaces.
+pub fn vhdl_tokens_to_string(tokens: &[VhdlToken]) -> String {
+    tokens
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Writes Verilog or SystemVerilog tokens as text with the spacing used in declarations.
+pub fn verilog_tokens_to_string(tokens: &[SystemVerilogToken]) -> String {
+    interface::tokens_to_string(&tokens.to_vec())
+}
+
+fn verilog_type_to_string(port: &Port) -> String {
+    let mut result = String::new();
+    if let Some(t) = port.get_data_type() {
+        result.push_str(&t.to_string());
+    }
+    if let Some(r) = port.get_range() {
+        result.push_str(&verilog_tokens_to_string(r));
+    }
+    if let Some(r) = port.get_unpacked_range() {
+        result.push_str(&verilog_tokens_to_string(r));
+    }
+    result
+}
+
+/// Splits a VHDL subtype indication into its lowercase type mark and optional range.
+///
+/// Returns `None` when the subtype indication is more complex than a type mark
+/// with a single index constraint.
+pub fn split_vhdl_type(tokens: &Vec<VhdlToken>) -> Option<(String, Option<Range>)> {
+    let mut iter = tokens.iter();
+    let mark = match iter.next()? {
+        VhdlToken::Identifier(id) => id.as_str().to_lowercase(),
+        _ => return None,
+    };
+    let rest: Vec<&VhdlToken> = iter.collect();
+    if rest.is_empty() == true {
+        return Some((mark, None));
+    }
+    // expect a single parenthesized constraint
+    if rest.first()?.to_string() != "(" || rest.last()?.to_string() != ")" {
+        return None;
+    }
+    let inner = &rest[1..rest.len() - 1];
+    let mut depth = 0;
+    for (i, t) in inner.iter().enumerate() {
+        match t.to_string().as_str() {
+            "(" => depth += 1,
+            ")" => depth -= 1,
+            _ => (),
+        }
+        if depth == 0 {
+            if let VhdlToken::Keyword(kw) = t {
+                let descending = match kw {
+                    VhdlKeyword::Downto => true,
+                    VhdlKeyword::To => false,
+                    _ => return None,
+                };
+                let left =
+                    vhdl_expr_to_verilog(&inner[..i].iter().map(|t| (*t).clone()).collect())?;
+                let right =
+                    vhdl_expr_to_verilog(&inner[i + 1..].iter().map(|t| (*t).clone()).collect())?;
+                return Some((
+                    mark,
+                    Some(Range {
+                        left: left,
+                        right: right,
+                        descending: descending,
+                    }),
+                ));
+            }
+        }
+    }
+    None
+}
+
+/// Determines the Verilog type and range that precede a generic's name.
+fn vhdl_generic_to_verilog(decl: &InterfaceDeclaration, is_sv: bool) -> Option<String> {
+    let (mark, range) = split_vhdl_type(decl.get_type_tokens())?;
+    let kind = match (mark.as_str(), is_sv) {
+        ("integer" | "natural" | "positive", true) => String::from("int "),
+        ("integer" | "natural" | "positive", false) => String::from("integer "),
+        ("real", _) => String::from("real "),
+        ("string", true) => String::from("string "),
+        ("boolean" | "std_logic" | "std_ulogic" | "bit", true) => String::from("bit "),
+        ("string" | "boolean" | "std_logic" | "std_ulogic" | "bit", false) => String::new(),
+        _ => return vhdl_vector_to_verilog(&mark, &range),
+    };
+    match range {
+        Some(_) => None,
+        None => Some(kind),
+    }
+}
+
+/// Determines the Verilog type and range that precede a port's name.
+fn vhdl_port_to_verilog(decl: &InterfaceDeclaration) -> Option<String> {
+    let (mark, range) = split_vhdl_type(decl.get_type_tokens())?;
+    match mark.as_str() {
+        "std_logic" | "std_ulogic" | "bit" => match range {
+            Some(_) => None,
+            None => Some(String::new()),
+        },
+        _ => vhdl_vector_to_verilog(&mark, &range),
+    }
+}
+
+fn vhdl_vector_to_verilog(mark: &str, range: &Option<Range>) -> Option<String> {
+    let range = range.as_ref()?;
+    match mark {
+        "std_logic_vector" | "std_ulogic_vector" | "bit_vector" | "unsigned" => {
+            Some(format!("{} ", range.to_verilog()))
+        }
+        "signed" => Some(format!("signed {} ", range.to_verilog())),
+        _ => None,
+    }
+}
+
+/// Translates a VHDL expression into a Verilog expression.
+///
+/// Returns `None` if the expression uses constructs that do not have a
+/// direct equivalent.
+pub fn vhdl_expr_to_verilog(tokens: &Vec<VhdlToken>) -> Option<String> {
+    let mut result = Vec::new();
+    let mut was_operand = false;
+    for t in tokens {
+        // adjacent operands indicate a physical literal such as `10 ns`
+        let is_operand = match t {
+            VhdlToken::Delimiter(_) | VhdlToken::Keyword(_) => false,
+            _ => true,
+        };
+        if was_operand == true && is_operand == true {
+            return None;
+        }
+        was_operand = is_operand;
+        result.push(match t {
+            VhdlToken::Identifier(id) => match id.as_str().to_lowercase().as_str() {
+                "true" => String::from("1"),
+                "false" => String::from("0"),
+                _ => id.to_string(),
+            },
+            VhdlToken::AbstLiteral(lit) => {
+                let text = lit.to_string().replace('_', "");
+                match text.split_once('#') {
+                    Some((base, digits)) => {
+                        let digits = digits.trim_end_matches('#');
+                        match base {
+                            "2" => format!("'b{}", digits),
+                            "8" => format!("'o{}", digits),
+                            "10" => digits.to_string(),
+                            "16" => format!("'h{}", digits),
+                            _ => return None,
+                        }
+                    }
+                    None => text,
+                }
+            }
+            VhdlToken::CharLiteral(c) => match c.0.as_str() {
+                "0" | "1" => format!("1'b{}", c.0),
+                "Z" | "z" | "X" | "x" => format!("1'b{}", c.0.to_lowercase()),
+                _ => return None,
+            },
+            VhdlToken::BitStrLiteral(b) => {
+                let text = b.0.replace('_', "");
+                let (base, digits) = text.split_once('"')?;
+                let digits = digits.trim_end_matches('"');
+                let (spec, bits) = match base.to_lowercase().as_str() {
+                    "b" => ('b', 1),
+                    "o" => ('o', 3),
+                    "x" => ('h', 4),
+                    _ => return None,
+                };
+                format!("{}'{}{}", digits.len() * bits, spec, digits)
+            }
+            VhdlToken::StrLiteral(s) => format!("\"{}\"", s),
+            VhdlToken::Delimiter(_) => match t.to_string().as_str() {
+                "+" | "-" | "*" | "/" | "**" | "(" | ")" => t.to_string(),
+                _ => return None,
+            },
+            VhdlToken::Keyword(kw) => match kw {
+                VhdlKeyword::Mod | VhdlKeyword::Rem => String::from("%"),
+                _ => return None,
+            },
+            _ => return None,
+        });
+    }
+    match result.is_empty() {
+        true => None,
+        false => Some(result.join(" ")),
+    }
+}
+
+/// Splits a Verilog packed range `[l:r]` into the tokens of its left and right bounds.
+pub fn split_verilog_range(
+    tokens: &[SystemVerilogToken],
+) -> Option<(&[SystemVerilogToken], &[SystemVerilogToken])> {
+    if tokens.first()?.to_string() != "[" || tokens.last()?.to_string() != "]" {
+        return None;
+    }
+    let inner = &tokens[1..tokens.len() - 1];
+    let mut depth = 0;
+    for (i, t) in inner.iter().enumerate() {
+        match t.to_string().as_str() {
+            "(" | "[" | "{" => depth += 1,
+            ")" | "]" | "}" => depth -= 1,
+            ":" if depth == 0 => return Some((&inner[..i], &inner[i + 1..])),
+            _ => (),
+        }
+    }
+    None
+}
+
+/// Translates a Verilog packed range `[l:r]` into a VHDL range.
+fn verilog_range_to_vhdl(tokens: &[SystemVerilogToken]) -> Option<Range> {
+    let (left, right) = split_verilog_range(tokens)?;
+    let left = verilog_expr_to_vhdl(left)?;
+    let right = verilog_expr_to_vhdl(right)?;
+    // only ascending when both bounds are known to be increasing
+    let descending = match (left.parse::<i64>(), right.parse::<i64>()) {
+        (Ok(l), Ok(r)) => l >= r,
+        _ => true,
+    };
+    Some(Range {
+        left: left,
+        right: right,
+        descending: descending,
+    })
+}
+
+/// Determines the VHDL type of a parameter.
+fn verilog_param_to_vhdl(port: &Port) -> Option<String> {
+    if let Some(r) = port.get_range() {
+        let range = verilog_range_to_vhdl(r)?;
+        return Some(match port.is_signed() {
+            true => format!("signed{}", range.to_vhdl()),
+            false => format!("std_logic_vector{}", range.to_vhdl()),
+        });
+    }
+    match port.get_data_type() {
+        Some(t) => match t.as_keyword()? {
+            SvKeyword::Integer | SvKeyword::Int => Some(String::from("integer")),
+            SvKeyword::Real | SvKeyword::Shortreal | SvKeyword::Realtime => {
+                Some(String::from("real"))
+            }
+            SvKeyword::String => Some(String::from("string")),
+            SvKeyword::Bit | SvKeyword::Logic => Some(String::from("std_logic")),
+            _ => None,
+        },
+        // infer the type from the default value when no type is given
+        None => match port.get_default() {
+            Some(v) => match v.first() {
+                Some(SystemVerilogToken::StringLiteral(_)) => Some(String::from("string")),
+                Some(SystemVerilogToken::Number(n)) if n.to_string().contains('.') => {
+                    Some(String::from("real"))
+                }
+                _ => Some(String::from("integer")),
+            },
+            None => Some(String::from("integer")),
+        },
+    }
+}
+
+/// Determines the VHDL type of a port.
+fn verilog_port_to_vhdl(port: &Port) -> Option<String> {
+    if port.get_unpacked_range().is_some() {
+        return None;
+    }
+    let is_logic = match port.get_data_type() {
+        Some(t) => match t.as_keyword() {
+            Some(SvKeyword::Logic) | Some(SvKeyword::Bit) => true,
+            Some(SvKeyword::Integer) | Some(SvKeyword::Int) => {
+                return match port.get_range() {
+                    Some(_) => None,
+                    None => Some(String::from("integer")),
+                }
+            }
+            _ => false,
+        },
+        None => true,
+    };
+    if is_logic == false {
+        return None;
+    }
+    match port.get_range() {
+        Some(r) => {
+            let range = verilog_range_to_vhdl(r)?;
+            Some(match port.is_signed() {
+                true => format!("signed{}", range.to_vhdl()),
+                false => format!("std_logic_vector{}", range.to_vhdl()),
+            })
+        }
+        None => Some(String::from("std_logic")),
+    }
+}
+
+/// Translates a Verilog expression into a VHDL expression.
+///
+/// Returns `None` if the expression uses constructs that do not have a
+/// direct equivalent.
+fn verilog_expr_to_vhdl(tokens: &[SystemVerilogToken]) -> Option<String> {
+    let mut result = Vec::new();
+    for t in tokens {
+        result.push(match t {
+            SystemVerilogToken::Identifier(id) => {
+                let name = id.to_string();
+                // system functions ($clog2) and escaped names have no equivalent
+                match name.starts_with('$') || name.starts_with('\\') {
+                    true => return None,
+                    false => name,
+                }
+            }
+            SystemVerilogToken::Number(n) => {
+                let text = n.to_string().replace('_', "");
+                match text.split_once('\'') {
+                    Some((size, value)) => {
+                        let mut chars = value.chars();
+                        let mut spec = chars.next()?.to_ascii_lowercase();
+                        // skip the signed specifier
+                        if spec == 's' {
+                            spec = chars.next()?.to_ascii_lowercase();
+                        }
+                        let digits: String = chars.collect();
+                        let base = match spec {
+                            'b' => 'b',
+                            'o' => 'o',
+                            'h' => 'x',
+                            'd' => 'd',
+                            _ => return None,
+                        };
+                        // keep the width as a sized bit string literal (VHDL-2008)
+                        match (size.trim().is_empty(), base) {
+                            (true, 'd') => digits,
+                            (true, _) => format!("{}\"{}\"", base, digits),
+                            (false, _) => format!("{}{}\"{}\"", size.trim(), base, digits),
+                        }
+                    }
+                    None => text,
+                }
+            }
+            SystemVerilogToken::StringLiteral(s) => format!("\"{}\"", s),
+            SystemVerilogToken::Operator(_) => match t.to_string().as_str() {
+                "+" | "-" | "*" | "/" | "**" | "(" | ")" => t.to_string(),
+                "%" => String::from("mod"),
+                _ => return None,
+            },
+            _ => return None,
+        });
+    }
+    match result.is_empty() {
+        true => None,
+        false => Some(result.join(" ")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::lang::sv::format::SystemVerilogFormat;
+    use crate::core::lang::sv::token::tokenizer::SystemVerilogTokenizer;
+    use crate::core::lang::vhdl::format::VhdlFormat;
+
+    fn parse_entity(s: &str) -> Entity {
+        VHDLParser::read(s)
+            .unwrap()
+            .into_symbols()
+            .into_iter()
+            .find_map(|s| s.into_entity())
+            .unwrap()
+    }
+
+    fn parse_module(s: &str) -> Module {
+        SystemVerilogParser::read(s)
+            .unwrap()
+            .into_symbols()
+            .into_iter()
+            .find_map(|s| s.into_module())
+            .unwrap()
+    }
+
+    #[test]
+    fn vhdl_to_verilog() {
+        let ent = parse_entity(
+            r#"entity fifo is
+    generic (
+        WIDTH : positive := 8;
+        MODE : string := "fast";
+        INIT : std_logic_vector(3 downto 0) := x"A"
+    );
+    port (
+        clk : in std_logic;
+        data : in std_logic_vector(WIDTH-1 downto 0);
+        count : out unsigned(3 downto 0);
+        level : out signed(7 downto 0)
+    );
+end entity;"#,
+        );
+        let tr = entity_to_module(&ent, &Lang::Verilog).unwrap();
+        assert_eq!(tr.get_warnings().len(), 0);
+        assert_eq!(
+            tr.get_unit().into_declaration(&SystemVerilogFormat::new()),
+            r#"module fifo #(
+  parameter integer WIDTH = 8,
+  parameter MODE = "fast",
+  parameter[3:0] INIT = 4'hA
+) (
+  input wire clk,
+  input wire[WIDTH-1:0] data,
+  output wire[3:0] count,
+  output wire signed[7:0] level
+);"#
+        );
+    }
+
+    #[test]
+    fn vhdl_to_verilog_untranslatable() {
+        let ent = parse_entity(
+            r#"entity ctrl is
+    generic ( DELAY : time := 10 ns );
+    port ( state : out state_t; reg : in std_logic );
+end entity;"#,
+        );
+        let tr = entity_to_module(&ent, &Lang::SystemVerilog).unwrap();
+        assert_eq!(tr.get_warnings().len(), 4);
+        assert_eq!(tr.get_unit().get_ports().len(), 2);
+    }
+
+    #[test]
+    fn verilog_to_vhdl() {
+        let module = parse_module(
+            r#"module adder #(parameter WIDTH = 8, parameter [7:0] INIT = 8'hFF) (
+    input logic clk,
+    input logic [WIDTH-1:0] a,
+    input signed [0:3] b,
+    output reg [WIDTH:0] sum
+);
+endmodule"#,
+        );
+        let tr = module_to_entity(&module).unwrap();
+        assert_eq!(tr.get_warnings().len(), 0);
+        assert_eq!(
+            tr.get_unit().into_component(&VhdlFormat::new()),
+            r#"component adder
+  generic(
+    WIDTH : integer := 8;
+    INIT  : std_logic_vector(7 downto 0) := 8x"FF"
+  );
+  port(
+    clk : in std_logic;
+    a   : in std_logic_vector(WIDTH-1 downto 0);
+    b   : in signed(0 to 3);
+    sum : out std_logic_vector(WIDTH downto 0)
+  );
+end component;
+"#
+        );
+    }
+
+    #[test]
+    fn verilog_to_vhdl_untranslatable() {
+        let module = parse_module(
+            r#"module bus_ctrl #(parameter DEPTH = $clog2(16)) (
+    axi_if.master bus,
+    input logic [3:0] mem [0:7],
+    input logic in
+);
+endmodule"#,
+        );
+        let tr = module_to_entity(&module).unwrap();
+        assert_eq!(tr.get_warnings().len(), 5);
+        assert_eq!(tr.get_unit().get_ports().len(), 3);
+    }
+
+    #[test]
+    fn vhdl_to_verilog_escaped_names() {
+        let ent = parse_entity(
+            r#"entity wire is
+    port ( \data bus\ : in std_logic; reg : out std_logic );
+end entity;"#,
+        );
+        let tr = entity_to_module(&ent, &Lang::Verilog).unwrap();
+        assert_eq!(tr.get_warnings().len(), 3);
+        assert_eq!(tr.get_unit().get_name().to_string(), "\\wire");
+        assert_eq!(
+            tr.get_unit()
+                .get_ports()
+                .iter()
+                .map(|p| p.get_name().to_string())
+                .collect::<Vec<String>>(),
+            vec!["\\data_bus", "\\reg"]
+        );
+    }
+
+    #[test]
+    fn verilog_to_vhdl_sized_literals() {
+        let lit = |code: &str| {
+            let tokens: Vec<SystemVerilogToken> = SystemVerilogTokenizer::from_source_code(code)
+                .into_tokens()
+                .into_iter()
+                .map(|t| t.take())
+                .filter(|t| t.is_eof() == false)
+                .collect();
+            verilog_expr_to_vhdl(&tokens)
+        };
+        assert_eq!(lit("5'h1F"), Some(String::from("5x\"1F\"")));
+        assert_eq!(lit("12'd5"), Some(String::from("12d\"5\"")));
+        assert_eq!(lit("4'sb1010"), Some(String::from("4b\"1010\"")));
+        assert_eq!(lit("6'o7_7"), Some(String::from("6o\"77\"")));
+        // unsized literals have no width to keep
+        assert_eq!(lit("'hFF"), Some(String::from("x\"FF\"")));
+        assert_eq!(lit("'d5"), Some(String::from("5")));
+        assert_eq!(lit("16 + 1 "), Some(String::from("16 + 1")));
+
+        // the literal is read back with its width
+        let module = parse_module(
+            r#"module rom #(parameter [4:0] ADDR = 5'h1F, parameter [11:0] LEN = 12'd5) (
+    input logic clk
+);
+endmodule"#,
+        );
+        let tr = module_to_entity(&module).unwrap();
+        assert_eq!(tr.get_warnings().len(), 0);
+        assert_eq!(
+            tr.get_unit().into_component(&VhdlFormat::new()),
+            r#"component rom
+  generic(
+    ADDR : std_logic_vector(4 downto 0) := 5x"1F";
+    LEN  : std_logic_vector(11 downto 0) := 12d"5"
+  );
+  port(
+    clk : in std_logic
+  );
+end component;
+"#
+        );
+    }
+
+    #[test]
+    fn verilog_to_vhdl_extended_names() {
+        let module = parse_module(
+            r#"module mux (
+    input logic a$b,
+    input logic c__d,
+    input logic e_,
+    input logic \f+g ,
+    output logic y
+);
+endmodule"#,
+        );
+        let tr = module_to_entity(&module).unwrap();
+        assert_eq!(tr.get_warnings().len(), 4);
+        assert_eq!(
+            tr.get_unit()
+                .get_ports()
+                .0
+                .iter()
+                .map(|p| p.get_identifier().to_string())
+                .collect::<Vec<String>>(),
+            vec!["\\a$b\\", "\\c__d\\", "\\e_\\", "\\f+g\\", "y"]
+        );
+    }
+
+    #[test]
+    fn basic_identifiers() {
+        assert_eq!(is_vhdl_basic_identifier("data_in"), true);
+        assert_eq!(is_vhdl_basic_identifier("data__in"), false);
+        assert_eq!(is_vhdl_basic_identifier("data_"), false);
+        assert_eq!(is_vhdl_basic_identifier("_data"), false);
+        assert_eq!(is_vhdl_basic_identifier("a$b"), false);
+        assert_eq!(is_verilog_simple_identifier("_data$0"), true);
+        assert_eq!(is_verilog_simple_identifier("$data"), false);
+        assert_eq!(is_verilog_simple_identifier("0data"), false);
+    }
+}
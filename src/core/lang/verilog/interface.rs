@@ -48,7 +48,8 @@ pub fn does_exist(ports: &Vec<Port>, name: &Identifier) -> bool {
         .is_some()
 }
 
-fn tokens_to_string(tokens: &Vec<SystemVerilogToken>) -> String {
+/// Writes the tokens as text with the spacing used in declarations.
+pub fn tokens_to_string(tokens: &Vec<SystemVerilogToken>) -> String {
     let mut result = String::new();
     // determine which delimiters to not add trailing spaces to
     let is_spaced_token = |d: &Operator| match d {
@@ -677,6 +678,107 @@ impl Port {
         &self.name
     }
 
+    /// Checks if the parameter is a `localparam`, which cannot be overridden.
+    pub fn is_localparam(&self) -> bool {
+        self.is_param == true && self.mode == Some(Keyword::Localparam)
+    }
+
+    /// Accesses the token naming the port's data type, if one was declared.
+    pub fn get_data_type(&self) -> Option<&SystemVerilogToken> {
+        self.data_type.data.as_ref()
+    }
+
+    /// Accesses the tokens of the port's packed range (`[7:0]`), if one was declared.
+    pub fn get_range(&self) -> Option<&Vec<SystemVerilogToken>> {
+        self.data_type.range.0.as_ref()
+    }
+
+    /// Accesses the tokens of the port's unpacked range, if one was declared.
+    pub fn get_unpacked_range(&self) -> Option<&Vec<SystemVerilogToken>> {
+        self.unpacked_range.0.as_ref()
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.data_type.is_signed
+    }
+
+    /// Accesses the tokens of the port's default value, if one was declared.
+    pub fn get_default(&self) -> Option<&Vec<SystemVerilogToken>> {
+        self.value.0.as_ref()
+    }
+
+    /// Returns the port's direction, which is `input` when it is omitted.
+    pub fn get_direction(&self) -> Keyword {
+        self.mode.clone().unwrap_or(Keyword::Input)
+    }
+
+    pub fn has_default(&self) -> bool {
+        self.value.0.is_some()
+    }
+
+    /// Checks if the port carries a single bit of data.
+    pub fn is_single_bit(&self) -> bool {
+        let is_bit_type = match &self.data_type.data {
+            Some(t) => t.check_keyword(&Keyword::Logic) || t.check_keyword(&Keyword::Bit),
+            None => true,
+        };
+        is_bit_type
+            && self.data_type.range.0.is_none()
+            && self.unpacked_range.0.is_none()
+            && self.data_type.modport.is_none()
+    }
+
+    /// Creates a declaration for a testbench variable that connects to the port.
+    ///
+    /// Ports without an explicit data type are declared as `logic` when `is_sv`
+    /// is true. Otherwise, inputs are declared as `reg` and all other ports as `wire`.
+    pub fn into_testbench_declaration(&self, is_sv: bool, fmt: &SystemVerilogFormat) -> String {
+        let mut result = String::new();
+
+        match &self.data_type.data {
+            Some(d) => {
+                result.push_str(&d.to_string());
+                if let Some(t) = &self.data_type.nested_type {
+                    result.push_str(&Operator::ScopeResolution.to_string());
+                    result.push_str(&t.to_string());
+                }
+            }
+            None => {
+                let kw = match is_sv {
+                    true => Keyword::Logic,
+                    false => match self.get_direction() {
+                        Keyword::Input => Keyword::Reg,
+                        _ => Keyword::Wire,
+                    },
+                };
+                result.push_str(&kw.to_string());
+            }
+        }
+
+        if self.data_type.is_signed == true {
+            result.push(' ');
+            result.push_str(&Keyword::Signed.to_string());
+        }
+
+        if let Some(r) = &self.data_type.range.0 {
+            for _ in 0..fmt.get_range_offset() as usize {
+                result.push(' ');
+            }
+            result.push_str(&tokens_to_string(r));
+        }
+
+        result.push(' ');
+        result.push_str(&self.name.to_string());
+
+        if let Some(up) = &self.unpacked_range.0 {
+            for _ in 0..fmt.get_range_offset() as usize {
+                result.push(' ');
+            }
+            result.push_str(&tokens_to_string(up));
+        }
+        result
+    }
+
     pub fn as_user_defined_data_type(&self) -> Option<&Identifier> {
         match &self.data_type.data {
             Some(t) => match t.as_identifier() {
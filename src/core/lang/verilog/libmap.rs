@@ -0,0 +1,205 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::util::filesystem;
+use glob::{MatchOptions, Pattern};
+use std::path::{Path, PathBuf};
+
+/// The file at the root of an ip that assigns its Verilog source files to libraries.
+pub const LIBRARY_MAP_FILE: &str = "lib.map";
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// A library declaration from a library map file.
+#[derive(Debug, PartialEq)]
+struct Library {
+    name: String,
+    patterns: Vec<Pattern>,
+}
+
+/// The library declarations of a Verilog-2001 library map file.
+///
+/// Each declaration follows the form `library <name> <file_path_spec> {, <file_path_spec>};`,
+/// where the paths are relative to the map file and may use the `*`, `?`, and `...`
+/// wildcards. Other map files can be read with `include <file_path_spec>;`.
+#[derive(Debug, PartialEq)]
+pub struct LibraryMap {
+    libraries: Vec<Library>,
+}
+
+impl LibraryMap {
+    /// Reads the library map file at the `root` of an ip, if one exists.
+    pub fn load(root: &Path) -> Option<Self> {
+        let path = root.join(LIBRARY_MAP_FILE);
+        match path.exists() {
+            true => {
+                let mut map = Self {
+                    libraries: Vec::new(),
+                };
+                map.read(&path, 0);
+                Some(map)
+            }
+            false => None,
+        }
+    }
+
+    /// Returns the name of the library that the `file` is mapped to, if any.
+    ///
+    /// A path specification without wildcards takes precedence over one with
+    /// wildcards. Otherwise, the first library declared to match the file is used.
+    pub fn lookup(&self, file: &str) -> Option<&str> {
+        let mut found = None;
+        for lib in &self.libraries {
+            for pattern in &lib.patterns {
+                if pattern.matches_with(file, MATCH_OPTIONS) == false {
+                    continue;
+                }
+                if pattern.as_str() == file {
+                    return Some(&lib.name);
+                }
+                if found.is_none() == true {
+                    found = Some(lib.name.as_str());
+                }
+            }
+        }
+        found
+    }
+
+    fn read(&mut self, path: &PathBuf, depth: usize) {
+        // guard against include cycles
+        if depth > 16 {
+            return;
+        }
+        let text = match std::fs::read_to_string(path) {
+            Ok(t) => t,
+            Err(_) => return,
+        };
+        let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        for stmt in Self::into_statements(&text) {
+            let mut words = stmt.iter();
+            match words.next().map(|w| w.as_str()) {
+                Some("library") => {
+                    let name = match words.next() {
+                        Some(n) => n.clone(),
+                        None => continue,
+                    };
+                    let patterns = words
+                        .take_while(|w| w.starts_with('-') == false)
+                        .filter_map(|w| Pattern::new(&Self::resolve(&dir, w)).ok())
+                        .collect();
+                    self.libraries.push(Library { name, patterns });
+                }
+                Some("include") => {
+                    if let Some(w) = words.next() {
+                        self.read(&PathBuf::from(Self::resolve(&dir, w)), depth + 1);
+                    }
+                }
+                // configurations within a map file are not supported
+                _ => (),
+            }
+        }
+    }
+
+    /// Translates a file path specification into a glob pattern relative to `dir`.
+    fn resolve(dir: &PathBuf, spec: &str) -> String {
+        let mut spec = spec.replace("...", "**");
+        // a trailing separator includes every file in the directory
+        if spec.ends_with('/') == true {
+            spec.push('*');
+        }
+        let path = match Path::new(&spec).is_absolute() {
+            true => PathBuf::from(spec),
+            false => dir.join(spec),
+        };
+        filesystem::into_std_str(path)
+    }
+
+    /// Splits the text into statements of words, ignoring comments.
+    fn into_statements(text: &str) -> Vec<Vec<String>> {
+        let mut code = String::with_capacity(text.len());
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            // comments only begin at the start of a word so paths like `rtl/*.v` are kept
+            let at_word = match code.chars().last() {
+                Some(p) => p.is_whitespace() || p == ';' || p == ',',
+                None => true,
+            };
+            match (c, chars.peek()) {
+                ('/', Some('/')) if at_word == true => {
+                    while let Some(n) = chars.next() {
+                        if n == '\n' {
+                            code.push('\n');
+                            break;
+                        }
+                    }
+                }
+                ('/', Some('*')) if at_word == true => {
+                    chars.next();
+                    let mut prev = ' ';
+                    while let Some(n) = chars.next() {
+                        if prev == '*' && n == '/' {
+                            break;
+                        }
+                        prev = n;
+                    }
+                    code.push(' ');
+                }
+                _ => code.push(c),
+            }
+        }
+        code.split(';')
+            .map(|s| {
+                s.split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|w| w.is_empty() == false)
+                    .map(|w| w.to_string())
+                    .collect::<Vec<String>>()
+            })
+            .filter(|s| s.is_empty() == false)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn lookup_libraries() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        std::fs::write(
+            root.join(LIBRARY_MAP_FILE),
+            "// cells for each technology\nlibrary lib_a rtl/a/*.v, rtl/common.v;\nlibrary lib_b rtl/.../*.v -incdir rtl/inc;\n/* more\nlibraries */ include extra.map;\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("extra.map"), "library lib_c gates/;\n").unwrap();
+
+        let map = LibraryMap::load(&root).unwrap();
+        let file = |f: &str| filesystem::into_std_str(root.join(f));
+        assert_eq!(map.lookup(&file("rtl/a/cell.v")), Some("lib_a"));
+        assert_eq!(map.lookup(&file("rtl/b/cell.v")), Some("lib_b"));
+        // an explicit path is preferred over a wildcard match
+        assert_eq!(map.lookup(&file("rtl/common.v")), Some("lib_a"));
+        assert_eq!(map.lookup(&file("gates/nand.v")), Some("lib_c"));
+        assert_eq!(map.lookup(&file("top.v")), None);
+    }
+}
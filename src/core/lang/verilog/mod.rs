@@ -18,6 +18,7 @@
 pub mod dst;
 pub mod error;
 pub mod interface;
+pub mod libmap;
 pub mod primaryunit;
 pub mod symbols;
 pub mod token;
@@ -24,9 +24,47 @@ use crate::core::lang::{
     verilog::{error::VerilogError, token::identifier::Identifier},
 };
 
+/// A cell name with an optional library, written as `[lib.]cell`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct CellId {
+    lib: Option<Identifier>,
+    cell: Identifier,
+}
+
+impl CellId {
+    pub fn get_lib(&self) -> Option<&Identifier> {
+        self.lib.as_ref()
+    }
+
+    pub fn get_cell(&self) -> &Identifier {
+        &self.cell
+    }
+}
+
+/// Selects which design element is bound to a cell or instance.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Binding {
+    /// Binds a specific cell (`use [lib.]cell`).
+    Use(CellId),
+    /// Searches the libraries in order for the cell (`liblist lib ...`).
+    Liblist(Vec<Identifier>),
+}
+
+/// A configuration rule statement.
+#[derive(Debug, PartialEq)]
+pub enum Rule {
+    /// Applies to the instance at the hierarchical path (`instance top.u1 ...`).
+    Instance(Vec<Identifier>, Binding),
+    /// Applies to every instance of the cell (`cell [lib.]cell ...`).
+    Cell(CellId, Binding),
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Config {
     name: Identifier,
+    design: Vec<CellId>,
+    default: Option<Vec<Identifier>>,
+    rules: Vec<Rule>,
     refs: RefSet,
     pos: Position,
 }
@@ -47,6 +85,39 @@ impl Config {
     pub fn extend_refs(&mut self, refs: RefSet) {
         self.refs.extend(refs);
     }
+
+    /// Accesses the top-level cells of the design.
+    pub fn get_design(&self) -> &Vec<CellId> {
+        &self.design
+    }
+
+    /// Accesses the libraries to search when no rule applies, if set.
+    pub fn get_default_liblist(&self) -> Option<&Vec<Identifier>> {
+        self.default.as_ref()
+    }
+
+    /// Returns the binding for the instance at `path` that instantiates `cell`, if
+    /// a rule exists for it.
+    ///
+    /// Instance rules take precedence over cell rules.
+    pub fn find_binding(&self, path: &[Identifier], cell: &Identifier) -> Option<&Binding> {
+        let inst_rule = self.rules.iter().find_map(|r| match r {
+            Rule::Instance(p, b) => match p.as_slice() == path {
+                true => Some(b),
+                false => None,
+            },
+            _ => None,
+        });
+        inst_rule.or_else(|| {
+            self.rules.iter().find_map(|r| match r {
+                Rule::Cell(c, b) => match c.get_cell() == cell {
+                    true => Some(b),
+                    false => None,
+                },
+                _ => None,
+            })
+        })
+    }
 }
 
 impl Config {
@@ -64,6 +135,9 @@ impl Config {
         tokens.next().take().unwrap();
 
         let mut refs = RefSet::new();
+        let mut design = Vec::new();
+        let mut default = None;
+        let mut rules = Vec::new();
 
         // parse until finding `endconfig`
         while let Some(t) = tokens.next() {
@@ -74,35 +148,183 @@ impl Config {
                 break;
             // parse to try to find a module name
             } else if t.as_type().check_keyword(&Keyword::Design) {
-                refs.extend(Self::parse_design_statement(tokens)?);
+                let stmt = Self::parse_statement(tokens)?;
+                let mut stmt = stmt.into_iter().peekable();
+                while stmt.peek().is_some() {
+                    if let Some(cell) = Self::parse_cell_id(&mut stmt) {
+                        design.push(cell);
+                    }
+                }
+            } else if t.as_type().check_keyword(&Keyword::Default) {
+                let stmt = Self::parse_statement(tokens)?;
+                if let Some(Binding::Liblist(libs)) = Self::parse_binding(&mut stmt.into_iter()) {
+                    default = Some(libs);
+                }
+            } else if t.as_type().check_keyword(&Keyword::Instance) {
+                let mut stmt = Self::parse_statement(tokens)?.into_iter().peekable();
+                let mut path = Vec::new();
+                while let Some(SystemVerilogToken::Identifier(_)) = stmt.peek() {
+                    path.push(stmt.next().unwrap().take_identifier().unwrap());
+                    // continue along the hierarchical path
+                    match stmt.peek() {
+                        Some(SystemVerilogToken::Operator(Operator::Dot)) => stmt.next(),
+                        _ => break,
+                    };
+                }
+                if let Some(binding) = Self::parse_binding(&mut stmt) {
+                    rules.push(Rule::Instance(path, binding));
+                }
+            } else if t.as_type().check_keyword(&Keyword::Cell) {
+                let mut stmt = Self::parse_statement(tokens)?.into_iter().peekable();
+                if let Some(cell) = Self::parse_cell_id(&mut stmt) {
+                    if let Some(binding) = Self::parse_binding(&mut stmt) {
+                        rules.push(Rule::Cell(cell, binding));
+                    }
+                }
             }
         }
 
+        // reference the cells that are directly selected by the configuration
+        design
+            .iter()
+            .chain(rules.iter().filter_map(|r| match r {
+                Rule::Instance(_, Binding::Use(c)) | Rule::Cell(_, Binding::Use(c)) => Some(c),
+                _ => None,
+            }))
+            .for_each(|c| {
+                refs.insert(CompoundIdentifier::new_minimal_verilog(
+                    c.get_cell().clone(),
+                ));
+            });
+
         Ok(Config {
             name: config_name,
+            design: design,
+            default: default,
+            rules: rules,
             refs: refs,
             pos: pos,
         })
     }
 
-    fn parse_design_statement<I>(tokens: &mut Peekable<I>) -> Result<RefSet, VerilogError>
+    /// Collects the tokens up until the `;` terminator, which is consumed.
+    fn parse_statement<I>(tokens: &mut Peekable<I>) -> Result<Vec<SystemVerilogToken>, VerilogError>
     where
         I: Iterator<Item = Token<SystemVerilogToken>>,
     {
-        let mut refs = RefSet::new();
+        let mut stmt = Vec::new();
         while let Some(t) = tokens.next() {
-            if t.as_type().check_delimiter(&Operator::Terminator) {
+            if t.as_type().is_eof() == true {
+                return Err(VerilogError::ExpectingOperator(Operator::Terminator));
+            } else if t.as_type().check_delimiter(&Operator::Terminator) {
                 break;
-            } else if let Some(name) = t.take().take_identifier() {
-                // skip the library name if this is what we are looking at
-                if let Some(t_next) = tokens.peek() {
-                    if t_next.as_type().check_delimiter(&Operator::Dot) == true {
-                        continue;
-                    }
+            }
+            stmt.push(t.take());
+        }
+        Ok(stmt)
+    }
+
+    /// Parses a `[lib.]cell` name, consuming an optional `:config` suffix.
+    fn parse_cell_id<I>(tokens: &mut Peekable<I>) -> Option<CellId>
+    where
+        I: Iterator<Item = SystemVerilogToken>,
+    {
+        let first = tokens.next()?.take_identifier()?;
+        let cell = match tokens.peek() {
+            Some(SystemVerilogToken::Operator(Operator::Dot)) => {
+                tokens.next();
+                CellId {
+                    lib: Some(first),
+                    cell: tokens.next()?.take_identifier()?,
                 }
-                refs.insert(CompoundIdentifier::new_minimal_verilog(name));
             }
+            _ => CellId {
+                lib: None,
+                cell: first,
+            },
+        };
+        // hierarchical configurations are bound as their cell
+        if let Some(SystemVerilogToken::Operator(Operator::Colon)) = tokens.peek() {
+            tokens.next();
+            tokens.next();
+        }
+        Some(cell)
+    }
+
+    /// Parses a `use` or `liblist` clause.
+    fn parse_binding<I>(tokens: &mut I) -> Option<Binding>
+    where
+        I: Iterator<Item = SystemVerilogToken>,
+    {
+        match tokens.next()? {
+            SystemVerilogToken::Keyword(Keyword::Use) => {
+                Some(Binding::Use(Self::parse_cell_id(&mut tokens.peekable())?))
+            }
+            SystemVerilogToken::Keyword(Keyword::Liblist) => Some(Binding::Liblist(
+                tokens.filter_map(|t| t.take_identifier()).collect(),
+            )),
+            _ => None,
         }
-        Ok(refs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::lang::sv::token::tokenizer::SystemVerilogTokenizer;
+    use std::str::FromStr;
+
+    #[test]
+    fn parse_config_rules() {
+        let code = r#"config cfg;
+    design lib_rtl.top;
+    default liblist lib_rtl lib_gate;
+    cell adder use lib_gate.adder;
+    instance top.u_mem liblist lib_sim;
+    instance top.u_alu use alu:cfg_alu;
+endconfig
+"#;
+        let mut tokens = SystemVerilogTokenizer::from_str(code)
+            .unwrap()
+            .into_tokens()
+            .into_iter()
+            .peekable();
+        // take the config keyword
+        tokens.next();
+        let cfg = Config::from_tokens(&mut tokens, Position::new()).unwrap();
+        let id = |s: &str| Identifier::from_str(s).unwrap();
+
+        assert_eq!(
+            cfg.get_design(),
+            &vec![CellId {
+                lib: Some(id("lib_rtl")),
+                cell: id("top")
+            }]
+        );
+        assert_eq!(
+            cfg.get_default_liblist(),
+            Some(&vec![id("lib_rtl"), id("lib_gate")])
+        );
+        // instance rules take precedence over cell rules
+        assert_eq!(
+            cfg.find_binding(&[id("top"), id("u_mem")], &id("adder")),
+            Some(&Binding::Liblist(vec![id("lib_sim")]))
+        );
+        assert_eq!(
+            cfg.find_binding(&[id("top"), id("u_add")], &id("adder")),
+            Some(&Binding::Use(CellId {
+                lib: Some(id("lib_gate")),
+                cell: id("adder")
+            }))
+        );
+        assert_eq!(
+            cfg.find_binding(&[id("top"), id("u_alu")], &id("alu")),
+            Some(&Binding::Use(CellId {
+                lib: None,
+                cell: id("alu")
+            }))
+        );
+        assert_eq!(cfg.find_binding(&[id("top"), id("u_x")], &id("mux")), None);
+        assert_eq!(cfg.get_refs().len(), 3);
     }
 }
@@ -22,6 +22,7 @@ use super::error::VerilogError;
 use super::interface::{self, ParamList};
 use super::token::identifier::Identifier;
 use super::token::tokenizer::VerilogTokenizer;
+use crate::core::lang::instance::{Association, Instance, Scope, ScopeKind};
 use crate::core::lang::lexer::{Position, Token};
 use crate::core::lang::parser::{Parse, Symbol};
 use crate::core::lang::reference::{CompoundIdentifier, RefSet};
@@ -89,6 +90,14 @@ impl VerilogSymbol {
         }
     }
 
+    /// Transforms `self` into module.
+    pub fn into_module(self) -> Option<Module> {
+        match self {
+            Self::Module(m) => Some(m),
+            _ => None,
+        }
+    }
+
     pub fn get_refs(&self) -> &RefSet {
         match &self {
             Self::Module(m) => m.get_refs(),
@@ -426,12 +435,15 @@ impl VerilogSymbol {
         tokens: &mut Peekable<I>,
         mut params: &mut ParamList,
         mut ports: &mut PortList,
-    ) -> Result<(RefSet, RefSet), VerilogError>
+    ) -> Result<(RefSet, RefSet, Vec<Instance<Identifier>>), VerilogError>
     where
         I: Iterator<Item = Token<SystemVerilogToken>>,
     {
         let mut refs = RefSet::new();
         let mut deps = RefSet::new();
+        let mut instances = Vec::new();
+        // the labeled generate blocks currently entered (`None` for other blocks)
+        let mut scopes: Vec<Option<Scope>> = Vec::new();
 
         while let Some(t) = tokens.next() {
             // expecting `endmodule`
@@ -442,6 +454,30 @@ impl VerilogSymbol {
                 break;
             } else if let Some(stmt) = Self::into_next_statement(t, tokens)? {
                 // println!("[arch]: {}", statement_to_string(&stmt));
+                // track the blocks that enclose instances
+                if stmt.first().unwrap().as_ref().check_keyword(&Keyword::End) == true {
+                    scopes.pop();
+                } else if stmt
+                    .iter()
+                    .any(|t| t.as_ref().check_keyword(&Keyword::Begin))
+                {
+                    scopes.push(Self::as_generate_scope(&stmt));
+                }
+                // remember the instances for resolving configurations and hierarchies
+                if let Some((dep, true)) = Self::as_module_instance(&stmt) {
+                    let params = Self::as_instance_params(&stmt);
+                    let within: Vec<Scope> = scopes.iter().filter_map(|s| s.clone()).collect();
+                    Self::as_instance_connections(&stmt)
+                        .into_iter()
+                        .for_each(|(i, ports)| {
+                            instances.push(
+                                Instance::new(dep.clone(), i.clone())
+                                    .generics(params.clone())
+                                    .ports(ports)
+                                    .scopes(within.clone()),
+                            );
+                        });
+                }
                 Self::handle_statement(
                     stmt,
                     Some(&mut params),
@@ -451,7 +487,7 @@ impl VerilogSymbol {
                 )?;
             }
         }
-        Ok((refs, deps))
+        Ok((refs, deps, instances))
     }
 
     pub fn into_next_statement<I>(
@@ -588,6 +624,19 @@ impl VerilogSymbol {
             refs.extend(s_refs);
         }
 
+        // try as a bind directive (not a dependency to keep it out of the design under test)
+        if let Some((target, dep)) = Self::as_bind_directive(&stmt) {
+            refs.insert(CompoundIdentifier::new_minimal_verilog(target.clone()));
+            refs.insert(CompoundIdentifier::new_minimal_verilog(dep.clone()));
+            return Ok(());
+        }
+
+        // try as a virtual interface declaration
+        if let Some(intf) = Self::as_virtual_interface(&stmt) {
+            refs.insert(CompoundIdentifier::new_minimal_verilog(intf.clone()));
+            return Ok(());
+        }
+
         // try as a module instantiation
         if let Some((dep, is_valid_mod)) = Self::as_module_instance(&stmt) {
             // println!("detected dependency! {}", dep);
@@ -713,6 +762,49 @@ impl VerilogSymbol {
         }
     }
 
+    /// Returns the target scope and the name of the module that is bound to it, if
+    /// this statement is a bind directive.
+    ///
+    /// The statement is expected to follow the form:
+    /// `bind <target> [: <instances>] <module> [#(<params>)] <instance> (<ports>);`
+    pub fn as_bind_directive(stmt: &Statement) -> Option<(&Identifier, &Identifier)> {
+        if stmt.first()?.as_ref().check_keyword(&Keyword::Bind) == false {
+            return None;
+        }
+        let target = stmt.get(1)?.as_ref().as_identifier()?;
+        // the bound module appears just before its parameters or its instance name
+        let i = stmt.iter().position(|t| {
+            t.as_ref().check_delimiter(&Operator::Pound)
+                || t.as_ref().check_delimiter(&Operator::ParenL)
+        })?;
+        let offset = match stmt.get(i)?.as_ref().check_delimiter(&Operator::Pound) {
+            true => 1,
+            false => 2,
+        };
+        if i < 2 + offset {
+            return None;
+        }
+        let dep = stmt.get(i - offset)?.as_ref().as_identifier()?;
+        Some((target, dep))
+    }
+
+    /// Returns the name of the interface that is declared as a virtual interface in
+    /// this statement, if one exists.
+    ///
+    /// The statement is expected to follow the form:
+    /// `virtual [interface] <interface>[.<modport>] [#(<params>)] <name>;`
+    pub fn as_virtual_interface(stmt: &Statement) -> Option<&Identifier> {
+        let mut tokens = stmt.iter();
+        if tokens.next()?.as_ref().check_keyword(&Keyword::Virtual) == false {
+            return None;
+        }
+        let mut next = tokens.next()?;
+        if next.as_ref().check_keyword(&Keyword::Interface) == true {
+            next = tokens.next()?;
+        }
+        next.as_ref().as_identifier()
+    }
+
     /// Returns the name of the module that is being instantiated in this statement, if
     /// one exists.
     fn as_module_instance(stmt: &Statement) -> Option<(&Identifier, bool)> {
@@ -803,6 +895,194 @@ impl VerilogSymbol {
         }
     }
 
+    /// Returns the name and port connections of every instance created by a
+    /// module instantiation statement.
+    fn as_instance_connections(stmt: &Statement) -> Vec<(&Identifier, Vec<Association>)> {
+        let mut instances: Vec<(&Identifier, Vec<Association>)> = Vec::new();
+        let mut depth = 0;
+        let mut expect_name = true;
+        // the tokens of the port list currently being read
+        let mut list: Option<Vec<&SystemVerilogToken>> = None;
+        // skip the module name
+        for t in stmt.iter().skip(1) {
+            let t = t.as_ref();
+            if t.check_delimiter(&Operator::ParenL) == true {
+                depth += 1;
+                if depth == 1 && expect_name == false && list.is_none() {
+                    list = Some(Vec::new());
+                    continue;
+                }
+            } else if t.check_delimiter(&Operator::ParenR) == true {
+                depth -= 1;
+                if depth == 0 {
+                    if let Some(ports) = list.take() {
+                        if let Some(inst) = instances.last_mut() {
+                            inst.1 = Self::into_associations(&ports);
+                        }
+                        continue;
+                    }
+                }
+            } else if depth == 0 {
+                if t.check_delimiter(&Operator::Comma) == true {
+                    expect_name = true;
+                } else if let Some(name) = t.as_identifier() {
+                    if expect_name == true {
+                        instances.push((name, Vec::new()));
+                        expect_name = false;
+                    }
+                }
+            }
+            if let Some(ports) = list.as_mut() {
+                ports.push(t);
+            }
+        }
+        instances
+    }
+
+    /// Returns the parameter associations of a module instantiation statement.
+    fn as_instance_params(stmt: &Statement) -> Vec<Association> {
+        let mut tokens = stmt.iter().skip(1).map(|t| t.as_ref()).peekable();
+        if tokens
+            .next()
+            .is_some_and(|t| t.check_delimiter(&Operator::Pound))
+            == false
+        {
+            return Vec::new();
+        }
+        // a single parameter value can be given without parentheses
+        if tokens
+            .peek()
+            .is_some_and(|t| t.check_delimiter(&Operator::ParenL))
+            == false
+        {
+            return match tokens.next() {
+                Some(t) => vec![Association::new(None, t.to_string())],
+                None => Vec::new(),
+            };
+        }
+        tokens.next();
+        let mut list = Vec::new();
+        let mut depth = 0;
+        for t in tokens {
+            if t.check_delimiter(&Operator::ParenL) == true {
+                depth += 1;
+            } else if t.check_delimiter(&Operator::ParenR) == true {
+                if depth == 0 {
+                    break;
+                }
+                depth -= 1;
+            }
+            list.push(t);
+        }
+        Self::into_associations(&list)
+    }
+
+    /// Splits the tokens within the parentheses of a parameter or port list into
+    /// its associations.
+    ///
+    /// A `.*` wildcard connection is kept as an association with the formal `*`.
+    fn into_associations(tokens: &[&SystemVerilogToken]) -> Vec<Association> {
+        let mut items: Vec<Vec<&SystemVerilogToken>> = vec![Vec::new()];
+        let mut depth = 0;
+        for t in tokens {
+            if t.check_delimiter(&Operator::ParenL) || t.check_delimiter(&Operator::ConcatL) {
+                depth += 1;
+            } else if t.check_delimiter(&Operator::ParenR) || t.check_delimiter(&Operator::ConcatR)
+            {
+                depth -= 1;
+            } else if depth == 0 && t.check_delimiter(&Operator::Comma) == true {
+                items.push(Vec::new());
+                continue;
+            }
+            items.last_mut().unwrap().push(t);
+        }
+        items
+            .into_iter()
+            .filter(|i| i.is_empty() == false)
+            .map(|i| match (i.get(0), i.get(1)) {
+                (Some(star), _) if star.check_delimiter(&Operator::DotStar) == true => {
+                    Association::new(Some(String::from("*")), String::new())
+                }
+                // named association: `.NAME(value)` or `.NAME`
+                (Some(dot), Some(name))
+                    if dot.check_delimiter(&Operator::Dot) == true
+                        && name.as_identifier().is_some() =>
+                {
+                    let actual = match i.len() {
+                        2 => name.to_string(),
+                        n if n > 3 => Self::into_text(&i[3..n - 1]),
+                        _ => String::new(),
+                    };
+                    Association::new(Some(name.to_string()), actual)
+                }
+                _ => Association::new(None, Self::into_text(&i)),
+            })
+            .collect()
+    }
+
+    /// Returns the labeled generate block entered by a statement ending with `begin`.
+    fn as_generate_scope(stmt: &Statement) -> Option<Scope> {
+        let tokens: Vec<&SystemVerilogToken> = stmt.iter().map(|t| t.as_ref()).collect();
+        // take the label following `begin :` or preceding `<label> : for/if/case`
+        let begin = tokens
+            .iter()
+            .position(|t| t.check_keyword(&Keyword::Begin))?;
+        let label = match (tokens.get(begin + 1), tokens.get(begin + 2)) {
+            (Some(c), Some(l)) if c.check_delimiter(&Operator::Colon) == true => l.as_identifier(),
+            _ => match (tokens.get(0), tokens.get(1)) {
+                (Some(l), Some(c)) if c.check_delimiter(&Operator::Colon) == true => {
+                    l.as_identifier()
+                }
+                _ => None,
+            },
+        }?;
+        match tokens.iter().position(|t| t.check_keyword(&Keyword::For)) {
+            Some(i) => {
+                let header = &tokens[i..begin];
+                // find the initial value and the final value of the loop variable
+                let left = header
+                    .iter()
+                    .position(|t| t.check_delimiter(&Operator::BlockAssign))
+                    .and_then(|j| header.get(j + 1))
+                    .and_then(|t| t.to_string().parse::<i64>().ok());
+                let right = header.iter().enumerate().find_map(|(j, t)| {
+                    let offset = match t {
+                        SystemVerilogToken::Operator(Operator::Lt) => -1,
+                        SystemVerilogToken::Operator(Operator::Gt) => 1,
+                        SystemVerilogToken::Operator(Operator::Lte)
+                        | SystemVerilogToken::Operator(Operator::Gte) => 0,
+                        _ => return None,
+                    };
+                    header
+                        .get(j + 1)
+                        .and_then(|t| t.to_string().parse::<i64>().ok())
+                        .map(|n| n + offset)
+                });
+                Some(Scope::with_bounds(label.to_string(), left, right))
+            }
+            None => Some(Scope::new(label.to_string(), ScopeKind::Branch)),
+        }
+    }
+
+    /// Writes the tokens back as source code text.
+    fn into_text(tokens: &[&SystemVerilogToken]) -> String {
+        let mut text = String::new();
+        let mut prev_word = false;
+        for t in tokens {
+            let word = t.to_string();
+            let is_word = word
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_');
+            if prev_word == true && is_word == true {
+                text.push(' ');
+            }
+            text.push_str(&word);
+            prev_word = is_word;
+        }
+        text
+    }
+
     /// Checks if this is special token to take a statement using parentheses
     fn is_start_to_parentheses_statement(t: &SystemVerilogToken) -> bool {
         match t {
@@ -18,6 +18,7 @@
 use super::super::super::vhdl::token::Identifier as VhdlIdentifier;
 use super::VerilogSymbol;
 use crate::core::lang::{
+    instance::Instance,
     lexer::{Position, Token},
     reference::{CompoundIdentifier, RefSet},
     sv::{
@@ -25,6 +26,7 @@ use crate::core::lang::{
         symbols::SystemVerilogSymbol,
         token::{keyword::Keyword, token::SystemVerilogToken},
     },
+    testbench::{self, PortRole},
     verilog::{
         error::VerilogError,
         interface::{self, ParamList, PortList},
@@ -48,6 +50,9 @@ pub struct Module {
     /// The set of references that were identified as components.
     #[serde(skip_serializing)]
     deps: RefSet,
+    /// The instances of other modules, in order of appearance.
+    #[serde(skip_serializing)]
+    instances: Vec<Instance<Identifier>>,
     #[serde(skip_serializing)]
     pos: Position,
     language: String,
@@ -66,6 +71,16 @@ impl Module {
         &self.refs
     }
 
+    /// Accesses the module's parameters.
+    pub fn get_parameters(&self) -> &ParamList {
+        &self.parameters
+    }
+
+    /// Accesses the module's ports.
+    pub fn get_ports(&self) -> &PortList {
+        &self.ports
+    }
+
     pub fn extend_refs(&mut self, refs: RefSet) {
         self.refs.extend(refs);
     }
@@ -166,11 +181,156 @@ impl Module {
     }
 }
 
+impl Module {
+    /// Generates a testbench that instantiates the module as the design under test.
+    ///
+    /// Single-bit inputs named like clocks and resets are driven by generator
+    /// blocks. All remaining inputs are left to the stimulus block.
+    pub fn into_testbench(
+        &self,
+        name: &Option<VhdlIdentifier>,
+        is_sv: bool,
+        fmt: &SystemVerilogFormat,
+    ) -> String {
+        let tab = fmt.get_tab_size() as usize;
+        let tb_name = format!("{}{}", self.name, testbench::TESTBENCH_SUFFIX);
+
+        // identify which inputs are clocks and resets
+        let roles: Vec<(&Identifier, PortRole)> = self
+            .ports
+            .iter()
+            .filter(|p| p.get_direction() == Keyword::Input && p.is_single_bit() == true)
+            .filter_map(|p| Some((p.get_name(), PortRole::classify(&p.get_name().to_string())?)))
+            .collect();
+        let is_timed = roles.is_empty() == false;
+
+        let mut result = String::new();
+        result.push_str("`timescale 1ns / 1ps\n");
+        result.push_str(&format!("\nmodule {};\n", tb_name));
+
+        if is_timed == true {
+            result.push_str(&format!(
+                "\n{:<tab$}localparam {} = 10;\n",
+                "",
+                testbench::CLK_PERIOD
+            ));
+        }
+
+        // declare the parameters as local constants
+        if self.parameters.is_empty() == false {
+            result.push('\n');
+        }
+        let param_spacer = match fmt.is_auto_name_aligned() {
+            true => Some(interface::longest_port_decl(false, &self.parameters, fmt)),
+            false => None,
+        };
+        self.parameters.iter().for_each(|p| {
+            if p.has_default() == false {
+                result.push_str(&format!(
+                    "{:<tab$}// @todo: set a value for parameter '{}'\n",
+                    "",
+                    p.get_name()
+                ));
+            }
+            result.push_str(&format!(
+                "{:<tab$}{}",
+                "",
+                p.into_declaration(false, &param_spacer, "", "", fmt)
+            ));
+            if p.has_default() == false {
+                result.push_str(" = 0");
+            }
+            result.push_str(&Operator::Terminator.to_string());
+            result.push('\n');
+        });
+
+        // declare the variables and nets that connect to the ports
+        if self.ports.is_empty() == false {
+            result.push('\n');
+        }
+        self.ports.iter().for_each(|p| {
+            result.push_str(&format!(
+                "{:<tab$}{}{}\n",
+                "",
+                p.into_testbench_declaration(is_sv, fmt),
+                Operator::Terminator
+            ));
+        });
+
+        // instantiate the design under test
+        result.push('\n');
+        result.push_str(&testbench::indent(
+            &self.into_instance(name, "", "", fmt),
+            tab,
+        ));
+        result.push('\n');
+
+        for (port, role) in &roles {
+            result.push('\n');
+            let lines = match role {
+                PortRole::Clock => vec![
+                    format!("// drive the clock for the entire simulation"),
+                    format!("initial {} = 1'b0;", port),
+                    format!(
+                        "always #({} / 2) {} = ~{};",
+                        testbench::CLK_PERIOD,
+                        port,
+                        port
+                    ),
+                ],
+                PortRole::Reset { active_low: _ } => vec![
+                    format!("// assert the reset for the first few clock cycles"),
+                    format!("initial begin"),
+                    format!("{:<tab$}{} = 1'b{};", "", port, role.active_level()),
+                    format!(
+                        "{:<tab$}#({} * {});",
+                        "",
+                        testbench::CLK_PERIOD,
+                        testbench::RESET_CYCLES
+                    ),
+                    format!("{:<tab$}{} = 1'b{};", "", port, role.inactive_level()),
+                    format!("end"),
+                ],
+            };
+            result.push_str(&testbench::indent(&lines.join("\n"), tab));
+            result.push('\n');
+        }
+
+        // stimulus block
+        let mut lines = vec![format!("initial begin")];
+        if is_timed == true {
+            lines.push(format!(
+                "{:<tab$}#({} * {});",
+                "",
+                testbench::CLK_PERIOD,
+                testbench::RESET_CYCLES + 1
+            ));
+        }
+        lines.push(format!(
+            "{:<tab$}// @todo: drive the inputs and check the outputs",
+            ""
+        ));
+        lines.push(String::new());
+        lines.push(format!("{:<tab$}$display(\"simulation complete\");", ""));
+        lines.push(format!("{:<tab$}$finish;", ""));
+        lines.push(format!("end"));
+        result.push('\n');
+        result.push_str(&testbench::indent(&lines.join("\n"), tab));
+        result.push_str("\n\nendmodule\n");
+        result
+    }
+}
+
 impl Module {
     pub fn get_deps(&self) -> &RefSet {
         &self.deps
     }
 
+    /// Accesses the instances of other modules within the module.
+    pub fn get_instances(&self) -> &Vec<Instance<Identifier>> {
+        &self.instances
+    }
+
     pub fn is_testbench(&self) -> bool {
         self.ports.is_empty()
     }
@@ -226,7 +386,7 @@ impl Module {
         refs.extend(d_refs);
 
         // parse the body of the module
-        let (b_refs, deps) =
+        let (b_refs, deps, instances) =
             VerilogSymbol::parse_module_architecture(tokens, &mut params, &mut ports)?;
         refs.extend(b_refs);
 
@@ -266,6 +426,7 @@ impl Module {
             ports: ports,
             refs: refs,
             deps: deps,
+            instances: instances,
             architectures: Vec::new(),
             pos: pos,
             language: String::from(language),
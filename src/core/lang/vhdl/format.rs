@@ -39,6 +39,26 @@ pub struct VhdlFormat {
     space_interface_parenthesis: Option<bool>,
     #[serde(rename = "instance-name")]
     instance_name: Option<String>,
+    #[serde(rename = "keyword-case")]
+    keyword_case: Option<KeywordCase>,
+}
+
+/// The letter case that keywords are rewritten to when formatting source code.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum KeywordCase {
+    Lower,
+    Upper,
+}
+
+impl KeywordCase {
+    /// Converts the `keyword` to this case.
+    pub fn apply(&self, keyword: &str) -> String {
+        match self {
+            Self::Lower => keyword.to_lowercase(),
+            Self::Upper => keyword.to_uppercase(),
+        }
+    }
 }
 
 impl VhdlFormat {
@@ -53,6 +73,7 @@ impl VhdlFormat {
             indent_interfaces: Some(true),
             space_interface_parenthesis: Some(false),
             instance_name: Some(String::from("uX")),
+            keyword_case: None,
         }
     }
 
@@ -95,6 +116,11 @@ impl VhdlFormat {
             .clone()
     }
 
+    /// Returns the case to rewrite keywords to, if any.
+    pub fn get_keyword_case(&self) -> Option<KeywordCase> {
+        self.keyword_case
+    }
+
     /// Merges any populated data from `rhs` into attributes that do not already
     /// have data defined in `self`.
     pub fn merge(&mut self, rhs: Option<Self>) -> () {
@@ -126,6 +152,9 @@ impl VhdlFormat {
             if self.instance_name.is_some() == false {
                 self.instance_name = rhs.instance_name
             }
+            if self.keyword_case.is_some() == false {
+                self.keyword_case = rhs.keyword_case
+            }
         }
     }
 }
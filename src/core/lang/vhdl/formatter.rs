@@ -0,0 +1,452 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use super::format::VhdlFormat;
+use super::token::delimiter::Delimiter;
+use super::token::tokenizer::VhdlTokenizer;
+use super::token::{Keyword, VhdlToken};
+use crate::core::lang::formatter::SourceFormatter;
+use crate::core::lang::lexer::Token;
+
+/// A region of code that is indented one level past its enclosing region.
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Frame {
+    Block,
+    Config,
+    If,
+    Case,
+    When,
+    Loop,
+    Generate,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ParenKind {
+    Interface,
+    Map,
+    Other,
+}
+
+/// An unclosed parenthesis.
+#[derive(Debug, PartialEq)]
+struct Paren {
+    base: usize,
+    group: usize,
+    kind: ParenKind,
+}
+
+/// Returns the first keyword of the statement, skipping over any label.
+fn first_keyword<'a>(head: &Vec<&'a VhdlToken>) -> Option<&'a Keyword> {
+    head.iter().find_map(|t| t.as_keyword())
+}
+
+/// Formats the VHDL `source` code.
+///
+/// Returns `None` if the formatted code would not consist of the same tokens as
+/// the original code.
+pub fn format(source: &str, fmt: &VhdlFormat) -> Option<String> {
+    let tokens = VhdlTokenizer::from_source_code(source).into_tokens_all();
+    let mut out = SourceFormatter::new();
+
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut parens: Vec<Paren> = Vec::new();
+    // the tokens of the statement being read
+    let mut head: Vec<&VhdlToken> = Vec::new();
+    // a frame was already opened by the statement being read
+    let mut pushed = false;
+    let mut after_end = false;
+    let mut in_when = false;
+    let mut groups = 0;
+
+    let mut line = 0;
+    let mut depth = 0;
+    let mut line_group: Option<usize> = None;
+    let mut anchored = false;
+
+    for (i, tkn) in tokens.iter().enumerate() {
+        let t = tkn.as_type();
+        let pos = tkn.locate();
+        if t.is_eof() == true {
+            break;
+        }
+        if pos.line() > line {
+            line = pos.line();
+            anchored = false;
+            line_group = parens.last().map(|p| p.group);
+            let d = frames.len();
+            let top = frames.last().copied();
+            depth = match parens.last() {
+                Some(p) => match t.check_delimiter(&Delimiter::ParenR) {
+                    true => p.base,
+                    false => p.base + 1,
+                },
+                None => match t.as_keyword() {
+                    Some(Keyword::End) if after_end == false => match top {
+                        Some(Frame::When) => d.saturating_sub(2),
+                        _ => d.saturating_sub(1),
+                    },
+                    Some(Keyword::Begin) => d.saturating_sub(1),
+                    Some(Keyword::Else) | Some(Keyword::Elsif)
+                        if head.is_empty() == true
+                            && (top == Some(Frame::If) || top == Some(Frame::Generate)) =>
+                    {
+                        d - 1
+                    }
+                    Some(Keyword::When) if head.is_empty() == true && top == Some(Frame::When) => {
+                        d - 1
+                    }
+                    // continue the statement from the previous line
+                    _ => match head.is_empty() == false && pushed == false {
+                        true => d + 1,
+                        false => d,
+                    },
+                },
+            };
+            out.indent(line, depth);
+        }
+
+        if let Some(case) = fmt.get_keyword_case() {
+            if let Some(kw) = t.as_keyword() {
+                out.replace(pos, case.apply(kw.as_str()));
+            }
+        }
+
+        // keep the inner lines of multi-line comments
+        if t.as_comment().is_some() == true {
+            let extra = t.to_string().matches('\n').count();
+            if extra == 0 {
+                out.keep_trailing(line);
+            }
+            for l in line + 1..=line + extra {
+                out.keep(l);
+            }
+            line += extra;
+            continue;
+        }
+
+        if after_end == true {
+            if t.check_delimiter(&Delimiter::Terminator) == true {
+                after_end = false;
+                head.clear();
+                pushed = false;
+            }
+            continue;
+        }
+
+        match t {
+            VhdlToken::Delimiter(Delimiter::ParenL) => {
+                let kind = match head.last().and_then(|h| h.as_keyword()) {
+                    Some(Keyword::Map) => ParenKind::Map,
+                    Some(Keyword::Port) | Some(Keyword::Generic) => ParenKind::Interface,
+                    _ => ParenKind::Other,
+                };
+                parens.push(Paren {
+                    base: depth,
+                    group: groups,
+                    kind: kind,
+                });
+                groups += 1;
+                head.push(t);
+            }
+            VhdlToken::Delimiter(Delimiter::ParenR) => {
+                parens.pop();
+                head.push(t);
+            }
+            VhdlToken::Delimiter(Delimiter::Colon) | VhdlToken::Delimiter(Delimiter::Arrow)
+                if parens.is_empty() == false =>
+            {
+                let paren = parens.last().unwrap();
+                // align the first mark of every line within the list
+                let offset = match (t, paren.kind) {
+                    (VhdlToken::Delimiter(Delimiter::Colon), ParenKind::Interface) => {
+                        match fmt.is_auto_type_aligned() {
+                            true => Some(fmt.get_type_offset()),
+                            false => None,
+                        }
+                    }
+                    (VhdlToken::Delimiter(Delimiter::Arrow), ParenKind::Map) => {
+                        match fmt.is_auto_mapping_aligned() {
+                            true => Some(fmt.get_mapping_offset()),
+                            false => None,
+                        }
+                    }
+                    _ => None,
+                };
+                if let Some(offset) = offset {
+                    if anchored == false && line_group == Some(paren.group) {
+                        out.align(pos, paren.group, t.to_string().len(), offset);
+                        anchored = true;
+                    }
+                }
+                head.push(t);
+            }
+            VhdlToken::Delimiter(Delimiter::Arrow) if in_when == true => {
+                frames.push(Frame::When);
+                in_when = false;
+                head.clear();
+                pushed = false;
+            }
+            VhdlToken::Delimiter(Delimiter::Terminator) if parens.is_empty() == true => {
+                head.clear();
+                pushed = false;
+            }
+            VhdlToken::Keyword(kw) if parens.is_empty() == true => match kw {
+                Keyword::End => {
+                    if frames.last() == Some(&Frame::When) {
+                        frames.pop();
+                    }
+                    frames.pop();
+                    after_end = true;
+                }
+                Keyword::Is => {
+                    let next = tokens.get(i + 1).map(|n: &Token<VhdlToken>| n.as_type());
+                    let is_new = next.is_some_and(|n| {
+                        n.check_keyword(&Keyword::New) || n.check_delimiter(&Delimiter::Box)
+                    });
+                    let frame = match (pushed || is_new, first_keyword(&head)) {
+                        (true, _) => None,
+                        (false, Some(Keyword::Case)) => Some(Frame::Case),
+                        (false, Some(Keyword::Configuration)) => Some(Frame::Config),
+                        (false, Some(k)) => match k {
+                            Keyword::Entity
+                            | Keyword::Architecture
+                            | Keyword::Package
+                            | Keyword::Context
+                            | Keyword::Function
+                            | Keyword::Procedure
+                            | Keyword::Pure
+                            | Keyword::Impure => Some(Frame::Block),
+                            _ => None,
+                        },
+                        (false, None) => None,
+                    };
+                    match (pushed, frame) {
+                        (_, Some(f)) => {
+                            frames.push(f);
+                            head.clear();
+                            pushed = false;
+                        }
+                        (true, None) => {
+                            head.clear();
+                            pushed = false;
+                        }
+                        (false, None) => head.push(t),
+                    }
+                }
+                Keyword::Begin => {
+                    head.clear();
+                    pushed = false;
+                }
+                Keyword::Then => {
+                    if first_keyword(&head) == Some(&Keyword::If) {
+                        frames.push(Frame::If);
+                    }
+                    head.clear();
+                    pushed = false;
+                }
+                Keyword::Loop => {
+                    frames.push(Frame::Loop);
+                    head.clear();
+                    pushed = false;
+                }
+                Keyword::Generate => {
+                    match first_keyword(&head) {
+                        Some(Keyword::For) | Some(Keyword::If) | Some(Keyword::Case) => {
+                            frames.push(Frame::Generate)
+                        }
+                        _ => (),
+                    }
+                    head.clear();
+                    pushed = false;
+                }
+                Keyword::Else if head.is_empty() == true => (),
+                Keyword::Record
+                | Keyword::Units
+                | Keyword::Protected
+                | Keyword::Process
+                | Keyword::Block => {
+                    frames.push(Frame::Block);
+                    pushed = true;
+                    head.push(t);
+                }
+                Keyword::Component if head.is_empty() == true => {
+                    frames.push(Frame::Block);
+                    pushed = true;
+                    head.push(t);
+                }
+                Keyword::For
+                    if head.is_empty() == true && frames.last() == Some(&Frame::Config) =>
+                {
+                    frames.push(Frame::Config);
+                    pushed = true;
+                    head.push(t);
+                }
+                Keyword::When
+                    if head.is_empty() == true
+                        && (frames.last() == Some(&Frame::Case)
+                            || frames.last() == Some(&Frame::When)) =>
+                {
+                    if frames.last() == Some(&Frame::When) {
+                        frames.pop();
+                    }
+                    in_when = true;
+                    head.push(t);
+                }
+                _ => head.push(t),
+            },
+            _ => head.push(t),
+        }
+    }
+
+    let result = out.apply(source, fmt.get_tab_size());
+    // verify the code itself is unchanged
+    let tokens_after = VhdlTokenizer::from_source_code(&result).into_tokens_all();
+    match tokens
+        .iter()
+        .map(|t| t.as_type())
+        .eq(tokens_after.iter().map(|t| t.as_type()))
+    {
+        true => Some(result),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::lang::vhdl::format::KeywordCase;
+
+    #[test]
+    fn format_design() {
+        let code = r#"library ieee;
+use ieee.std_logic_1164.all;
+
+-- A counter.
+ENTITY counter is
+generic (
+WIDTH: positive := 8;
+  RESET_VALUE   :  natural := 0
+);
+    port (
+  clk : in std_logic;
+        -- active-high reset
+    rst: in std_logic;
+    count : out std_logic_vector(WIDTH-1 downto 0)
+  );
+end entity;
+
+architecture rtl of counter is
+  type state_t is (IDLE, RUN);
+    signal state : state_t;
+begin
+process (clk)
+begin
+if rising_edge(clk) then
+    if rst = '1' then
+state <= IDLE;
+    elsif state = IDLE then
+      state <= RUN;
+        else
+    case state is
+  when IDLE =>
+    state <= RUN;
+      when others =>
+        null;
+    end case;
+      end if;
+end if;
+  end process;
+
+  gen_lanes: for i in 0 to 1 generate
+  u_sub: entity work.sub
+  generic map (
+  N => 4,
+  RESET_VALUE => 0
+  )
+    port map (clk => clk,
+    rst => rst);
+  end generate;
+
+  x <= a when sel = '1' else
+    b;
+end architecture;
+"#;
+        let expected = r#"library ieee;
+use ieee.std_logic_1164.all;
+
+-- A counter.
+ENTITY counter is
+  generic (
+    WIDTH       : positive := 8;
+    RESET_VALUE : natural := 0
+  );
+  port (
+    clk   : in std_logic;
+    -- active-high reset
+    rst   : in std_logic;
+    count : out std_logic_vector(WIDTH-1 downto 0)
+  );
+end entity;
+
+architecture rtl of counter is
+  type state_t is (IDLE, RUN);
+  signal state : state_t;
+begin
+  process (clk)
+  begin
+    if rising_edge(clk) then
+      if rst = '1' then
+        state <= IDLE;
+      elsif state = IDLE then
+        state <= RUN;
+      else
+        case state is
+          when IDLE =>
+            state <= RUN;
+          when others =>
+            null;
+        end case;
+      end if;
+    end if;
+  end process;
+
+  gen_lanes: for i in 0 to 1 generate
+    u_sub: entity work.sub
+      generic map (
+        N           => 4,
+        RESET_VALUE => 0
+      )
+      port map (clk => clk,
+        rst => rst);
+  end generate;
+
+  x <= a when sel = '1' else
+    b;
+end architecture;
+"#;
+        assert_eq!(format(code, &VhdlFormat::new()).unwrap(), expected);
+    }
+
+    #[test]
+    fn format_keyword_case() {
+        let code = "Entity foo IS\nEND entity;\n";
+        let mut fmt: VhdlFormat = toml::from_str("keyword-case = \"upper\"").unwrap();
+        fmt.merge(Some(VhdlFormat::new()));
+        assert_eq!(format(code, &fmt).unwrap(), "ENTITY foo IS\nEND ENTITY;\n");
+        assert_eq!(KeywordCase::Lower.apply("ENTITY"), "entity");
+    }
+}
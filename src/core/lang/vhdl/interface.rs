@@ -124,7 +124,9 @@ impl<'a> std::fmt::Display for Architectures<'a> {
 // @note: identifier_list ::= identifier { , identifier }
 
 use super::super::lexer;
-use crate::core::lang::vhdl::token::{delimiter::Delimiter, keyword::Keyword, VhdlToken};
+use crate::core::lang::vhdl::token::{
+    delimiter::Delimiter, keyword::Keyword, tokenizer::VhdlTokenizer, VhdlToken,
+};
 use std::fmt::Display;
 use std::iter::Peekable;
 
@@ -155,7 +157,7 @@ impl IdentifierList {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 struct SubtypeIndication(Vec<VhdlToken>);
 
 impl SubtypeIndication {
@@ -187,7 +189,7 @@ impl Serialize for SubtypeIndication {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 struct StaticExpression(Vec<VhdlToken>);
 
 impl StaticExpression {
@@ -240,7 +242,7 @@ impl std::fmt::Display for StaticExpression {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Mode(Option<Keyword>);
 
 impl Serialize for Mode {
@@ -258,7 +260,7 @@ impl Serialize for Mode {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Expr(Option<StaticExpression>);
 
 impl Serialize for Expr {
@@ -273,7 +275,7 @@ impl Serialize for Expr {
     }
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Clone)]
 pub struct InterfaceDeclaration {
     #[serde(rename = "identifier")]
     identifier: Identifier,
@@ -342,6 +344,88 @@ fn tokens_to_string(tokens: &Vec<VhdlToken>) -> ColorVec {
 }
 
 impl InterfaceDeclaration {
+    /// Accesses the declaration's identifier.
+    pub fn get_identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+
+    /// Returns the declaration's mode, which is `in` when it is omitted.
+    pub fn get_mode(&self) -> Keyword {
+        self.mode.0.clone().unwrap_or(Keyword::In)
+    }
+
+    /// Accesses the name of the declaration's base type.
+    ///
+    /// For example, `std_logic_vector` is the type mark of `std_logic_vector(7 downto 0)`.
+    pub fn get_type_mark(&self) -> Option<&Identifier> {
+        // skip any library and package prefixes (`ieee.std_logic_1164.std_logic`)
+        let mut mark = None;
+        for tkn in &self.datatype.0 {
+            match tkn {
+                VhdlToken::Identifier(id) => mark = Some(id),
+                VhdlToken::Delimiter(Delimiter::Dot) => (),
+                _ => break,
+            }
+        }
+        mark
+    }
+
+    /// Accesses the tokens of the declaration's subtype indication.
+    pub fn get_type_tokens(&self) -> &Vec<VhdlToken> {
+        &self.datatype.0
+    }
+
+    /// Accesses the tokens of the declaration's default expression, if one exists.
+    pub fn get_default_tokens(&self) -> Option<&Vec<VhdlToken>> {
+        self.expr.0.as_ref().map(|e| &e.0)
+    }
+
+    /// Checks if the declaration has a default expression.
+    pub fn has_default(&self) -> bool {
+        self.expr.0.is_some()
+    }
+
+    /// Checks if the declaration's type is a single scalar logic value.
+    pub fn is_single_bit(&self) -> bool {
+        match self.get_type_mark() {
+            Some(mark) => {
+                self.datatype.0.len() == 1
+                    && ["std_logic", "std_ulogic", "bit"]
+                        .contains(&mark.as_str().to_lowercase().as_str())
+            }
+            None => false,
+        }
+    }
+
+    /// Creates a default expression from a literal value appropriate for the
+    /// declaration's type, if the type is a recognized standard type.
+    fn set_placeholder_default(&mut self) {
+        let mark = match self.get_type_mark() {
+            Some(m) => m.as_str().to_lowercase(),
+            None => return,
+        };
+        let value = match mark.as_str() {
+            "integer" | "natural" => "0",
+            "positive" => "1",
+            "real" => "0.0",
+            "boolean" => "false",
+            "time" => "0 ns",
+            "string" => "\"\"",
+            "std_logic" | "std_ulogic" | "bit" => "'0'",
+            "std_logic_vector" | "std_ulogic_vector" | "bit_vector" | "unsigned" | "signed" => {
+                "(others => '0')"
+            }
+            _ => return,
+        };
+        let tokens = VhdlTokenizer::from_source_code(value)
+            .into_tokens()
+            .into_iter()
+            .map(|t| t.take())
+            .filter(|t| t != &VhdlToken::EOF)
+            .collect();
+        self.expr = Expr(Some(StaticExpression(tokens)));
+    }
+
     fn into_interface_string(&self, offset: usize) -> ColorVec {
         let mut result = ColorVec::new();
         // identifier
@@ -440,7 +524,7 @@ impl InterfaceDeclaration {
     }
 }
 
-#[derive(Debug, PartialEq, Serialize)]
+#[derive(Debug, PartialEq, Serialize, Clone)]
 pub struct InterfaceDeclarations(Vec<InterfaceDeclaration>);
 
 impl InterfaceDeclarations {
@@ -452,6 +536,26 @@ impl InterfaceDeclarations {
         self.0.len()
     }
 
+    pub fn iter(&self) -> std::slice::Iter<'_, InterfaceDeclaration> {
+        self.0.iter()
+    }
+
+    /// Gives declarations without a default expression a placeholder value of
+    /// their type so they can be elaborated on their own.
+    ///
+    /// Returns the identifiers of the declarations that were missing a default
+    /// expression. Declarations with unrecognized types remain without one.
+    pub fn fill_missing_defaults(&mut self) -> Vec<Identifier> {
+        self.0
+            .iter_mut()
+            .filter(|d| d.has_default() == false)
+            .map(|d| {
+                d.set_placeholder_default();
+                d.identifier.clone()
+            })
+            .collect()
+    }
+
     /// Determines the length of the longest identifier.
     pub fn longest_identifier(&self) -> usize {
         let longest = self
@@ -16,6 +16,7 @@
 //
 
 use super::{Identifier, Position};
+use crate::core::lang::instance::Instance;
 use crate::core::lang::reference::RefSet;
 use serde_derive::Serialize;
 
@@ -30,6 +31,8 @@ pub struct Architecture {
     #[serde(skip_serializing)]
     deps: RefSet,
     #[serde(skip_serializing)]
+    instances: Vec<Instance<Identifier>>,
+    #[serde(skip_serializing)]
     pos: Position,
 }
 
@@ -39,6 +42,7 @@ impl Architecture {
         owner: Identifier,
         refs: RefSet,
         deps: RefSet,
+        instances: Vec<Instance<Identifier>>,
         pos: Position,
     ) -> Self {
         Self {
@@ -46,6 +50,7 @@ impl Architecture {
             owner: owner,
             refs: refs,
             deps: deps,
+            instances: instances,
             pos: pos,
         }
     }
@@ -76,6 +81,11 @@ impl Architecture {
         &self.deps
     }
 
+    /// Accesses the labeled instantiations within the architecture, in order of appearance.
+    pub fn get_instances(&self) -> &Vec<Instance<Identifier>> {
+        &self.instances
+    }
+
     /// Accesses the references for the entity.
     pub fn get_refs(&self) -> &RefSet {
         &self.refs
@@ -21,6 +21,7 @@ use serde_derive::Serialize;
 
 use crate::core::lang::{
     reference::RefSet,
+    testbench::{self, PortRole},
     vhdl::{error::VhdlError, format::VhdlFormat},
 };
 
@@ -271,6 +272,160 @@ impl Entity {
         result
     }
 
+    /// Generates a VHDL testbench that instantiates the entity as the design
+    /// under test.
+    ///
+    /// Single-bit inputs named like clocks and resets are driven by generator
+    /// processes. All remaining inputs are left to the stimulus process.
+    pub fn into_testbench(
+        &self,
+        inst: &Option<Identifier>,
+        library: &Identifier,
+        fmt: &VhdlFormat,
+    ) -> String {
+        let tab = fmt.get_tab_size() as usize;
+        let tb_name = self.name.into_extension(testbench::TESTBENCH_SUFFIX);
+
+        // identify which inputs are clocks and resets
+        let roles: Vec<(&Identifier, PortRole)> = self
+            .ports
+            .0
+            .iter()
+            .filter(|p| p.get_mode() == Keyword::In && p.is_single_bit() == true)
+            .filter_map(|p| {
+                Some((
+                    p.get_identifier(),
+                    PortRole::classify(p.get_identifier().as_str())?,
+                ))
+            })
+            .collect();
+        let has_clock = roles.iter().any(|(_, r)| r.is_clock());
+        let is_timed = roles.is_empty() == false;
+
+        // give every generic a value for the testbench's constants
+        let mut generics = self.generics.0.clone();
+        let unset = generics.fill_missing_defaults();
+
+        let mut result = String::new();
+        // context clause
+        result.push_str("library ieee;\n");
+        result.push_str("use ieee.std_logic_1164.all;\n");
+        result.push_str("use ieee.numeric_std.all;\n");
+        if library != &Identifier::new_working() {
+            result.push_str(&format!("\nlibrary {};\n", library));
+        }
+        // testbench entity
+        result.push_str(&format!("\nentity {} is\nend entity;\n", tb_name));
+        // architecture declarative part
+        result.push_str(&format!("\narchitecture sim of {} is\n", tb_name));
+        if is_timed == true {
+            result.push_str(&format!(
+                "\n{:<tab$}constant {} : time := 10 ns;\n",
+                "",
+                testbench::CLK_PERIOD,
+            ));
+        }
+        if generics.len() > 0 {
+            result.push('\n');
+            for id in &unset {
+                result.push_str(&format!(
+                    "{:<tab$}-- @todo: set a value for generic '{}'\n",
+                    "", id
+                ));
+            }
+            let constants = generics
+                .to_declaration_part_string(Keyword::Constant, &fmt, "", "")
+                .to_string();
+            result.push_str(&testbench::indent(&constants, tab));
+            result.push('\n');
+        }
+        if self.ports.len() > 0 {
+            result.push('\n');
+            result.push_str(&testbench::indent(&self.into_signals(&fmt, "", ""), tab));
+            result.push('\n');
+        }
+        if has_clock == true {
+            result.push_str(&format!("\n{:<tab$}signal halt : boolean := false;\n", ""));
+        }
+        // architecture statement part
+        result.push_str("\nbegin\n\n");
+        let dut = self.into_instance(inst, &Some(library.clone()), &fmt, "", "", "", "");
+        result.push_str(&testbench::indent(&dut, tab));
+        result.push('\n');
+
+        for (port, role) in &roles {
+            result.push('\n');
+            let lines = match role {
+                PortRole::Clock => vec![
+                    format!("-- drive the clock until the simulation is halted"),
+                    format!("{}_gen: process", port),
+                    format!("begin"),
+                    format!("{:<tab$}while halt = false loop", ""),
+                    format!("{:<w$}{} <= '0';", "", port, w = tab * 2),
+                    format!(
+                        "{:<w$}wait for {} / 2;",
+                        "",
+                        testbench::CLK_PERIOD,
+                        w = tab * 2
+                    ),
+                    format!("{:<w$}{} <= '1';", "", port, w = tab * 2),
+                    format!(
+                        "{:<w$}wait for {} / 2;",
+                        "",
+                        testbench::CLK_PERIOD,
+                        w = tab * 2
+                    ),
+                    format!("{:<tab$}end loop;", ""),
+                    format!("{:<tab$}wait;", ""),
+                    format!("end process;"),
+                ],
+                PortRole::Reset { active_low: _ } => vec![
+                    format!("-- assert the reset for the first few clock cycles"),
+                    format!("{}_gen: process", port),
+                    format!("begin"),
+                    format!("{:<tab$}{} <= '{}';", "", port, role.active_level()),
+                    format!(
+                        "{:<tab$}wait for {} * {};",
+                        "",
+                        testbench::CLK_PERIOD,
+                        testbench::RESET_CYCLES
+                    ),
+                    format!("{:<tab$}{} <= '{}';", "", port, role.inactive_level()),
+                    format!("{:<tab$}wait;", ""),
+                    format!("end process;"),
+                ],
+            };
+            result.push_str(&testbench::indent(&lines.join("\n"), tab));
+            result.push('\n');
+        }
+
+        // stimulus process
+        let mut lines = vec![format!("stimulus: process"), format!("begin")];
+        if is_timed == true {
+            lines.push(format!(
+                "{:<tab$}wait for {} * {};",
+                "",
+                testbench::CLK_PERIOD,
+                testbench::RESET_CYCLES + 1
+            ));
+        }
+        lines.push(format!(
+            "{:<tab$}-- @todo: drive the inputs and check the outputs",
+            ""
+        ));
+        lines.push(String::new());
+        if has_clock == true {
+            lines.push(format!("{:<tab$}halt <= true;", ""));
+        }
+        lines.push(format!("{:<tab$}report \"simulation complete\";", ""));
+        lines.push(format!("{:<tab$}wait;", ""));
+        lines.push(format!("end process;"));
+        result.push('\n');
+        result.push_str(&testbench::indent(&lines.join("\n"), tab));
+        result.push_str("\n\nend architecture;\n");
+        result
+    }
+
     /// Generates list of available architectures.
     ///
     /// Note: This fn must be ran after linking entities and architectures in the
@@ -29,6 +29,7 @@ use self::packagebody::PackageBody;
 use super::super::lexer::*;
 use super::super::parser::*;
 
+use crate::core::lang::instance::{Association, Instance, Scope, ScopeKind};
 use crate::core::lang::reference::{CompoundIdentifier, RefSet};
 use crate::core::lang::vhdl::interface::*;
 use crate::core::lang::vhdl::token::*;
@@ -734,7 +735,8 @@ impl VhdlSymbol {
             // panic!("expecting keyword IS")
             return Err(VhdlError::Vague);
         }
-        let (b_refs, _b_deps) = VhdlSymbol::parse_body(tokens, &Self::is_primary_ending, false);
+        let (b_refs, _b_deps, _b_insts) =
+            VhdlSymbol::parse_body(tokens, &Self::is_primary_ending, false);
         Ok(PackageBody::new(
             match pack_name {
                 VhdlToken::Identifier(id) => id,
@@ -1070,13 +1072,14 @@ impl VhdlSymbol {
         let entity_name = VhdlSymbol::parse_owner_design_unit(tokens)?;
         // println!("*--- unit {}", arch_name);
 
-        let (arch_refs, arch_deps) =
+        let (arch_refs, arch_deps, arch_insts) =
             VhdlSymbol::parse_declaration(tokens, &Self::is_primary_ending)?;
         Ok(VhdlSymbol::Architecture(Architecture::new(
             arch_name,
             entity_name,
             arch_refs,
             arch_deps,
+            arch_insts,
             pos,
         )))
     }
@@ -1384,7 +1387,8 @@ impl VhdlSymbol {
             // stop the declaration section and enter a statement section
             if t.as_type().check_keyword(&Keyword::Begin) {
                 tokens.next();
-                let (b_refs, b_deps) = Self::parse_body(tokens, &Self::is_primary_ending, false);
+                let (b_refs, b_deps, _b_insts) =
+                    Self::parse_body(tokens, &Self::is_primary_ending, false);
                 entity_refs.extend(b_refs);
                 entity_deps.extend(b_deps);
                 break;
@@ -1463,7 +1467,7 @@ impl VhdlSymbol {
             // determine when to branch to declaration section or body section
             if t.as_type().check_keyword(&Keyword::Is) {
                 // println!("OUT SUB: {:?}", t);
-                let (inner_refs, inner_deps) =
+                let (inner_refs, inner_deps, _inner_insts) =
                     Self::parse_declaration(tokens, &Self::is_subprogram_ending)?;
                 refs.extend(inner_refs);
                 deps.extend(inner_deps);
@@ -1488,7 +1492,7 @@ impl VhdlSymbol {
     fn parse_declaration<I>(
         tokens: &mut Peekable<I>,
         eval_exit: &dyn Fn(&Statement) -> bool,
-    ) -> Result<(RefSet, RefSet), VhdlError>
+    ) -> Result<(RefSet, RefSet, Vec<Instance<Identifier>>), VhdlError>
     where
         I: Iterator<Item = Token<VhdlToken>>,
     {
@@ -1500,6 +1504,7 @@ impl VhdlSymbol {
         }
         let mut refs = RefSet::new();
         let mut deps = RefSet::new();
+        let mut instances = Vec::new();
 
         while let Some(t) = tokens.peek() {
             // println!("dec: {:?}", t);
@@ -1507,9 +1512,11 @@ impl VhdlSymbol {
             if t.as_type().check_keyword(&Keyword::Begin) {
                 tokens.next();
                 // combine refs from declaration and from body
-                let (body_refs, body_deps) = Self::parse_body(tokens, &eval_exit, false);
+                let (body_refs, body_deps, mut body_insts) =
+                    Self::parse_body(tokens, &eval_exit, false);
                 refs.extend(body_refs);
                 deps.extend(body_deps);
+                instances.append(&mut body_insts);
 
                 // println!("{}", "stop reading tokens");
                 // STOP READING TOKENS
@@ -1529,8 +1536,10 @@ impl VhdlSymbol {
                 // find a nested package
             } else if t.as_type().check_keyword(&Keyword::Package) {
                 let inner_pos = tokens.next().unwrap().into_position();
-                let _pack_name = Self::route_package_parse(tokens, inner_pos);
-                // println!("**** INFO: detected nested package \"{}\"", pack_name);
+                // keep references from nested packages and package instantiations
+                if let Ok(inner_pack) = Self::route_package_parse(tokens, inner_pos) {
+                    refs.extend(inner_pack.get_refs().clone());
+                }
 
                 // detect subprograms
             } else if t.as_type().as_keyword().is_some()
@@ -1553,7 +1562,7 @@ impl VhdlSymbol {
                 }
             }
         }
-        Ok((refs, deps))
+        Ok((refs, deps, instances))
     }
 
     /// Checks if the statement is a valid primary unit END statement.
@@ -1681,7 +1690,7 @@ impl VhdlSymbol {
         tokens: &mut Peekable<I>,
         eval_exit: &dyn Fn(&Statement) -> bool,
         is_subprogram: bool,
-    ) -> (RefSet, RefSet)
+    ) -> (RefSet, RefSet, Vec<Instance<Identifier>>)
     where
         I: Iterator<Item = Token<VhdlToken>>,
     {
@@ -1689,23 +1698,40 @@ impl VhdlSymbol {
         let mut refs = RefSet::new();
         // collect component names
         let mut deps = RefSet::new();
+        // collect the labeled instances and the generate blocks enclosing them
+        let mut instances = Vec::new();
+        let mut scopes: Vec<Scope> = Vec::new();
+        // remember if the last statement was the head of a generate statement
+        let mut after_generate = false;
         // println!("*--- statement section");
         while let Some(t) = tokens.peek() {
+            let generate_body = after_generate;
+            after_generate = false;
             if t.as_type().check_keyword(&Keyword::End) == true {
                 let (clause, _c_refs) = Self::parse_statement(tokens);
                 // println!("IN BODY AT END: {:?}", stmt);
                 if eval_exit(&clause) == true {
                     break;
                 }
+                // leave a generate block
+                if clause
+                    .get_tokens()
+                    .get(1)
+                    .is_some_and(|t| t.as_type().check_keyword(&Keyword::Generate))
+                {
+                    scopes.pop();
+                }
             // enter a subprogram
             } else if t.as_type().check_keyword(&Keyword::Function)
                 || t.as_type().check_keyword(&Keyword::Begin)
                 || t.as_type().check_keyword(&Keyword::Procedure)
             {
                 let is_subprogram = Self::is_subprogram(t.as_type().as_keyword().unwrap());
-                let next_eval_exit = match is_subprogram {
-                    true => Self::is_subprogram_ending,
-                    false => Self::is_sub_ending,
+                let next_eval_exit = match (is_subprogram, generate_body) {
+                    (true, _) => Self::is_subprogram_ending,
+                    // an explicit generate body closes with `end [label];`
+                    (false, true) => Self::is_primary_ending,
+                    (false, false) => Self::is_sub_ending,
                 };
                 let (_clause, c_refs) = Self::parse_statement(tokens);
                 // println!("ENTERING SUBPROGRAM {:?}", _clause);
@@ -1713,12 +1739,14 @@ impl VhdlSymbol {
                 refs.extend(c_refs);
 
                 // println!("REFS BEFORE: {:?}", refs);
-                let (inner_refs, inner_deps) =
+                let (inner_refs, inner_deps, mut inner_insts) =
                     Self::parse_body(tokens, &next_eval_exit, is_subprogram);
                 // println!("DEPS: {:?}", inner_deps);
                 // update any references caught
                 refs.extend(inner_refs);
                 deps.extend(inner_deps);
+                inner_insts.iter_mut().for_each(|i| i.enclose(&scopes));
+                instances.append(&mut inner_insts);
                 // println!("REFS AFTER: {:?}", refs);
                 // println!("EXITING SUBPROGRAM");
                 // find component names (could be in package)
@@ -1728,25 +1756,167 @@ impl VhdlSymbol {
                 // find packages
             } else if t.as_type().check_keyword(&Keyword::Package) {
                 let inner_pos = tokens.next().unwrap().into_position();
-                let _symbol = Self::route_package_parse(tokens, inner_pos);
-                // println!("**** INFO: Detected nested package \"{}\"", symbol);
+                // keep references from nested packages and package instantiations
+                if let Ok(inner_pack) = Self::route_package_parse(tokens, inner_pos) {
+                    refs.extend(inner_pack.get_refs().clone());
+                }
                 // build statements
             } else {
                 let (clause, c_refs) = Self::parse_statement(tokens);
                 // println!("IN BODY: {:?}", clause);
                 refs.extend(c_refs);
                 // check if statement is an instantiation
+                after_generate = clause
+                    .get_tokens()
+                    .last()
+                    .is_some_and(|t| t.as_type().check_keyword(&Keyword::Generate));
                 if is_subprogram == false {
+                    // enter a generate block
+                    if let Some(scope) = Self::as_generate_scope(&clause) {
+                        scopes.push(scope);
+                    }
+                    let instance = Self::as_instance(&clause);
                     if let Some(i_refs) = Self::parse_instantiation(clause.0) {
                         // println!("info: detected dependency \"{:?}\"", i_refs);
                         deps.extend(i_refs);
+                        if let Some(inst) = instance {
+                            instances.push(inst.scopes(scopes.clone()));
+                        }
                     }
                 }
             }
         }
         // println!("DEPS: {:?}", deps);
         // println!("REFS: {:?}", refs);
-        (refs, deps)
+        (refs, deps, instances)
+    }
+
+    /// Returns the labeled generate block entered by the statement `stmt`, which
+    /// follows the form `<label> : for|if|case ... generate`.
+    fn as_generate_scope(stmt: &Statement) -> Option<Scope> {
+        let tokens: Vec<&VhdlToken> = stmt.get_tokens().iter().map(|t| t.as_type()).collect();
+        let label = tokens.get(0)?.as_identifier()?;
+        if tokens.get(1)?.check_delimiter(&Delimiter::Colon) == false
+            || tokens.last()?.check_keyword(&Keyword::Generate) == false
+        {
+            return None;
+        }
+        match tokens.get(2)?.check_keyword(&Keyword::For) {
+            true => {
+                // only literal ranges of the form `<left> to|downto <right>` are known
+                let bounds = match tokens.iter().position(|t| t.check_keyword(&Keyword::In)) {
+                    Some(i) if i + 4 == tokens.len() - 1 => {
+                        match tokens[i + 2].check_keyword(&Keyword::To)
+                            || tokens[i + 2].check_keyword(&Keyword::Downto)
+                        {
+                            true => (
+                                tokens[i + 1].to_string().parse::<i64>().ok(),
+                                tokens[i + 3].to_string().parse::<i64>().ok(),
+                            ),
+                            false => (None, None),
+                        }
+                    }
+                    _ => (None, None),
+                };
+                Some(Scope::with_bounds(label.to_string(), bounds.0, bounds.1))
+            }
+            false => Some(Scope::new(label.to_string(), ScopeKind::Branch)),
+        }
+    }
+
+    /// Reads the instance label, the instantiated unit, and the generic map from
+    /// an instantiation statement `stmt`.
+    fn as_instance(stmt: &Statement) -> Option<Instance<Identifier>> {
+        let mut tokens = stmt.get_tokens().iter().map(|t| t.as_type()).peekable();
+        let name = tokens.next()?.as_identifier()?.clone();
+        if tokens.next()?.check_delimiter(&Delimiter::Colon) == false {
+            return None;
+        }
+        if let Some(VhdlToken::Keyword(kw)) = tokens.peek() {
+            match kw {
+                Keyword::Component | Keyword::Entity | Keyword::Configuration => tokens.next(),
+                _ => return None,
+            };
+        }
+        // take the (selected) name of the unit
+        let mut names = vec![tokens.next()?.as_identifier()?.clone()];
+        while let Some(VhdlToken::Delimiter(Delimiter::Dot)) = tokens.peek() {
+            tokens.next();
+            names.push(tokens.next()?.as_identifier()?.clone());
+        }
+        let unit = names.pop().unwrap();
+        let lib = names.pop();
+
+        // take the generic map's and port map's associations
+        let mut generics = Vec::new();
+        let mut ports = Vec::new();
+        while let Some(t) = tokens.next() {
+            let list = match t {
+                VhdlToken::Keyword(Keyword::Generic) => &mut generics,
+                VhdlToken::Keyword(Keyword::Port) => &mut ports,
+                _ => continue,
+            };
+            // skip `map (`
+            tokens.next();
+            tokens.next();
+            let mut depth = 0;
+            let mut item: Vec<&VhdlToken> = Vec::new();
+            while let Some(t) = tokens.next() {
+                if t.check_delimiter(&Delimiter::ParenL) == true {
+                    depth += 1;
+                } else if t.check_delimiter(&Delimiter::ParenR) == true {
+                    if depth == 0 {
+                        list.push(Self::into_association(&item));
+                        break;
+                    }
+                    depth -= 1;
+                } else if depth == 0 && t.check_delimiter(&Delimiter::Comma) == true {
+                    list.push(Self::into_association(&item));
+                    item.clear();
+                    continue;
+                }
+                item.push(t);
+            }
+        }
+        Some(
+            Instance::new(unit, name)
+                .library(lib)
+                .generics(generics)
+                .ports(ports),
+        )
+    }
+
+    /// Creates an association from the tokens of a `formal => actual` element.
+    fn into_association(tokens: &[&VhdlToken]) -> Association {
+        match tokens
+            .iter()
+            .position(|t| t.check_delimiter(&Delimiter::Arrow))
+        {
+            Some(i) => Association::new(
+                Some(Self::into_text(&tokens[..i])),
+                Self::into_text(&tokens[i + 1..]),
+            ),
+            None => Association::new(None, Self::into_text(tokens)),
+        }
+    }
+
+    /// Writes the tokens back as source code text.
+    fn into_text(tokens: &[&VhdlToken]) -> String {
+        let mut text = String::new();
+        let mut prev_word = false;
+        for t in tokens {
+            let word = t.to_string();
+            let is_word = word
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_alphanumeric() || c == '_');
+            if prev_word == true && is_word == true {
+                text.push(' ');
+            }
+            text.push_str(&word);
+            prev_word = is_word;
+        }
+        text
     }
 }
 
@@ -2618,4 +2788,149 @@ end architecture rtl;
         // verify we captured all 3 sub-entities following procedures
         assert_eq!(syms[1].as_architecture().unwrap().get_refs().len(), 3);
     }
+
+    #[test]
+    fn test_package_instantiation() {
+        let s = r#"
+library math;
+
+package inst_pkg is new math.gen_pkg generic map (WIDTH => 8);
+
+context my_ctx is
+    library ieee;
+    use ieee.std_logic_1164.all;
+    use work.inst_pkg.all;
+    context math.base_ctx;
+end context;
+"#;
+        let syms = VHDLParser::read(&s).unwrap().into_symbols();
+        // the instantiated package is its own primary design unit
+        assert_eq!(
+            syms[0].as_package().unwrap().get_name(),
+            &Identifier::Basic(String::from("inst_pkg"))
+        );
+        assert_eq!(
+            syms[0].get_refs(),
+            &RefSet::from([CompoundIdentifier::new_vhdl(
+                Identifier::Basic(String::from("math")),
+                Identifier::Basic(String::from("gen_pkg"))
+            )])
+        );
+        // context declarations reference packages and other contexts
+        assert_eq!(syms[1].get_refs().len(), 3);
+        assert_eq!(
+            syms[1].get_refs().contains(&CompoundIdentifier::new_vhdl(
+                Identifier::Basic(String::from("math")),
+                Identifier::Basic(String::from("base_ctx"))
+            )),
+            true
+        );
+    }
+
+    #[test]
+    fn test_nested_package_instantiation() {
+        let s = r#"
+context work.my_ctx;
+
+entity top is
+end entity;
+
+architecture rtl of top is
+    package arch_pkg is new work.gen_pkg generic map (WIDTH => 4);
+begin
+    process
+        package proc_pkg is new lib1.gen_pkg generic map (WIDTH => 2);
+    begin
+        wait;
+    end process;
+end architecture;
+
+package outer_pkg is
+    package inner_pkg is new lib2.gen_pkg generic map (WIDTH => 1);
+end package;
+"#;
+        let syms = VHDLParser::read(&s).unwrap().into_symbols();
+        let gen_pkg = |lib: &str| {
+            CompoundIdentifier::new_vhdl(
+                Identifier::Basic(String::from(lib)),
+                Identifier::Basic(String::from("gen_pkg")),
+            )
+        };
+        // the context reference belongs to the following primary design unit
+        assert_eq!(
+            syms[0].get_refs(),
+            &RefSet::from([CompoundIdentifier::new_vhdl(
+                Identifier::Basic(String::from("work")),
+                Identifier::Basic(String::from("my_ctx"))
+            )])
+        );
+        // instantiations in the architecture's declarative regions are kept
+        assert_eq!(
+            syms[1].get_refs(),
+            &RefSet::from([gen_pkg("work"), gen_pkg("lib1")])
+        );
+        // instantiations within a package declaration are kept
+        assert_eq!(syms[2].get_refs(), &RefSet::from([gen_pkg("lib2")]));
+    }
+
+    #[test]
+    fn test_instance_hierarchy() {
+        let s = r#"
+architecture rtl of core is
+begin
+    gen_lane: for i in 0 to 1 generate
+        u_alu: entity work.alu(rtl)
+            generic map (WIDTH => 8, DEPTH => N - 1)
+            port map (clk => clk);
+    end generate;
+
+    gen_pipe: for i in 0 to N - 1 generate
+    begin
+        gen_reg: if i > 0 generate
+            u_reg: dff port map (clk, d(i), q(i));
+        end generate gen_reg;
+    end;
+    end generate gen_pipe;
+
+    u_fifo: component fifo generic map (16) port map (clk => clk);
+end architecture;
+"#;
+        let syms = VHDLParser::read(&s).unwrap().into_symbols();
+        let insts = syms[0].as_architecture().unwrap().get_instances();
+        let paths: Vec<(String, String, Vec<String>)> = insts
+            .iter()
+            .map(|i| {
+                (
+                    i.get_name().to_string(),
+                    i.get_unit().to_string(),
+                    i.get_scopes()
+                        .iter()
+                        .map(|s| s.expand().join(","))
+                        .collect(),
+                )
+            })
+            .collect();
+        let s = |v: &str| String::from(v);
+        assert_eq!(
+            paths,
+            vec![
+                (s("u_alu"), s("alu"), vec![s("gen_lane[0],gen_lane[1]")]),
+                (s("u_reg"), s("dff"), vec![s("gen_pipe[*]"), s("gen_reg")]),
+                (s("u_fifo"), s("fifo"), vec![]),
+            ]
+        );
+        assert_eq!(
+            insts[0].get_library(),
+            Some(&Identifier::Basic(String::from("work")))
+        );
+        let generics = |i: usize| {
+            insts[i]
+                .get_generics()
+                .iter()
+                .map(|g| g.to_string())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(generics(0), vec!["WIDTH=8", "DEPTH=N-1"]);
+        assert_eq!(generics(2), vec!["16"]);
+    }
 }
@@ -200,8 +200,10 @@ type Dependencies = HashMap<IpName, Dependency>;
 pub const IP_MANIFEST_FILE: &str = "Orbit.toml";
 pub const ORBIT_SUM_FILE: &str = ".orbit-checksum";
 pub const ORBIT_METADATA_FILE: &str = ".orbit-metadata";
+pub const ORBIT_STATUS_FILE: &str = ".orbit-status";
 
 const DEPENDENCIES_KEY: &str = "dependencies";
+const DEV_DEPENDENCIES_KEY: &str = "dev-dependencies";
 
 #[derive(Deserialize, Serialize, Debug, PartialEq)]
 #[serde(deny_unknown_fields)]
@@ -528,6 +530,10 @@ impl Package {
         &self.keywords
     }
 
+    pub fn get_description(&self) -> &Option<String> {
+        &self.description
+    }
+
     pub fn get_library(&self) -> &Option<IpName> {
         &self.library
     }
@@ -562,6 +568,84 @@ impl Package {
     }
 }
 
+/// Adds the dependency `name` with the version `version` to the `[dependencies]`
+/// table of the manifest's `contents`, or to the `[dev-dependencies]` table if
+/// `dev` is set.
+///
+/// The dependency is written as an inline table when it also has a pinned `uuid`
+/// or a relative `path`. An existing entry for `name` in either table is
+/// replaced. The existing formatting of the manifest is preserved.
+pub fn insert_dependency(
+    contents: &str,
+    name: &PkgPart,
+    version: &str,
+    uuid: Option<&Uuid>,
+    path: Option<&str>,
+    dev: bool,
+) -> Result<String, Fault> {
+    let mut doc = contents.parse::<toml_edit::Document>()?;
+    let (key, other) = match dev {
+        true => (DEV_DEPENDENCIES_KEY, DEPENDENCIES_KEY),
+        false => (DEPENDENCIES_KEY, DEV_DEPENDENCIES_KEY),
+    };
+    if let Some(table) = doc.get_mut(other).and_then(|t| t.as_table_like_mut()) {
+        table.remove(name.as_ref());
+    }
+    if doc.contains_key(key) == false {
+        doc.insert(key, toml_edit::table());
+    }
+    let entry = match uuid.is_none() == true && path.is_none() == true {
+        true => toml_edit::value(version),
+        false => {
+            let mut table = toml_edit::InlineTable::new();
+            table.insert("version", version.into());
+            if let Some(id) = uuid {
+                table.insert("uuid", id.encode().into());
+            }
+            if let Some(p) = path {
+                table.insert("path", p.into());
+            }
+            toml_edit::value(table)
+        }
+    };
+    doc[key][name.as_ref()] = entry;
+    Ok(doc.to_string())
+}
+
+/// Removes the dependency `name` from the `[dependencies]` and `[dev-dependencies]`
+/// tables of the manifest's `contents`.
+///
+/// Returns `None` if neither table has the dependency. The existing formatting of
+/// the manifest is preserved.
+pub fn remove_dependency(contents: &str, name: &PkgPart) -> Result<Option<String>, Fault> {
+    let mut doc = contents.parse::<toml_edit::Document>()?;
+    let mut removed = false;
+    for key in [DEPENDENCIES_KEY, DEV_DEPENDENCIES_KEY] {
+        if let Some(table) = doc.get_mut(key).and_then(|t| t.as_table_like_mut()) {
+            removed |= table.remove(name.as_ref()).is_some();
+        }
+    }
+    match removed {
+        true => Ok(Some(doc.to_string())),
+        false => Ok(None),
+    }
+}
+
+/// Sets the `version` field of the `[ip]` table of the manifest's `contents`.
+///
+/// The existing formatting of the manifest, including any comment following
+/// the version, is preserved.
+pub fn set_version(contents: &str, version: &IpVersion) -> Result<String, Fault> {
+    let mut doc = contents.parse::<toml_edit::Document>()?;
+    let item = &mut doc["ip"]["version"];
+    let decor = item.as_value().map(|v| v.decor().clone());
+    *item = toml_edit::value(version.to_string());
+    if let Some(decor) = decor {
+        *item.as_value_mut().unwrap().decor_mut() = decor;
+    }
+    Ok(doc.to_string())
+}
+
 /// Takes an iterative approach to iterating through directories to find a file
 /// matching `name`.
 ///
@@ -778,6 +862,58 @@ mod test {
             };
         }
     }
+
+    #[test]
+    fn add_dependency() {
+        let name = PkgPart::from_str("gates").unwrap();
+        let result = insert_dependency(EX2, &name, "1.0.0", None, None, false).unwrap();
+        assert_eq!(
+            result,
+            format!("{}\n[dependencies]\ngates = \"1.0.0\"\n", EX2)
+        );
+
+        let contents = "[ip]\nname = \"a\" # name\n\n[dependencies]\nuart = \"2.3.1\"\n";
+        let result = insert_dependency(contents, &name, "1.0", None, None, false).unwrap();
+        assert_eq!(
+            result,
+            "[ip]\nname = \"a\" # name\n\n[dependencies]\nuart = \"2.3.1\"\ngates = \"1.0\"\n"
+        );
+
+        // move the dependency to the dev-dependencies with a pinned uuid
+        let uuid = Uuid::from_str("5h2kq7m2x0wdlc4ns8tbe1pzq").unwrap();
+        let result = insert_dependency(&result, &name, "1.0", Some(&uuid), None, true).unwrap();
+        assert_eq!(
+            result,
+            "[ip]\nname = \"a\" # name\n\n[dependencies]\nuart = \"2.3.1\"\n\n[dev-dependencies]\ngates = { version = \"1.0\", uuid = \"5h2kq7m2x0wdlc4ns8tbe1pzq\" }\n"
+        );
+    }
+
+    #[test]
+    fn delete_dependency() {
+        let name = PkgPart::from_str("gates").unwrap();
+        let contents = "[ip]\nname = \"a\"\n\n[dependencies]\nuart = \"2.3.1\" # serial\ngates = \"1.0\"\n\n[dev-dependencies]\ngates = { path = \"../gates\", version = \"1.0.0\" }\n";
+        assert_eq!(
+            remove_dependency(contents, &name).unwrap(),
+            Some(String::from(
+                "[ip]\nname = \"a\"\n\n[dependencies]\nuart = \"2.3.1\" # serial\n\n[dev-dependencies]\n"
+            ))
+        );
+        assert_eq!(remove_dependency(EX2, &name).unwrap(), None);
+    }
+
+    #[test]
+    fn update_version() {
+        let version = IpVersion::from_str("0.2.0-rc1").unwrap();
+        let result = set_version(EX1, &version).unwrap();
+        assert_eq!(
+            result,
+            EX1.replace("version = \"0.1.0\"", "version = \"0.2.0-rc1\"")
+        );
+
+        let contents = "[ip]\nname = \"a\"\nversion =  \"1.0.0\" # keep\n";
+        let result = set_version(contents, &IpVersion::from_str("1.0.1").unwrap()).unwrap();
+        assert_eq!(result, "[ip]\nname = \"a\"\nversion =  \"1.0.1\" # keep\n");
+    }
 }
 
 const EX1: &str = r#"[ip]
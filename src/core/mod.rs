@@ -19,12 +19,18 @@ pub mod algo;
 pub mod blueprint;
 pub mod catalog;
 pub mod channel;
+pub mod compat;
 pub mod config;
 pub mod context;
+pub mod filelist;
 pub mod fileset;
+pub mod fingerprint;
+pub mod fusesoc;
+pub mod generic;
 pub mod ip;
 pub mod iparchive;
 pub mod ippointer;
+pub mod ipxact;
 pub mod lang;
 pub mod lockfile;
 pub mod manifest;
@@ -36,3 +42,4 @@ pub mod target;
 pub mod uuid;
 pub mod version;
 pub mod visibility;
+pub mod watch;
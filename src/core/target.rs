@@ -17,7 +17,7 @@
 
 use crate::core::context::Context;
 use crate::core::fileset::Style;
-use crate::error::Error;
+use crate::error::{Error, Hint};
 use crate::util::anyerror::Fault;
 use crate::util::filesystem;
 use colored::Colorize;
@@ -26,7 +26,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::str::FromStr;
 
-use super::blueprint::Scheme;
+use super::blueprint::{FilelistConfig, NinjaConfig, Scheme};
 use super::swap;
 use super::swap::StrSwapTable;
 
@@ -47,6 +47,10 @@ pub struct Target {
     args: Option<Vec<String>>,
     fileset: Option<Filesets>,
     plans: Option<Vec<Scheme>>,
+    filelist: Option<FilelistConfig>,
+    ninja: Option<NinjaConfig>,
+    requires: Option<Vec<String>>,
+    share: Option<bool>,
 }
 
 impl Target {
@@ -68,6 +72,71 @@ impl Target {
         self.fileset.as_ref()
     }
 
+    /// Returns the names of the targets that must execute before this target.
+    pub fn get_requires(&self) -> Vec<&String> {
+        match &self.requires {
+            Some(r) => r.iter().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Checks if the targets in this target's pipeline share its blueprint and
+    /// output directory.
+    pub fn is_sharing(&self) -> bool {
+        self.share.unwrap_or(false)
+    }
+
+    /// Determines the order to execute this target's pipeline, which is every
+    /// required target (and their required targets) followed by this target.
+    pub fn resolve_pipeline<'a>(
+        &'a self,
+        targets: &HashMap<&str, &'a Target>,
+    ) -> Result<Vec<&'a Target>, Error> {
+        let mut order = Vec::new();
+        self.visit_pipeline(targets, &mut Vec::new(), &mut order)?;
+        Ok(order)
+    }
+
+    fn visit_pipeline<'a>(
+        &'a self,
+        targets: &HashMap<&str, &'a Target>,
+        path: &mut Vec<&'a str>,
+        order: &mut Vec<&'a Target>,
+    ) -> Result<(), Error> {
+        if order.iter().any(|t| t.get_name() == self.get_name()) == true {
+            return Ok(());
+        }
+        if path.contains(&self.get_name()) == true {
+            return Err(Error::TargetPipelineCycle(self.name.clone()));
+        }
+        path.push(self.get_name());
+        for req in self.get_requires() {
+            match targets.get(req.as_str()) {
+                Some(t) => t.visit_pipeline(targets, path, order)?,
+                None => {
+                    return Err(Error::TargetRequiresUnknown(
+                        self.name.clone(),
+                        req.clone(),
+                        Hint::TargetsList,
+                    ))
+                }
+            }
+        }
+        path.pop();
+        order.push(self);
+        Ok(())
+    }
+
+    /// Returns the settings for writing a `.f` blueprint.
+    pub fn get_filelist(&self) -> Option<&FilelistConfig> {
+        self.filelist.as_ref()
+    }
+
+    /// Returns the commands for writing a `.ninja` blueprint.
+    pub fn get_ninja(&self) -> Option<&NinjaConfig> {
+        self.ninja.as_ref()
+    }
+
     pub fn coordinate_plan(&self, plan: &Option<Scheme>) -> Result<Scheme, Error> {
         match plan {
             Some(p) => {
@@ -122,12 +191,34 @@ impl Target {
     /// Creates a string to display a list of plugins.
     ///
     /// The string lists the plugins in alphabetical order by `alias`.
+    ///
+    /// Targets that require other targets are followed by their pipeline.
     pub fn list_targets(targets: &mut [&&Target], def_target: Option<&Target>) -> String {
         let mut list = String::new();
         targets.sort_by(|a, b| a.name.cmp(&b.name));
-        for t in targets {
+        let map: HashMap<&str, &Target> = targets.iter().map(|t| (t.get_name(), **t)).collect();
+        for t in targets.iter() {
             let is_default = def_target.is_some() && def_target.unwrap().get_name() == t.get_name();
             list += &format!("{}\n", t.quick_info(is_default));
+            if t.get_requires().is_empty() == false {
+                let pipeline = match t.resolve_pipeline(&map) {
+                    Ok(p) => p
+                        .iter()
+                        .map(|s| s.get_name())
+                        .collect::<Vec<&str>>()
+                        .join(" -> "),
+                    Err(e) => e.to_string(),
+                };
+                list += &format!(
+                    "    {} {}{}\n",
+                    "pipeline:".blue(),
+                    pipeline,
+                    match t.is_sharing() {
+                        true => " (shared)",
+                        false => "",
+                    }
+                );
+            }
         }
         list
     }
@@ -171,6 +262,10 @@ impl std::fmt::Display for Target {
             root: self.root.clone(),
             fileset: self.fileset.clone(),
             plans: self.plans.clone(),
+            filelist: self.filelist.clone(),
+            ninja: self.ninja.clone(),
+            requires: self.requires.clone(),
+            share: self.share.clone(),
         };
 
         write!(f, "{}", toml::to_string_pretty(&refreshed_target).unwrap())
@@ -323,6 +418,10 @@ args = ["~/scripts/download.bash"]
                     (String::from("text"), Style::from_str("*.txt").unwrap()),
                 ])),
                 root: None,
+                filelist: None,
+                ninja: None,
+                requires: None,
+                share: None,
             }
         );
 
@@ -337,10 +436,54 @@ args = ["~/scripts/download.bash"]
                 plans: None,
                 fileset: None,
                 root: None,
+                filelist: None,
+                ninja: None,
+                requires: None,
+                share: None,
             }
         );
     }
 
+    #[test]
+    fn resolve_pipeline() {
+        let lint = Target::from_str("name = \"lint\"\ncommand = \"verible\"").unwrap();
+        let sim =
+            Target::from_str("name = \"sim\"\ncommand = \"ghdl\"\nrequires = [\"lint\"]").unwrap();
+        let synth = Target::from_str(
+            "name = \"synth\"\ncommand = \"yosys\"\nrequires = [\"sim\", \"lint\"]\nshare = true",
+        )
+        .unwrap();
+        let map = HashMap::from([("lint", &lint), ("sim", &sim), ("synth", &synth)]);
+        assert_eq!(
+            synth.resolve_pipeline(&map).unwrap(),
+            vec![&lint, &sim, &synth]
+        );
+        assert_eq!(lint.resolve_pipeline(&map).unwrap(), vec![&lint]);
+        assert_eq!(synth.is_sharing(), true);
+        assert_eq!(sim.is_sharing(), false);
+
+        // required targets must exist
+        let map = HashMap::from([("sim", &sim), ("synth", &synth)]);
+        assert_eq!(
+            synth.resolve_pipeline(&map).unwrap_err().to_string(),
+            Error::TargetRequiresUnknown(
+                String::from("sim"),
+                String::from("lint"),
+                Hint::TargetsList
+            )
+            .to_string()
+        );
+
+        // pipelines cannot loop
+        let a = Target::from_str("name = \"a\"\ncommand = \"a\"\nrequires = [\"b\"]").unwrap();
+        let b = Target::from_str("name = \"b\"\ncommand = \"b\"\nrequires = [\"a\"]").unwrap();
+        let map = HashMap::from([("a", &a), ("b", &b)]);
+        assert_eq!(
+            a.resolve_pipeline(&map).unwrap_err().to_string(),
+            Error::TargetPipelineCycle(String::from("a")).to_string()
+        );
+    }
+
     #[test]
     fn series_of_plugins() {
         let contents = format!("{0}{1}\n{0}{2}", "[[plugin]]", P_1, P_2);
@@ -0,0 +1,318 @@
+//
+//  Copyright (C) 2022-2025  Chase Ruskin
+//
+//  This program is free software: you can redistribute it and/or modify
+//  it under the terms of the GNU General Public License as published by
+//  the Free Software Foundation, either version 3 of the License, or
+//  (at your option) any later version.
+//
+//  This program is distributed in the hope that it will be useful,
+//  but WITHOUT ANY WARRANTY; without even the implied warranty of
+//  MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+//  GNU General Public License for more details.
+//
+//  You should have received a copy of the GNU General Public License
+//  along with this program.  If not, see <http://www.gnu.org/licenses/>.
+//
+
+use crate::core::fileset;
+use crate::core::ip::Ip;
+use crate::core::lang;
+use crate::core::manifest::IP_MANIFEST_FILE;
+use crate::util::anyerror::Fault;
+use colored::Colorize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+/// The time to wait between each check of the watched files.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The time the watched files must remain unchanged before a change is reported.
+const DEBOUNCE: Duration = Duration::from_millis(400);
+
+/// The modification time and size of a file.
+type Stamp = (Option<SystemTime>, u64);
+
+/// The files that changed between two polls of the watched files.
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    files: Vec<String>,
+    structural: bool,
+}
+
+impl Change {
+    pub fn get_files(&self) -> &Vec<String> {
+        &self.files
+    }
+
+    /// Checks if the change affects the design units, their references, or
+    /// the manifest, which requires the design to be planned again.
+    pub fn is_structural(&self) -> bool {
+        self.structural
+    }
+}
+
+/// Detects changes to the files of an ip and its relative dependencies by
+/// periodically polling their metadata.
+pub struct Watcher {
+    stamps: BTreeMap<String, Stamp>,
+    structure: BTreeMap<String, String>,
+}
+
+impl Watcher {
+    pub fn new(ip: &Ip) -> Self {
+        let stamps = Self::poll(ip);
+        let structure = stamps
+            .keys()
+            .filter(|f| fileset::is_hdl(f) == true)
+            .map(|f| (f.clone(), Self::outline(f)))
+            .collect();
+        Self { stamps, structure }
+    }
+
+    /// Blocks until at least one watched file changes and the files have since
+    /// settled.
+    pub fn wait(&mut self, ip: &Ip) -> Change {
+        // wait for the first change
+        let mut latest = loop {
+            std::thread::sleep(POLL_INTERVAL);
+            let stamps = Self::poll(ip);
+            if stamps != self.stamps {
+                break stamps;
+            }
+        };
+        // wait for any burst of writes to finish
+        loop {
+            std::thread::sleep(DEBOUNCE);
+            let stamps = Self::poll(ip);
+            if stamps == latest {
+                break;
+            }
+            latest = stamps;
+        }
+
+        let mut files: Vec<String> = self
+            .stamps
+            .keys()
+            .chain(latest.keys())
+            .filter(|f| self.stamps.get(*f) != latest.get(*f))
+            .cloned()
+            .collect();
+        files.sort();
+        files.dedup();
+
+        let mut structural = false;
+        for file in &files {
+            if file.ends_with(IP_MANIFEST_FILE) == true {
+                structural = true;
+            }
+            if fileset::is_hdl(file) == false {
+                continue;
+            }
+            let outline = match latest.contains_key(file) {
+                true => Some(Self::outline(file)),
+                false => None,
+            };
+            if self.structure.get(file) != outline.as_ref() {
+                structural = true;
+            }
+            match outline {
+                Some(o) => self.structure.insert(file.clone(), o),
+                None => self.structure.remove(file),
+            };
+        }
+        self.stamps = latest;
+        Change { files, structural }
+    }
+
+    /// Collects the stamps for every file of the `ip` and its relative dependencies.
+    fn poll(ip: &Ip) -> BTreeMap<String, Stamp> {
+        let mut stamps = BTreeMap::new();
+        let mut ips = vec![ip];
+        while let Some(ip) = ips.pop() {
+            for file in ip.gather_current_files() {
+                let stamp = match std::fs::metadata(&file) {
+                    Ok(m) => (m.modified().ok(), m.len()),
+                    Err(_) => continue,
+                };
+                stamps.insert(file, stamp);
+            }
+            ips.extend(
+                ip.get_man()
+                    .get_deps_list(true, false)
+                    .into_iter()
+                    .filter_map(|(_, dep)| dep.as_ip()),
+            );
+        }
+        stamps
+    }
+
+    /// Summarizes the parts of an hdl `file` that affect planning: the design
+    /// units it defines, the units they reference, and its included files.
+    fn outline(file: &str) -> String {
+        let mut entries: Vec<String> = match lang::collect_units(&vec![file.to_string()]) {
+            Ok(units) => units
+                .values()
+                .map(|u| {
+                    let mut refs: Vec<String> =
+                        u.get_references().iter().map(|r| r.to_string()).collect();
+                    refs.sort();
+                    format!("{}:{}", u.get_name(), refs.join(","))
+                })
+                .collect(),
+            Err(e) => vec![format!("error:{}", e)],
+        };
+        if let Ok(text) = std::fs::read_to_string(file) {
+            entries.extend(
+                text.lines()
+                    .map(|l| l.trim())
+                    .filter(|l| l.starts_with("`include"))
+                    .map(|l| l.to_string()),
+            );
+        }
+        entries.sort();
+        entries.join("\n")
+    }
+}
+
+/// Calls `run` once and then again after every change to the files of the
+/// `ip`, printing whether each run passed or failed.
+///
+/// The second argument to `run` is `true` when the design must be planned
+/// again.
+pub fn repeat<F>(mut ip: Ip, mut run: F) -> Result<(), Fault>
+where
+    F: FnMut(&Ip, bool) -> Result<(), Fault>,
+{
+    let mut watcher = Watcher::new(&ip);
+    let mut replan = true;
+    let mut count = 1;
+    loop {
+        let start = Instant::now();
+        let result = run(&ip, replan);
+        let elapsed = start.elapsed().as_secs_f32();
+        match &result {
+            Ok(()) => println!(
+                "info: run {} {} in {:.2}s",
+                count,
+                "passed".green(),
+                elapsed
+            ),
+            Err(e) => {
+                eprintln!("{}: {}", "error".red().bold(), e);
+                println!("info: run {} {} in {:.2}s", count, "failed".red(), elapsed)
+            }
+        }
+        println!("info: watching for changes ...");
+
+        let change = watcher.wait(&ip);
+        match change.get_files().len() {
+            1 => println!("info: detected change in {}", change.get_files()[0]),
+            n => println!("info: detected changes in {} files", n),
+        }
+        // a failed plan leaves no blueprint to reuse
+        replan = change.is_structural() == true || (replan == true && result.is_err() == true);
+        if change.is_structural() == true {
+            ip = match Ip::load(PathBuf::from(ip.get_root()), true, false) {
+                Ok(next) => next,
+                Err(e) => {
+                    eprintln!("{}: {}", "error".red().bold(), e);
+                    ip
+                }
+            };
+        }
+        count += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn outline_ignores_bodies() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("top.vhd").display().to_string();
+
+        std::fs::write(
+            &file,
+            "entity top is end entity;\narchitecture rtl of top is begin end architecture;\n",
+        )
+        .unwrap();
+        let before = Watcher::outline(&file);
+
+        // editing statements does not change the structure
+        std::fs::write(&file, "entity top is end entity;\narchitecture rtl of top is\n  signal a : bit;\nbegin\n  a <= '1';\nend architecture;\n").unwrap();
+        assert_eq!(Watcher::outline(&file), before);
+
+        // instantiating another entity does change the structure
+        std::fs::write(&file, "entity top is end entity;\narchitecture rtl of top is begin\n  u0: entity work.child;\nend architecture;\n").unwrap();
+        assert_ne!(Watcher::outline(&file), before);
+    }
+
+    #[test]
+    fn wait_classifies_changes() {
+        let dir = tempdir().unwrap();
+        let root = dir.path().join("top");
+        let gates = dir.path().join("gates");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(&gates).unwrap();
+        std::fs::write(
+            gates.join(IP_MANIFEST_FILE),
+            "[ip]\nname = \"gates\"\nversion = \"0.1.0\"\nuuid = \"1111111111111111111111111\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            gates.join("Orbit.lock"),
+            "version = 1\n\n[[ip]]\nname = \"gates\"\nversion = \"0.1.0\"\nuuid = \"1111111111111111111111111\"\ndependencies = []\n",
+        )
+        .unwrap();
+        std::fs::write(gates.join("and2.vhd"), "entity and2 is end entity;\n").unwrap();
+        std::fs::write(
+            root.join(IP_MANIFEST_FILE),
+            "[ip]\nname = \"top\"\nversion = \"0.1.0\"\nuuid = \"2222222222222222222222222\"\n\n[dependencies]\ngates = { path = \"../gates\", version = \"0.1.0\" }\n",
+        )
+        .unwrap();
+        let top = root.join("top.vhd");
+        std::fs::write(
+            &top,
+            "entity top is end entity;\narchitecture rtl of top is begin end architecture;\n",
+        )
+        .unwrap();
+        let ip = Ip::load(root.clone(), true, false).unwrap();
+        let mut watcher = Watcher::new(&ip);
+
+        // editing statements only runs the target again
+        std::fs::write(&top, "entity top is end entity;\narchitecture rtl of top is\n  signal a : bit;\nbegin\nend architecture;\n").unwrap();
+        let change = watcher.wait(&ip);
+        assert_eq!(change.get_files().len(), 1);
+        assert_eq!(change.get_files()[0].ends_with("top.vhd"), true);
+        assert_eq!(change.is_structural(), false);
+
+        // a new hdl file requires planning again
+        std::fs::write(root.join("or2.vhd"), "entity or2 is end entity;\n").unwrap();
+        let change = watcher.wait(&ip);
+        assert_eq!(change.get_files()[0].ends_with("or2.vhd"), true);
+        assert_eq!(change.is_structural(), true);
+
+        // the files of relative dependencies are watched
+        std::fs::write(
+            gates.join("and2.vhd"),
+            "entity and2 is end entity;\nentity nand2 is end entity;\n",
+        )
+        .unwrap();
+        let change = watcher.wait(&ip);
+        assert_eq!(change.get_files()[0].ends_with("and2.vhd"), true);
+        assert_eq!(change.is_structural(), true);
+
+        // editing the manifest requires planning again
+        let mut manifest = std::fs::read_to_string(root.join(IP_MANIFEST_FILE)).unwrap();
+        manifest.push_str("\n# a comment\n");
+        std::fs::write(root.join(IP_MANIFEST_FILE), manifest).unwrap();
+        let change = watcher.wait(&ip);
+        assert_eq!(change.get_files()[0].ends_with(IP_MANIFEST_FILE), true);
+        assert_eq!(change.is_structural(), true);
+    }
+}
@@ -21,7 +21,7 @@ use std::{fmt::Display, path::PathBuf};
 use crate::core::{
     blueprint::Scheme,
     ip::IpSpec,
-    lang::{lexer::Position, LangIdentifier},
+    lang::{lexer::Position, Lang, LangIdentifier},
     pkgid::PkgPart,
     version::{AnyVersion, PartialVersion, Version},
     visibility::Visibility,
@@ -63,6 +63,20 @@ pub enum Error {
     TargetNotSpecified(Hint),
     #[error("failed to execute target process: {0}")]
     TargetProcFailed(LastError),
+    #[error("failed to execute target {0:?} in the pipeline for target {1:?}: {2}")]
+    TargetPipelineFailed(String, String, LastError),
+    #[error("target {0:?} requires unknown target {1:?}{2}")]
+    TargetRequiresUnknown(String, String, Hint),
+    #[error("target {0:?} requires itself through its pipeline")]
+    TargetPipelineCycle(String),
+    #[error("unit {1:?} has no generic or parameter named {0:?}")]
+    GenericNotFound(String, String),
+    #[error("generics require a top-level unit or testbench to be set")]
+    GenericsWithoutTop,
+    #[error("failed to execute command {0:?}: {1}")]
+    CommandProcFailed(String, LastError),
+    #[error("configuration {1:?} selects design cell {0:?} that does not exist in its libraries")]
+    ConfigCellNotFound(String, String),
     #[error("failed to execute protocol process: {0}")]
     ProtocolProcFailed(LastError),
     #[error("no protocol named {0:?}")]
@@ -95,6 +109,8 @@ pub enum Error {
     GetUnitNotFound(String, Hint),
     #[error("unit \"{0}\" is not a usable design component{1}")]
     GetUnitNotComponent(String, Hint),
+    #[error("unit \"{0}\" could not be translated into {1}")]
+    UnitNotTranslatable(String, Lang),
     #[error("failed to load ip: {0}")]
     IpLoadFailed(LastError),
     #[error("failed to parse ip name: {0}")]
@@ -181,6 +197,40 @@ pub enum Error {
     OrbitHomeDoesNotExist(PathBuf),
     #[error("edge kinds are: \"unit\", \"ip\", \"all\"")]
     EdgeKindInvalid(String),
+    #[error("path {0:?} does not exist")]
+    PathNotFound(PathBuf),
+    #[error("{0} file(s) are not formatted; run \"orbit fmt\" to format them")]
+    FilesNeedFormatting(usize),
+    #[error("{0} file(s) were skipped and could not be checked for formatting")]
+    FilesNotChecked(usize),
+    #[error("the \"--add\" flag requires the \"--unit\" option")]
+    AddWithoutUnit,
+    #[error("no units in the catalog match \"{0}\"")]
+    UnitNotFoundInCatalog(String),
+    #[error("units matching \"{0}\" are provided by more than one ip: {1}")]
+    UnitFoundInManyIps(String, String),
+    #[error(
+        "version {0} is not a large enough bump from version {2}: changes to the public interface require a {1} version bump{3}"
+    )]
+    PublishVersionBumpTooSmall(Version, String, Version, Hint),
+    #[error(
+        "version {0} must be greater than version {1}, which already exists in the catalog{2}"
+    )]
+    VersionNotIncreasing(Version, Version, Hint),
+    #[error("an ip or the \"--path\" option is required to add a dependency")]
+    AddWithoutIp,
+    #[error("path {1:?} does not contain ip \"{0}\"")]
+    DependencyNotAtPath(PkgPart, PathBuf),
+    #[error("ip \"{0}\" is not a dependency of the local ip")]
+    DependencyNotFound(PkgPart),
+    #[error("ip \"{0}\" is not published to any configured channel")]
+    IpNotPublished(String),
+    #[error("a full version is required to yank ip \"{0}\"")]
+    YankWithoutVersion(String),
+    #[error("a reason is required to yank or deprecate an ip; use \"--reason\" to provide one")]
+    YankWithoutReason,
+    #[error("ip {0} is yanked and no other compatible version is available")]
+    IpOnlyYanked(IpSpec),
 }
 
 #[derive(Debug, PartialEq)]
@@ -238,6 +288,7 @@ pub enum Hint {
     ShowConfigFiles,
     ConfirmUuidChange(String),
     SolveNamespaceCollision,
+    PublishAllowBreaking,
 }
 
 impl Display for Hint {
@@ -279,6 +330,9 @@ impl Display for Hint {
             }
             Self::MakeLock => "use `orbit lock` to generate the latest lockfile for this ip",
             Self::PublishWithReady => "use the \"--ready\" flag to publish the ip to its channels",
+            Self::PublishAllowBreaking => {
+                "use the \"--allow-breaking\" flag to publish the ip regardless of its version bump"
+            }
             Self::RegenerateLockfile => "verify the ip's lockfile exists and is up to date",
             Self::ShowVersions => "use `orbit info <ip> --versions` to see all known versions",
             Self::ShowConfigFiles => {
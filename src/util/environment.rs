@@ -291,6 +291,7 @@ pub const ORBIT_TB_NAME: &str = "ORBIT_TB_NAME";
 pub const ORBIT_TB_FILE: &str = "ORBIT_TB_FILE";
 pub const ORBIT_DUT_NAME: &str = "ORBIT_DUT_NAME";
 pub const ORBIT_DUT_FILE: &str = "ORBIT_DUT_FILE";
+pub const ORBIT_GENERICS: &str = "ORBIT_GENERICS";
 
 pub const ORBIT_BLUEPRINT: &str = "ORBIT_BLUEPRINT";
 pub const ORBIT_TARGET_DIR: &str = "ORBIT_TARGET_DIR";
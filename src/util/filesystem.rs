@@ -182,6 +182,29 @@ pub fn remove_base(base: &PathBuf, full: &PathBuf) -> PathBuf {
     result.join(f_comps.as_path())
 }
 
+/// Computes the path to `target` when starting from the directory `base`.
+///
+/// Both paths are expected to be absolute and normalized.
+pub fn relative_path(base: &PathBuf, target: &PathBuf) -> PathBuf {
+    let common = base
+        .components()
+        .zip(target.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut result = PathBuf::new();
+    base.components()
+        .skip(common)
+        .for_each(|_| result.push(".."));
+    target
+        .components()
+        .skip(common)
+        .for_each(|c| result.push(c));
+    match result.as_os_str().is_empty() {
+        true => PathBuf::from("."),
+        false => result,
+    }
+}
+
 pub fn is_orbit_metadata(s: &str) -> bool {
     s == manifest::IP_MANIFEST_FILE || s == lockfile::IP_LOCK_FILE
 }
@@ -435,6 +458,20 @@ mod test {
     use super::*;
     use tempfile::tempdir;
 
+    #[test]
+    fn compute_relative_path() {
+        let base = PathBuf::from("/home/user/ips/top");
+        assert_eq!(
+            relative_path(&base, &PathBuf::from("/home/user/ips/gates")),
+            PathBuf::from("../gates")
+        );
+        assert_eq!(
+            relative_path(&base, &PathBuf::from("/home/user/ips/top/lib/uart")),
+            PathBuf::from("lib/uart")
+        );
+        assert_eq!(relative_path(&base, &base), PathBuf::from("."));
+    }
+
     #[test]
     fn resolve_path_simple() {
         // expands relative path to full path
@@ -18,6 +18,7 @@
 pub mod anyerror;
 pub mod checksum;
 pub mod compress;
+pub mod date;
 pub mod environment;
 pub mod filesystem;
 pub mod graph;
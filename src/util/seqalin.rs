@@ -73,6 +73,11 @@ fn sequence_alignment(s1: &str, s2: &str, gap_penalty: Cost, mismatch_penalty: C
     lut[s1.len()][s2.len()]
 }
 
+/// Computes the minimum number of edits to transform `s1` into `s2`.
+pub fn edit_distance(s1: &str, s2: &str) -> Cost {
+    sequence_alignment(s1, s2, 1, 1)
+}
+
 /// Given a word `s` and a known set of words `bank`, determine which word has
 /// the minimum edit distance to the given word while being below the `threshold`.
 ///
@@ -125,4 +130,11 @@ mod test {
         assert_eq!(sel_min_edit_str("cck", &bank, 3), Some("check"));
         assert_eq!(sel_min_edit_str("digt", &bank, 3), Some("digit"));
     }
+
+    #[test]
+    fn count_edits() {
+        assert_eq!(edit_distance("fifo_async", "fifo_async"), 0);
+        assert_eq!(edit_distance("fifo_asnyc", "fifo_async"), 2);
+        assert_eq!(edit_distance("fifo", "fifo_async"), 6);
+    }
 }